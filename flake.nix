@@ -24,6 +24,9 @@
             llvmPackages.bintools
             rustup
             git
+            python3
+            nodejs
+            typescript
           ];
 
           RUSTC_VERSION = overrides.toolchain.channel;
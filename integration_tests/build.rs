@@ -2,17 +2,28 @@ use std::env;
 use std::fs::File;
 use std::path::PathBuf;
 
-use codes_parser::Arguments;
+use codes_parser::{Arguments, CPlatform};
 
 fn build(source_file: &String) {
     cc::Build::new()
         .cpp(true)
         .include(env::var("OUT_DIR").unwrap())
-        .include("./c")
         .file(source_file)
         .compile("generated");
 }
 
+/// Only checks the C code of the other modes compiles, its symbols clashing with the
+/// tested library. The Zephyr allocator is shimmed by `c/zephyr/zephyr.h`.
+fn check_build(source_file: &String, library: &str) {
+    cc::Build::new()
+        .cpp(true)
+        .include(env::var("OUT_DIR").unwrap())
+        .include("./c")
+        .file(source_file)
+        .cargo_metadata(false)
+        .compile(library);
+}
+
 fn generate_bindings(header_file: &String) {
     let bindings = bindgen::Builder::default()
         .header(header_file)
@@ -34,14 +45,115 @@ fn main() {
     let output_c_source: String = out_path.join("test_output.cpp").to_str().unwrap().into();
     let output_c_header: String = out_path.join("test_output.h").to_str().unwrap().into();
     let output_rs: String = out_path.join("test_output.rs").to_str().unwrap().into();
+    let output_py: String = out_path.join("test_output.py").to_str().unwrap().into();
+    let output_ts: String = out_path.join("test_output.ts").to_str().unwrap().into();
     let opts = Arguments {
         c_header: Some(output_c_header.clone()),
         c_source: Some(output_c_source.clone()),
+        c_platform: CPlatform::Libc,
+        c_no_alloc: false,
         rust_source: Some(output_rs),
+        rust_no_std: false,
+        python_source: Some(output_py),
+        typescript_source: Some(output_ts),
+        documentation: None,
+        wireshark_dissector: None,
+        validate: false,
+        compat: None,
         input: "test.yml".into(),
     };
     codes_parser::parse_input_file_and_generate_outputs(input_file, opts).unwrap();
+    let no_std_opts = Arguments {
+        c_header: None,
+        c_source: None,
+        c_platform: CPlatform::default(),
+        c_no_alloc: false,
+        rust_source: Some(
+            out_path
+                .join("test_output_no_std.rs")
+                .to_str()
+                .unwrap()
+                .into(),
+        ),
+        rust_no_std: true,
+        python_source: None,
+        typescript_source: None,
+        documentation: None,
+        wireshark_dissector: None,
+        validate: false,
+        compat: None,
+        input: "test.yml".into(),
+    };
+    codes_parser::parse_input_file_and_generate_outputs(
+        File::open("test.yml").unwrap(),
+        no_std_opts,
+    )
+    .unwrap();
+    let output_no_alloc_source: String = out_path
+        .join("test_output_no_alloc.cpp")
+        .to_str()
+        .unwrap()
+        .into();
+    let no_alloc_opts = Arguments {
+        c_header: Some(
+            out_path
+                .join("test_output_no_alloc.h")
+                .to_str()
+                .unwrap()
+                .into(),
+        ),
+        c_source: Some(output_no_alloc_source.clone()),
+        c_platform: CPlatform::default(),
+        c_no_alloc: true,
+        rust_source: None,
+        rust_no_std: false,
+        python_source: None,
+        typescript_source: None,
+        documentation: None,
+        wireshark_dissector: None,
+        validate: false,
+        compat: None,
+        input: "test.yml".into(),
+    };
+    codes_parser::parse_input_file_and_generate_outputs(
+        File::open("test.yml").unwrap(),
+        no_alloc_opts,
+    )
+    .unwrap();
+    let output_zephyr_source: String = out_path
+        .join("test_output_zephyr.cpp")
+        .to_str()
+        .unwrap()
+        .into();
+    let zephyr_opts = Arguments {
+        c_header: Some(
+            out_path
+                .join("test_output_zephyr.h")
+                .to_str()
+                .unwrap()
+                .into(),
+        ),
+        c_source: Some(output_zephyr_source.clone()),
+        c_platform: CPlatform::Zephyr,
+        c_no_alloc: false,
+        rust_source: None,
+        rust_no_std: false,
+        python_source: None,
+        typescript_source: None,
+        documentation: None,
+        wireshark_dissector: None,
+        validate: false,
+        compat: None,
+        input: "test.yml".into(),
+    };
+    codes_parser::parse_input_file_and_generate_outputs(
+        File::open("test.yml").unwrap(),
+        zephyr_opts,
+    )
+    .unwrap();
     build(&output_c_source);
+    check_build(&output_no_alloc_source, "generated_no_alloc");
+    check_build(&output_zephyr_source, "generated_zephyr");
     generate_bindings(&output_c_header);
 
     println!("cargo::rustc-link-search=native={out_path_string}");
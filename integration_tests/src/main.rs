@@ -1,5 +1,8 @@
 mod test_c;
+mod test_python;
 mod test_rust;
+mod test_rust_no_std;
+mod test_typescript;
 
 #[cfg(test)]
 pub fn match_buffers(expected: &[i8], buffer: &[i8], len: usize) {
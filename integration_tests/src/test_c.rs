@@ -99,7 +99,7 @@ fn parse_feedback_sleeping_frame() {
     let mut buffer: [i8; 2] = [0x03, 0x01];
     let mut code: u32 = 0;
     let parameters: s_fb_sleeppin_params;
-    let mut ptr = ptr::null_mut::<c_void>();
+    let mut ptr = ptr::null_mut::<Feedbacks_params>();
 
     let result = unsafe {
         parse_feedback_frame(
@@ -127,7 +127,7 @@ fn parse_feedback_sleeping_frame() {
     );
 
     unsafe {
-        free(ptr);
+        free(ptr as *mut c_void);
     }
 }
 
@@ -135,7 +135,7 @@ fn parse_feedback_sleeping_frame() {
 fn parse_invalid_sleeping_frame_length() {
     let mut buf: [i8; 1] = [0x03];
     let mut code: u32 = 0;
-    let mut ptr = ptr::null_mut::<c_void>();
+    let mut ptr = ptr::null_mut::<Feedbacks_params>();
 
     let result =
         unsafe { parse_feedback_frame(buf.as_mut_ptr(), buf.len() as i32, &mut code, &mut ptr) };
@@ -150,7 +150,7 @@ fn parse_invalid_sleeping_frame_length() {
 fn parse_invalid_feedback_frame_code() {
     let mut buf = [0x50i8, 0x00, 0x00, 0x00, 0x00];
     let mut code = 0u32;
-    let mut ptr = ptr::null_mut::<c_void>();
+    let mut ptr = ptr::null_mut::<Feedbacks_params>();
 
     let result =
         unsafe { parse_feedback_frame(buf.as_mut_ptr(), buf.len() as i32, &mut code, &mut ptr) };
@@ -165,7 +165,7 @@ fn parse_invalid_feedback_frame_code() {
 fn parse_invalid_instruction_frame_code() {
     let mut buf = [0x50i8, 0x00, 0x00, 0x00, 0x00];
     let mut code = 0u32;
-    let mut ptr = ptr::null_mut::<c_void>();
+    let mut ptr = ptr::null_mut::<Instructions_params>();
 
     let result =
         unsafe { parse_instruction_frame(buf.as_mut_ptr(), buf.len() as i32, &mut code, &mut ptr) };
@@ -180,7 +180,7 @@ fn parse_invalid_instruction_frame_code() {
 fn parse_instruction_sleeping_frame() {
     let mut buf = [0x03i8, 120, 0, 1];
     let mut code = 0u32;
-    let mut ptr = ptr::null_mut::<c_void>();
+    let mut ptr = ptr::null_mut::<Instructions_params>();
     let expected_struct = s_inst_sleeppin_params {
         pre_sleep_time: 120,
         wake_pin_active_state: true,
@@ -190,11 +190,7 @@ fn parse_instruction_sleeping_frame() {
         unsafe { parse_instruction_frame(buf.as_mut_ptr(), buf.len() as i32, &mut code, &mut ptr) };
 
     assert_eq!(0, result, "Frame parsing should succeed");
-    assert_ne!(
-        ptr::null_mut::<c_void>(),
-        ptr,
-        "Should have allocated some pointer"
-    );
+    assert_ne!(ptr::null_mut(), ptr, "Should have allocated some pointer");
     assert_eq!(
         __instructions_enum_INST_SLEEPPIN, code,
         "Instruction code should match Sleepin instruction"
@@ -206,7 +202,7 @@ fn parse_instruction_sleeping_frame() {
         "Decoded structure should match the expected values"
     );
     unsafe {
-        free(ptr);
+        free(ptr as *mut c_void);
     }
 }
 
@@ -248,7 +244,7 @@ fn build_sleeptime_feedback() {
 #[test]
 fn parse_feedback_sleeptime() {
     let mut frame_to_parse = [4i8, 0x7e];
-    let mut ptr = ptr::null_mut::<c_void>();
+    let mut ptr = ptr::null_mut::<Feedbacks_params>();
     let mut code = 0u32;
 
     let result = unsafe {
@@ -275,7 +271,7 @@ fn parse_feedback_sleeptime() {
         "Decoded frame should match expected one"
     );
 
-    unsafe { free(ptr) };
+    unsafe { free(ptr as *mut c_void) };
 }
 
 #[test]
@@ -306,7 +302,7 @@ fn parse_instruction_sleeptime() {
     };
     let mut buf = [0x04i8, 10, 0, -0x18, 0x03, 0, 0];
     let mut code = 0u32;
-    let mut ptr = ptr::null_mut::<c_void>();
+    let mut ptr = ptr::null_mut::<Instructions_params>();
 
     let result =
         unsafe { parse_instruction_frame(buf.as_mut_ptr(), buf.len() as i32, &mut code, &mut ptr) };
@@ -325,7 +321,7 @@ fn parse_instruction_sleeptime() {
         "Decoded frame should match expectation"
     );
 
-    unsafe { free(ptr) };
+    unsafe { free(ptr as *mut c_void) };
 }
 
 #[test]
@@ -347,7 +343,7 @@ fn build_instruction_getversion() {
 fn parse_instruction_getversion() {
     let mut buf = [0i8];
     let mut code = 5u32;
-    let mut ptr = ptr::null_mut::<c_void>();
+    let mut ptr = ptr::null_mut::<Instructions_params>();
 
     let result =
         unsafe { parse_instruction_frame(buf.as_mut_ptr(), buf.len() as i32, &mut code, &mut ptr) };
@@ -360,7 +356,7 @@ fn parse_instruction_getversion() {
         "Should have GetVersion instruction id"
     );
 
-    unsafe { free(ptr) };
+    unsafe { free(ptr as *mut c_void) };
 }
 
 #[test]
@@ -386,7 +382,7 @@ fn build_feedback_getversion() {
 fn parse_feedback_getversion() {
     let mut buf = [0i8, 1, 0, 99];
     let mut code = 6u32;
-    let mut ptr = ptr::null_mut::<c_void>();
+    let mut ptr = ptr::null_mut::<Feedbacks_params>();
 
     let expected_struct = s_fb_getversion_params {
         major: 1,
@@ -409,7 +405,7 @@ fn parse_feedback_getversion() {
         expected_struct, decoded_struct,
         "Decoded structure should match expected one"
     );
-    unsafe { free(ptr) };
+    unsafe { free(ptr as *mut c_void) };
 }
 
 #[test]
@@ -467,7 +463,7 @@ fn parse_coverage_inst() {
     .collect::<Vec<i8>>();
 
     let mut code = 0u32;
-    let mut ptr = ptr::null_mut::<c_void>();
+    let mut ptr = ptr::null_mut::<Instructions_params>();
 
     let result = unsafe {
         parse_instruction_frame(
@@ -491,7 +487,7 @@ fn parse_coverage_inst() {
 
     unsafe {
         free(data.a_string as *mut c_void);
-        free(ptr);
+        free(ptr as *mut c_void);
     }
 }
 
@@ -553,7 +549,7 @@ fn parse_coverage_fb() {
     .map(|i| *i as i8)
     .collect::<Vec<i8>>();
 
-    let mut ptr = ptr::null_mut::<c_void>();
+    let mut ptr = ptr::null_mut::<Feedbacks_params>();
     let mut code = 0u32;
 
     let result = unsafe {
@@ -585,7 +581,187 @@ fn parse_invalid_string() {
     .collect::<Vec<i8>>();
 
     let mut code = 0u32;
-    let mut ptr = ptr::null_mut::<c_void>();
+    let mut ptr = ptr::null_mut::<Instructions_params>();
+
+    let result = unsafe {
+        parse_instruction_frame(
+            encoded.as_mut_ptr(),
+            encoded.len() as i32,
+            &mut code,
+            &mut ptr,
+        )
+    };
+
+    assert_ne!(0, result, "Parsing should fail");
+    assert_eq!(ptr::null_mut(), ptr, "Should have freed allocated memory");
+}
+
+#[test]
+fn generate_array_inst() {
+    let mut readings = [0x0102u16, 3];
+    let mut label_a = [b'a' as i8, 0];
+    let mut label_bc = [b'b' as i8, b'c' as i8, 0];
+    let mut labels = [label_a.as_mut_ptr(), label_bc.as_mut_ptr()];
+    let mut input_struct = s_inst_arraytest_params {
+        readings: readings.as_mut_ptr(),
+        readings_count: readings.len(),
+        labels: labels.as_mut_ptr(),
+        labels_count: labels.len(),
+    };
+
+    let mut buffer = [0i8; 255];
+    let mut len = buffer.len() as i32;
+
+    let result = unsafe {
+        build_instruction_arraytest_frame(buffer.as_mut_ptr(), &mut len, &mut input_struct)
+    };
+
+    assert_eq!(0, result, "Generation should success");
+
+    let expected = [5u8, 2, 0x02, 0x01, 3, 0, 2, 0, b'a', 0, b'b', b'c', 0]
+        .iter()
+        .map(|u| *u as i8)
+        .collect::<Vec<i8>>();
+
+    match_buffers(&expected, &buffer, len as usize);
+}
+
+#[test]
+fn generate_array_too_small_buffer() {
+    let mut registers = [0i32; 2];
+    let mut input_struct = s_fb_arraytest_params {
+        registers: registers.as_mut_ptr(),
+        registers_count: registers.len(),
+        status: 0,
+    };
+
+    let mut buffer = [0i8; 8];
+    let mut len = buffer.len() as i32;
+
+    let result =
+        unsafe { build_feedback_arraytest_frame(buffer.as_mut_ptr(), &mut len, &mut input_struct) };
+
+    assert_ne!(0, result, "Generation should fail due to too small buffer");
+}
+
+#[test]
+fn parse_array_inst() {
+    let mut encoded = [5u8, 2, 0x02, 0x01, 3, 0, 2, 0, b'a', 0, b'b', b'c', 0]
+        .iter()
+        .map(|u| *u as i8)
+        .collect::<Vec<i8>>();
+
+    let mut code = 0u32;
+    let mut ptr = ptr::null_mut::<Instructions_params>();
+
+    let result = unsafe {
+        parse_instruction_frame(
+            encoded.as_mut_ptr(),
+            encoded.len() as i32,
+            &mut code,
+            &mut ptr,
+        )
+    };
+
+    assert_eq!(0, result, "Parsing should success");
+    assert_eq!(__instructions_enum_INST_ARRAYTEST, code);
+    assert_ne!(ptr::null_mut(), ptr);
+
+    let decoded = unsafe { *(ptr as *mut s_inst_arraytest_params) };
+    assert_eq!(2, decoded.readings_count);
+    assert_eq!(2, decoded.labels_count);
+
+    unsafe {
+        let readings = std::slice::from_raw_parts(decoded.readings, decoded.readings_count);
+        assert_eq!(&[0x0102u16, 3], readings);
+
+        let labels = std::slice::from_raw_parts(decoded.labels, decoded.labels_count);
+        assert_eq!(0, strcmp(labels[0], c"a".as_ptr()));
+        assert_eq!(0, strcmp(labels[1], c"bc".as_ptr()));
+
+        for label in labels {
+            free(*label as *mut c_void);
+        }
+        free(decoded.labels as *mut c_void);
+        free(decoded.readings as *mut c_void);
+        free(ptr as *mut c_void);
+    }
+}
+
+#[test]
+fn parse_array_inst_into_params_union() {
+    let mut encoded = [5u8, 2, 0x02, 0x01, 3, 0, 2, 0, b'a', 0, b'b', b'c', 0]
+        .iter()
+        .map(|u| *u as i8)
+        .collect::<Vec<i8>>();
+
+    let mut code = 0u32;
+    let mut ptr = ptr::null_mut::<Instructions_params>();
+
+    let result = unsafe {
+        parse_instruction_frame(
+            encoded.as_mut_ptr(),
+            encoded.len() as i32,
+            &mut code,
+            &mut ptr,
+        )
+    };
+
+    assert_eq!(0, result, "Parsing should success");
+    assert_eq!(__instructions_enum_INST_ARRAYTEST, code);
+
+    unsafe {
+        assert_eq!(code, (*ptr).code);
+        let decoded = &(*ptr).params.arraytest;
+        let labels = std::slice::from_raw_parts(decoded.labels, decoded.labels_count);
+        assert_eq!(0, strcmp(labels[1], c"bc".as_ptr()));
+
+        // Releases the labels, the readings and the parameters
+        free_instruction_params(ptr);
+        free_instruction_params(ptr::null_mut());
+    }
+}
+
+#[test]
+fn free_array_inst_params_twice() {
+    let mut encoded = [5u8, 2, 0x02, 0x01, 3, 0, 2, 0, b'a', 0, b'b', b'c', 0]
+        .iter()
+        .map(|u| *u as i8)
+        .collect::<Vec<i8>>();
+
+    let mut code = 0u32;
+    let mut ptr = ptr::null_mut::<Instructions_params>();
+
+    let result = unsafe {
+        parse_instruction_frame(
+            encoded.as_mut_ptr(),
+            encoded.len() as i32,
+            &mut code,
+            &mut ptr,
+        )
+    };
+
+    assert_eq!(0, result, "Parsing should success");
+
+    unsafe {
+        let decoded = &mut (*ptr).params.arraytest;
+        free_instruction_arraytest_params(decoded);
+        assert_eq!(ptr::null_mut(), decoded.labels);
+        assert_eq!(0, decoded.labels_count);
+        assert_eq!(ptr::null_mut(), decoded.readings);
+
+        // Released strings and arrays aren't released again
+        free_instruction_arraytest_params(decoded);
+        free_instruction_params(ptr);
+    }
+}
+
+#[test]
+fn parse_array_longer_than_frame() {
+    let mut encoded = [5i8, 100, 0x02, 0x01, 3, 0];
+
+    let mut code = 0u32;
+    let mut ptr = ptr::null_mut::<Instructions_params>();
 
     let result = unsafe {
         parse_instruction_frame(
@@ -599,3 +775,527 @@ fn parse_invalid_string() {
     assert_ne!(0, result, "Parsing should fail");
     assert_eq!(ptr::null_mut(), ptr, "Should have freed allocated memory");
 }
+
+#[test]
+fn parse_failing_after_allocation_zeroes_the_parameters() {
+    // The readings are allocated, then the second label is truncated
+    let mut encoded = [5u8, 2, 0x02, 0x01, 3, 0, 2, 0, b'a', 0, b'b']
+        .iter()
+        .map(|u| *u as i8)
+        .collect::<Vec<i8>>();
+
+    // Garbage left by the caller, which would crash if released
+    let mut decoded = s_inst_arraytest_params {
+        readings: 0x10 as *mut u16,
+        readings_count: 3,
+        labels: 0x20 as *mut *mut i8,
+        labels_count: 5,
+    };
+
+    let result = unsafe {
+        parse_instruction_arraytest_frame(encoded.as_mut_ptr(), encoded.len() as i32, &mut decoded)
+    };
+
+    assert_ne!(0, result, "Parsing should fail");
+    assert_eq!(ptr::null_mut(), decoded.readings);
+    assert_eq!(0, decoded.readings_count);
+    assert_eq!(ptr::null_mut(), decoded.labels);
+    assert_eq!(0, decoded.labels_count);
+}
+
+#[test]
+fn parse_failing_ignores_the_previous_parameters() {
+    // No label, then a truncated date
+    let mut encoded = [11u8, 0, 0, 1, 0xe7]
+        .iter()
+        .map(|u| *u as i8)
+        .collect::<Vec<i8>>();
+
+    let mut decoded: s_inst_optionaltest_params = unsafe { std::mem::zeroed() };
+    decoded.label = 0x20 as *mut i8;
+    decoded.has_label = true;
+
+    let result = unsafe {
+        parse_instruction_optionaltest_frame(
+            encoded.as_mut_ptr(),
+            encoded.len() as i32,
+            &mut decoded,
+        )
+    };
+
+    assert_ne!(0, result, "Parsing should fail");
+    assert_eq!(ptr::null_mut(), decoded.label);
+    assert!(!decoded.has_label);
+}
+
+#[test]
+fn generate_fixed_array_inst() {
+    let mut input_struct = s_inst_fixedarraytest_params {
+        device_id: [0xa5; 16],
+        calibration: [1, -1, 256],
+        key: [7; 40],
+    };
+
+    let mut buffer = [0i8; 255];
+    let mut len = buffer.len() as i32;
+
+    let result = unsafe {
+        build_instruction_fixedarraytest_frame(buffer.as_mut_ptr(), &mut len, &mut input_struct)
+    };
+
+    assert_eq!(0, result, "Generation should success");
+
+    let expected = [
+        &[6u8] as &[u8],
+        &[0xa5; 16],
+        &[1, 0, 0xff, 0xff, 0, 1],
+        &[7; 40],
+    ]
+    .concat()
+    .iter()
+    .map(|u| *u as i8)
+    .collect::<Vec<i8>>();
+
+    match_buffers(&expected, &buffer, len as usize);
+}
+
+#[test]
+fn parse_fixed_array_fb() {
+    let mut encoded = [6u8, b'a', 0, b'b', 0, 1, 2, 3, 4, 5, 6]
+        .iter()
+        .map(|u| *u as i8)
+        .collect::<Vec<i8>>();
+
+    let mut code = 0u32;
+    let mut ptr = ptr::null_mut::<Feedbacks_params>();
+
+    let result = unsafe {
+        parse_feedback_frame(
+            encoded.as_mut_ptr(),
+            encoded.len() as i32,
+            &mut code,
+            &mut ptr,
+        )
+    };
+
+    assert_eq!(0, result, "Parsing should success");
+    assert_eq!(__feedbacks_enum_FB_FIXEDARRAYTEST, code);
+    assert_ne!(ptr::null_mut(), ptr);
+
+    let decoded = unsafe { *(ptr as *mut s_fb_fixedarraytest_params) };
+    assert_eq!([1u8, 2, 3, 4, 5, 6], decoded.mac);
+
+    unsafe {
+        assert_eq!(0, strcmp(decoded.labels[0], c"a".as_ptr()));
+        assert_eq!(0, strcmp(decoded.labels[1], c"b".as_ptr()));
+
+        for label in decoded.labels {
+            free(label as *mut c_void);
+        }
+        free(ptr as *mut c_void);
+    }
+}
+
+#[test]
+fn parse_fixed_array_too_short() {
+    let mut encoded = [6i8, b'a' as i8, 0, b'b' as i8, 0, 1, 2, 3, 4, 5];
+
+    let mut code = 0u32;
+    let mut ptr = ptr::null_mut::<Feedbacks_params>();
+
+    let result = unsafe {
+        parse_feedback_frame(
+            encoded.as_mut_ptr(),
+            encoded.len() as i32,
+            &mut code,
+            &mut ptr,
+        )
+    };
+
+    assert_ne!(0, result, "Parsing should fail");
+    assert_eq!(ptr::null_mut(), ptr, "Should have freed allocated memory");
+}
+
+#[test]
+fn generate_float_inst() {
+    let mut input_struct = s_inst_floattest_params {
+        setpoint: 1.5,
+        gain: -2.0,
+    };
+
+    let mut buffer = [0i8; 255];
+    let mut len = buffer.len() as i32;
+
+    let result = unsafe {
+        build_instruction_floattest_frame(buffer.as_mut_ptr(), &mut len, &mut input_struct)
+    };
+
+    assert_eq!(0, result, "Generation should success");
+
+    let expected = [7u8, 0, 0, 0xc0, 0x3f, 0, 0, 0, 0, 0, 0, 0, 0xc0]
+        .iter()
+        .map(|u| *u as i8)
+        .collect::<Vec<i8>>();
+
+    match_buffers(&expected, &buffer, len as usize);
+}
+
+#[test]
+fn parse_float_inst() {
+    let mut encoded = [7u8, 0, 0, 0xc0, 0x3f, 0, 0, 0, 0, 0, 0, 0, 0xc0]
+        .iter()
+        .map(|u| *u as i8)
+        .collect::<Vec<i8>>();
+
+    let mut code = 0u32;
+    let mut ptr = ptr::null_mut::<Instructions_params>();
+
+    let result = unsafe {
+        parse_instruction_frame(
+            encoded.as_mut_ptr(),
+            encoded.len() as i32,
+            &mut code,
+            &mut ptr,
+        )
+    };
+
+    assert_eq!(0, result, "Parsing should success");
+    assert_eq!(__instructions_enum_INST_FLOATTEST, code);
+    assert_ne!(ptr::null_mut(), ptr);
+
+    let decoded = unsafe { *(ptr as *mut s_inst_floattest_params) };
+    assert_eq!(1.5, decoded.setpoint);
+    assert_eq!(-2.0, decoded.gain);
+
+    unsafe { free(ptr as *mut c_void) };
+}
+
+#[test]
+fn generate_enum_inst() {
+    let mut input_struct = s_inst_enumtest_params {
+        mode: __sleepmode_enum_SLEEPMODE_HIBERNATE,
+        offset: __offset_enum_OFFSET_NEGATIVE,
+    };
+
+    let mut buffer = [0i8; 255];
+    let mut len = buffer.len() as i32;
+
+    let result = unsafe {
+        build_instruction_enumtest_frame(buffer.as_mut_ptr(), &mut len, &mut input_struct)
+    };
+
+    assert_eq!(0, result, "Generation should success");
+
+    let expected = [8u8, 7, 0xd4, 0xfe]
+        .iter()
+        .map(|u| *u as i8)
+        .collect::<Vec<i8>>();
+
+    match_buffers(&expected, &buffer, len as usize);
+}
+
+#[test]
+fn parse_enum_inst() {
+    let mut encoded = [8i8, 7, 0x2c, 0x01];
+
+    let mut code = 0u32;
+    let mut ptr = ptr::null_mut::<Instructions_params>();
+
+    let result = unsafe {
+        parse_instruction_frame(
+            encoded.as_mut_ptr(),
+            encoded.len() as i32,
+            &mut code,
+            &mut ptr,
+        )
+    };
+
+    assert_eq!(0, result, "Parsing should success");
+    assert_eq!(__instructions_enum_INST_ENUMTEST, code);
+    assert_ne!(ptr::null_mut(), ptr);
+
+    let decoded = unsafe { *(ptr as *mut s_inst_enumtest_params) };
+    assert_eq!(__sleepmode_enum_SLEEPMODE_HIBERNATE, decoded.mode);
+    assert_eq!(__offset_enum_OFFSET_POSITIVE, decoded.offset);
+
+    unsafe { free(ptr as *mut c_void) };
+}
+
+#[test]
+fn parse_unknown_enum_value() {
+    let mut encoded = [8i8, 2, 0x2c, 0x01];
+
+    let mut code = 0u32;
+    let mut ptr = ptr::null_mut::<Instructions_params>();
+
+    let result = unsafe {
+        parse_instruction_frame(
+            encoded.as_mut_ptr(),
+            encoded.len() as i32,
+            &mut code,
+            &mut ptr,
+        )
+    };
+
+    assert_eq!(
+        FRAME_ERROR_UNKNOWN_ENUM_VALUE, result,
+        "Parsing should fail on the unknown enumeration value"
+    );
+    assert_eq!(ptr::null_mut(), ptr, "Should have freed allocated memory");
+}
+
+#[test]
+fn generate_struct_inst() {
+    let mut input_struct = s_inst_structtest_params {
+        date: RtcDate {
+            year: 2024,
+            month: 10,
+            day: 18,
+        },
+    };
+
+    let mut buffer = [0i8; 255];
+    let mut len = buffer.len() as i32;
+
+    let result = unsafe {
+        build_instruction_structtest_frame(buffer.as_mut_ptr(), &mut len, &mut input_struct)
+    };
+
+    assert_eq!(0, result, "Generation should success");
+
+    let expected = [9u8, 0xe8, 0x07, 10, 18]
+        .iter()
+        .map(|u| *u as i8)
+        .collect::<Vec<i8>>();
+
+    match_buffers(&expected, &buffer, len as usize);
+}
+
+#[test]
+fn parse_struct_inst() {
+    let mut encoded = [9i8, 0xe8u8 as i8, 0x07, 10, 18];
+
+    let mut code = 0u32;
+    let mut ptr = ptr::null_mut::<Instructions_params>();
+
+    let result = unsafe {
+        parse_instruction_frame(
+            encoded.as_mut_ptr(),
+            encoded.len() as i32,
+            &mut code,
+            &mut ptr,
+        )
+    };
+
+    assert_eq!(0, result, "Parsing should success");
+    assert_eq!(__instructions_enum_INST_STRUCTTEST, code);
+    assert_ne!(ptr::null_mut(), ptr);
+
+    let decoded = unsafe { *(ptr as *mut s_inst_structtest_params) };
+    assert_eq!(2024, decoded.date.year);
+    assert_eq!(10, decoded.date.month);
+    assert_eq!(18, decoded.date.day);
+
+    unsafe { free(ptr as *mut c_void) };
+}
+
+#[test]
+fn parse_truncated_nested_struct_fb() {
+    let mut encoded = [
+        9i8,
+        0xe8u8 as i8,
+        0x07,
+        1,
+        2,
+        0x62,
+        0x6f,
+        0x6f,
+        0x74,
+        0,
+        1,
+        1,
+        0xe8u8 as i8,
+    ];
+
+    let mut code = 0u32;
+    let mut ptr = ptr::null_mut::<Feedbacks_params>();
+
+    let result = unsafe {
+        parse_feedback_frame(
+            encoded.as_mut_ptr(),
+            encoded.len() as i32,
+            &mut code,
+            &mut ptr,
+        )
+    };
+
+    assert_eq!(-1, result, "Parsing should fail on the truncated frame");
+    assert_eq!(ptr::null_mut(), ptr, "Should have freed allocated memory");
+}
+
+#[test]
+fn generate_bitfield_inst() {
+    let status = (1 << STATUS_CHARGING_SHIFT) | (5 << STATUS_LEVEL_SHIFT);
+    let mut input_struct = s_inst_bitfieldtest_params {
+        status: status as Status,
+    };
+
+    let mut buffer = [0i8; 255];
+    let mut len = buffer.len() as i32;
+
+    let result = unsafe {
+        build_instruction_bitfieldtest_frame(buffer.as_mut_ptr(), &mut len, &mut input_struct)
+    };
+
+    assert_eq!(0, result, "Generation should success");
+    match_buffers(&[10i8, 0b0_101_1], &buffer, len as usize);
+}
+
+#[test]
+fn parse_bitfield_inst() {
+    let mut encoded = [10i8, 0b1_011_0];
+
+    let mut code = 0u32;
+    let mut ptr = ptr::null_mut::<Instructions_params>();
+
+    let result = unsafe {
+        parse_instruction_frame(
+            encoded.as_mut_ptr(),
+            encoded.len() as i32,
+            &mut code,
+            &mut ptr,
+        )
+    };
+
+    assert_eq!(0, result, "Parsing should success");
+    assert_eq!(__instructions_enum_INST_BITFIELDTEST, code);
+
+    let decoded = unsafe { *(ptr as *mut s_inst_bitfieldtest_params) };
+    let status = decoded.status as u32;
+    assert_eq!(0, (status >> STATUS_CHARGING_SHIFT) & STATUS_CHARGING_MASK);
+    assert_eq!(3, (status >> STATUS_LEVEL_SHIFT) & STATUS_LEVEL_MASK);
+    assert_eq!(1, (status >> STATUS_ERROR_SHIFT) & STATUS_ERROR_MASK);
+
+    unsafe { free(ptr as *mut c_void) };
+}
+
+#[test]
+fn generate_optional_inst() {
+    let mut input_struct = s_inst_optionaltest_params {
+        level: 3,
+        has_level: true,
+        label: ptr::null_mut(),
+        has_label: false,
+        date: RtcDate {
+            year: 2024,
+            month: 10,
+            day: 18,
+        },
+        has_date: true,
+    };
+
+    let mut buffer = [0i8; 255];
+    let mut len = buffer.len() as i32;
+
+    let result = unsafe {
+        build_instruction_optionaltest_frame(buffer.as_mut_ptr(), &mut len, &mut input_struct)
+    };
+
+    assert_eq!(0, result, "Generation should success");
+
+    let expected = [11u8, 1, 3, 0, 1, 0xe8, 0x07, 10, 18]
+        .iter()
+        .map(|u| *u as i8)
+        .collect::<Vec<i8>>();
+
+    match_buffers(&expected, &buffer, len as usize);
+}
+
+#[test]
+fn parse_optional_fb() {
+    let mut encoded = [11i8, 1, 2, 1, 0, 2, 0, 7];
+
+    let mut code = 0u32;
+    let mut ptr = ptr::null_mut::<Feedbacks_params>();
+
+    let result = unsafe {
+        parse_feedback_frame(
+            encoded.as_mut_ptr(),
+            encoded.len() as i32,
+            &mut code,
+            &mut ptr,
+        )
+    };
+
+    assert_eq!(0, result, "Parsing should success");
+    assert_eq!(__feedbacks_enum_FB_OPTIONALTEST, code);
+
+    let decoded = unsafe { *(ptr as *mut s_fb_optionaltest_params) };
+    assert!(decoded.has_readings);
+    assert_eq!(2, decoded.readings_count);
+    assert_eq!(&[1u16, 2], unsafe {
+        std::slice::from_raw_parts(decoded.readings, decoded.readings_count)
+    });
+    assert_eq!(7, decoded.status);
+
+    unsafe {
+        free(decoded.readings as *mut c_void);
+        free(ptr as *mut c_void)
+    };
+}
+
+#[cfg(test)]
+unsafe extern "C" fn answer_getversion(
+    _instruction: *const s_inst_getversion_params,
+    feedback: *mut s_fb_getversion_params,
+    ctx: *mut c_void,
+) -> i32 {
+    unsafe {
+        (*feedback).major = 1;
+        (*feedback).patch = *(ctx as *mut u8);
+    }
+    0
+}
+
+#[test]
+fn dispatch_getversion_instruction() {
+    let mut handlers: instruction_handlers = unsafe { std::mem::zeroed() };
+    handlers.on_getversion = Some(answer_getversion);
+    let mut patch = 99u8;
+    let mut instruction = [0i8];
+    let mut feedback = [0i8; 8];
+    let mut len = feedback.len() as i32;
+
+    let result = unsafe {
+        dispatch_instruction_frame(
+            instruction.as_mut_ptr(),
+            instruction.len() as i32,
+            &handlers,
+            &mut patch as *mut u8 as *mut c_void,
+            feedback.as_mut_ptr(),
+            &mut len,
+        )
+    };
+
+    assert_eq!(0, result, "Dispatch should success");
+    match_buffers(&[0, 1, 0, 99], &feedback, len as usize);
+}
+
+#[test]
+fn dispatch_instruction_without_handler() {
+    let handlers: instruction_handlers = unsafe { std::mem::zeroed() };
+    let mut instruction = [0i8];
+
+    let result = unsafe {
+        dispatch_instruction_frame(
+            instruction.as_mut_ptr(),
+            instruction.len() as i32,
+            &handlers,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            ptr::null_mut(),
+        )
+    };
+
+    assert_eq!(FRAME_ERROR_NO_HANDLER, result);
+}
@@ -0,0 +1,18 @@
+#[cfg(test)]
+use std::process::Command;
+
+/// Runs the round trips of `test_python.py` on the Python module generated from test.yml
+#[test]
+fn python_frames_round_trip() {
+    let output = Command::new("python3")
+        .arg("test_python.py")
+        .arg(concat!(env!("OUT_DIR"), "/test_output.py"))
+        .output()
+        .expect("python3 should be installed");
+
+    assert!(
+        output.status.success(),
+        "Python tests failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
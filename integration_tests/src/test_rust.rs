@@ -221,3 +221,665 @@ fn parse_coverage_fb() {
 
     assert_eq!(expected_struct, decoded);
 }
+
+#[test]
+fn generate_array_inst() {
+    let frame = Instructions::ArrayTest {
+        readings: vec![0x0102, 3],
+        labels: vec!["a".into(), "bc".into()],
+    };
+
+    let encoded = frame.to_bytes();
+    let expected = [5u8, 2, 0x02, 0x01, 3, 0, 2, 0, b'a', 0, b'b', b'c', 0];
+
+    match_buffers(&expected, &encoded);
+}
+
+#[test]
+fn parse_array_inst() {
+    let encoded = [5u8, 2, 0x02, 0x01, 3, 0, 2, 0, b'a', 0, b'b', b'c', 0];
+    let expected = Instructions::ArrayTest {
+        readings: vec![0x0102, 3],
+        labels: vec!["a".into(), "bc".into()],
+    };
+
+    let decoded = Instructions::from_bytes(&encoded).expect("Should sucessfully parse the frame");
+
+    assert_eq!(expected, decoded);
+}
+
+#[test]
+fn generate_empty_array_fb() {
+    let frame = Feedbacks::ArrayTest {
+        registers: vec![],
+        status: 7,
+    };
+
+    let encoded = frame.to_bytes();
+    let expected = [5u8, 0, 0, 0, 0, 7];
+
+    match_buffers(&expected, &encoded);
+}
+
+#[test]
+fn parse_array_fb() {
+    let encoded = [5u8, 1, 0, 0, 0, 0xfe, 0xff, 0xff, 0xff, 1];
+    let expected = Feedbacks::ArrayTest {
+        registers: vec![-2],
+        status: 1,
+    };
+
+    let decoded = Feedbacks::from_bytes(&encoded).expect("Should sucessfully parse the frame");
+
+    assert_eq!(expected, decoded);
+}
+
+#[test]
+fn parse_array_longer_than_frame() {
+    let encoded = [5u8, 3, 0x02, 0x01, 3, 0, 0, 0];
+
+    assert!(Instructions::from_bytes(&encoded).is_err());
+}
+
+#[test]
+fn generate_fixed_array_inst() {
+    let frame = Instructions::FixedArrayTest {
+        device_id: [0xa5; 16],
+        calibration: [1, -1, 256],
+        key: [7; 40],
+    };
+
+    let encoded = frame.to_bytes();
+    let expected = [
+        &[6u8] as &[u8],
+        &[0xa5; 16],
+        &[1, 0, 0xff, 0xff, 0, 1],
+        &[7; 40],
+    ]
+    .concat();
+
+    match_buffers(&expected, &encoded);
+}
+
+#[test]
+fn parse_fixed_array_inst() {
+    let encoded = [
+        &[6u8] as &[u8],
+        &[0xa5; 16],
+        &[1, 0, 0xff, 0xff, 0, 1],
+        &[7; 40],
+    ]
+    .concat();
+    let expected = Instructions::FixedArrayTest {
+        device_id: [0xa5; 16],
+        calibration: [1, -1, 256],
+        key: [7; 40],
+    };
+
+    let decoded = Instructions::from_bytes(&encoded).expect("Should sucessfully parse the frame");
+
+    assert_eq!(expected, decoded);
+}
+
+#[test]
+fn generate_fixed_array_fb() {
+    let frame = Feedbacks::FixedArrayTest {
+        labels: ["a".into(), "b".into()],
+        mac: [1, 2, 3, 4, 5, 6],
+    };
+
+    let encoded = frame.to_bytes();
+    let expected = [6u8, b'a', 0, b'b', 0, 1, 2, 3, 4, 5, 6];
+
+    match_buffers(&expected, &encoded);
+}
+
+#[test]
+fn parse_fixed_array_fb() {
+    let encoded = [6u8, b'a', 0, b'b', 0, 1, 2, 3, 4, 5, 6];
+    let expected = Feedbacks::FixedArrayTest {
+        labels: ["a".into(), "b".into()],
+        mac: [1, 2, 3, 4, 5, 6],
+    };
+
+    let decoded = Feedbacks::from_bytes(&encoded).expect("Should sucessfully parse the frame");
+
+    assert_eq!(expected, decoded);
+}
+
+#[test]
+fn parse_fixed_array_too_short() {
+    let encoded = [6u8, b'a', 0, b'b', 0, 1, 2, 3, 4, 5];
+
+    assert!(Feedbacks::from_bytes(&encoded).is_err());
+}
+
+#[test]
+fn generate_float_inst() {
+    let frame = Instructions::FloatTest {
+        setpoint: 1.5,
+        gain: -2.0,
+    };
+
+    let encoded = frame.to_bytes();
+    let expected = [7u8, 0, 0, 0xc0, 0x3f, 0, 0, 0, 0, 0, 0, 0, 0xc0];
+
+    match_buffers(&expected, &encoded);
+}
+
+#[test]
+fn parse_float_inst() {
+    let encoded = [7u8, 0, 0, 0xc0, 0x3f, 0, 0, 0, 0, 0, 0, 0, 0xc0];
+    let expected = Instructions::FloatTest {
+        setpoint: 1.5,
+        gain: -2.0,
+    };
+
+    let decoded = Instructions::from_bytes(&encoded).expect("Should sucessfully parse the frame");
+
+    assert_eq!(expected, decoded);
+}
+
+#[test]
+fn generate_float_fb() {
+    let frame = Feedbacks::FloatTest {
+        temperatures: vec![0.25, -1.0],
+    };
+
+    let encoded = frame.to_bytes();
+    let expected = [7u8, 2, 0, 0, 0x80, 0x3e, 0, 0, 0x80, 0xbf];
+
+    match_buffers(&expected, &encoded);
+}
+
+#[test]
+fn parse_float_fb() {
+    let encoded = [7u8, 2, 0, 0, 0x80, 0x3e, 0, 0, 0x80, 0xbf];
+    let expected = Feedbacks::FloatTest {
+        temperatures: vec![0.25, -1.0],
+    };
+
+    let decoded = Feedbacks::from_bytes(&encoded).expect("Should sucessfully parse the frame");
+
+    assert_eq!(expected, decoded);
+}
+
+#[test]
+fn generate_enum_inst() {
+    let frame = Instructions::EnumTest {
+        mode: SleepMode::Hibernate,
+        offset: Offset::Negative,
+    };
+
+    let encoded = frame.to_bytes();
+    let expected = [8u8, 7, 0xd4, 0xfe];
+
+    match_buffers(&expected, &encoded);
+}
+
+#[test]
+fn parse_enum_inst() {
+    let encoded = [8u8, 7, 0x2c, 0x01];
+    let expected = Instructions::EnumTest {
+        mode: SleepMode::Hibernate,
+        offset: Offset::Positive,
+    };
+
+    let decoded = Instructions::from_bytes(&encoded).expect("Should sucessfully parse the frame");
+
+    assert_eq!(expected, decoded);
+}
+
+#[test]
+fn parse_enum_array_fb() {
+    let encoded = [8u8, 2, 0, 1];
+    let expected = Feedbacks::EnumTest {
+        modes: vec![SleepMode::Light, SleepMode::Deep],
+    };
+
+    let decoded = Feedbacks::from_bytes(&encoded).expect("Should sucessfully parse the frame");
+
+    assert_eq!(expected, decoded);
+}
+
+#[test]
+fn parse_unknown_enum_value() {
+    let encoded = [8u8, 2, 0x2c, 0x01];
+
+    let decoded = Instructions::from_bytes(&encoded);
+
+    assert!(matches!(decoded, Err(TypesEnumError::UnknownEnumValue)));
+}
+
+#[test]
+fn convert_enum_from_underlying_value() {
+    assert_eq!(
+        Ok(SleepMode::Deep),
+        SleepMode::try_from(1u8).map_err(|_| ())
+    );
+    assert!(SleepMode::try_from(2u8).is_err());
+}
+
+#[test]
+fn generate_struct_inst() {
+    let frame = Instructions::StructTest {
+        date: RtcDate {
+            year: 2024,
+            month: 10,
+            day: 18,
+        },
+    };
+
+    let encoded = frame.to_bytes();
+    let expected = [9u8, 0xe8, 0x07, 10, 18];
+
+    match_buffers(&expected, &encoded);
+}
+
+#[test]
+fn generate_nested_struct_fb() {
+    let frame = Feedbacks::StructTest {
+        last: Event {
+            date: RtcDate {
+                year: 2024,
+                month: 1,
+                day: 2,
+            },
+            label: String::from("boot"),
+            mode: SleepMode::Deep,
+        },
+        history: vec![Event {
+            date: RtcDate {
+                year: 2024,
+                month: 3,
+                day: 4,
+            },
+            label: String::from("a"),
+            mode: SleepMode::Light,
+        }],
+    };
+
+    let encoded = frame.to_bytes();
+    let expected = [
+        9u8, 0xe8, 0x07, 1, 2, b'b', b'o', b'o', b't', 0, 1, 1, 0xe8, 0x07, 3, 4, b'a', 0, 0,
+    ];
+
+    match_buffers(&expected, &encoded);
+}
+
+#[test]
+fn parse_nested_struct_fb() {
+    let encoded = [
+        9u8, 0xe8, 0x07, 1, 2, b'b', b'o', b'o', b't', 0, 1, 1, 0xe8, 0x07, 3, 4, b'a', 0, 0,
+    ];
+    let expected = Feedbacks::StructTest {
+        last: Event {
+            date: RtcDate {
+                year: 2024,
+                month: 1,
+                day: 2,
+            },
+            label: String::from("boot"),
+            mode: SleepMode::Deep,
+        },
+        history: vec![Event {
+            date: RtcDate {
+                year: 2024,
+                month: 3,
+                day: 4,
+            },
+            label: String::from("a"),
+            mode: SleepMode::Light,
+        }],
+    };
+
+    let decoded = Feedbacks::from_bytes(&encoded).expect("Should sucessfully parse the frame");
+
+    assert_eq!(expected, decoded);
+}
+
+#[test]
+fn parse_truncated_struct() {
+    let encoded = [9u8, 0xe8, 0x07, 10];
+
+    let decoded = Instructions::from_bytes(&encoded);
+
+    assert!(matches!(decoded, Err(TypesEnumError::TooShort)));
+}
+
+#[test]
+fn bitfield_accessors() {
+    let mut status = Status::default();
+    status.set_charging(true);
+    status.set_level(5);
+    status.set_error(true);
+
+    assert_eq!(Status(0b1_101_1), status);
+    assert!(status.charging());
+    assert_eq!(5, status.level());
+
+    status.set_level(9);
+    assert_eq!(
+        1,
+        status.level(),
+        "Values should be truncated to the field width"
+    );
+    assert!(status.error());
+}
+
+#[test]
+fn generate_bitfield_inst() {
+    let mut status = Status::default();
+    status.set_level(3);
+    status.set_error(true);
+    let frame = Instructions::BitfieldTest { status };
+
+    let encoded = frame.to_bytes();
+    let expected = [10u8, 0b1_011_0];
+
+    match_buffers(&expected, &encoded);
+}
+
+#[test]
+fn parse_bitfield_array_fb() {
+    let encoded = [10u8, 2, 0b0_111_1, 0xff];
+    let expected = Feedbacks::BitfieldTest {
+        history: vec![Status(0b0_111_1), Status(0xff)],
+    };
+
+    let decoded = Feedbacks::from_bytes(&encoded).expect("Should sucessfully parse the frame");
+
+    assert_eq!(expected, decoded);
+    if let Feedbacks::BitfieldTest { history } = decoded {
+        assert!(history[0].charging());
+        assert_eq!(7, history[0].level());
+        assert!(!history[0].error());
+    }
+}
+
+#[test]
+fn generate_optional_inst() {
+    let frame = Instructions::OptionalTest {
+        level: Some(3),
+        label: None,
+        date: Some(RtcDate {
+            year: 2024,
+            month: 10,
+            day: 18,
+        }),
+    };
+
+    let encoded = frame.to_bytes();
+    let expected = [11u8, 1, 3, 0, 1, 0xe8, 0x07, 10, 18];
+
+    match_buffers(&expected, &encoded);
+}
+
+#[test]
+fn parse_optional_inst() {
+    let encoded = [11u8, 0, 1, b'o', b'k', 0, 0];
+    let expected = Instructions::OptionalTest {
+        level: None,
+        label: Some(String::from("ok")),
+        date: None,
+    };
+
+    let decoded = Instructions::from_bytes(&encoded).expect("Should sucessfully parse the frame");
+
+    assert_eq!(expected, decoded);
+}
+
+#[test]
+fn parse_optional_array_fb() {
+    let encoded = [11u8, 1, 2, 1, 0, 2, 0, 7];
+    let expected = Feedbacks::OptionalTest {
+        readings: Some(vec![1, 2]),
+        status: 7,
+    };
+
+    let decoded = Feedbacks::from_bytes(&encoded).expect("Should sucessfully parse the frame");
+
+    assert_eq!(expected, decoded);
+}
+
+#[test]
+fn parse_present_optional_without_value() {
+    let encoded = [11u8, 1];
+
+    let decoded = Instructions::from_bytes(&encoded);
+
+    assert!(matches!(decoded, Err(TypesEnumError::TooShort)));
+}
+
+#[test]
+fn parse_borrowed_coverage_fb() {
+    let encoded = [
+        1u8, 99u8, 0xbf, 0x07, 0xe0, 0xb3, 0xff, 0xff, b'T', b'h', b'i', b's', b' ', b'i', b's',
+        b' ', b'a', b' ', b't', b'e', b's', b't', b' ', b's', b't', b'r', b'i', b'n', b'g', 0,
+    ];
+    let expected = FeedbacksRef::CoverageTest {
+        a_i8: 99,
+        a_i16: 1983,
+        a_i32: -19488,
+        a_string: "This is a test string",
+    };
+
+    let decoded = FeedbacksRef::from_bytes(&encoded).expect("Should sucessfully decode this frame");
+
+    assert_eq!(expected, decoded);
+}
+
+#[test]
+fn parse_borrowed_struct_fb() {
+    let encoded = [
+        9u8, 0xe8, 0x07, 5, 17, b'u', b'p', 0, 1, 1, 0xe7, 0x07, 1, 2, b'x', 0, 7,
+    ];
+
+    let decoded = FeedbacksRef::from_bytes(&encoded).expect("Should sucessfully parse the frame");
+
+    let FeedbacksRef::StructTest { last, history } = &decoded else {
+        panic!("Decoded frame should be a StructTest");
+    };
+    assert_eq!(last.label, "up");
+    let previous = history
+        .iter()
+        .next()
+        .expect("History should hold one event");
+    assert_eq!(previous.date.year, 2023);
+    assert_eq!(previous.mode, SleepMode::Hibernate);
+    let mut encoded_again = Vec::new();
+    decoded
+        .write_to(&mut encoded_again)
+        .expect("Frame encoding should have worked");
+    assert_eq!(encoded_again, encoded);
+}
+
+#[test]
+fn encode_array_inst_into_buffer() {
+    let frame = Instructions::ArrayTest {
+        readings: vec![0x0102, 3],
+        labels: vec!["a".into(), "bc".into()],
+    };
+    let mut buffer = [0u8; 16];
+
+    let len = frame
+        .encode(&mut buffer)
+        .expect("Frame encoding should have worked");
+
+    match_buffers(
+        &[5u8, 2, 0x02, 0x01, 3, 0, 2, 0, b'a', 0, b'b', b'c', 0],
+        &buffer[..len].to_vec(),
+    );
+}
+
+#[test]
+fn encode_into_too_small_buffer() {
+    let frame = Instructions::SleepPin {
+        pre_sleep_time: 100,
+        wake_pin_active_state: true,
+    };
+    let mut buffer = [0u8; 3];
+
+    let encoded = frame.encode(&mut buffer);
+
+    assert!(matches!(encoded, Err(TypesEnumError::BufferTooSmall)));
+}
+
+#[test]
+fn generate_array_inst_too_long_for_count() {
+    let frame = Instructions::ArrayTest {
+        readings: vec![0; 256],
+        labels: vec![],
+    };
+
+    let encoded = frame.try_to_bytes();
+
+    assert!(matches!(encoded, Err(TypesEnumError::ArrayTooLong)));
+}
+
+#[test]
+#[should_panic]
+fn generate_array_inst_too_long_for_count_panics_without_try() {
+    let frame = Instructions::ArrayTest {
+        readings: vec![0; 256],
+        labels: vec![],
+    };
+
+    frame.to_bytes();
+}
+
+#[test]
+fn encode_borrowed_array_inst_too_long_for_count() {
+    let readings = [0u16; 256];
+    let frame = InstructionsRef::ArrayTest {
+        readings: Array::from(&readings[..]),
+        labels: Array::from(&[][..]),
+    };
+    let mut buffer = [0u8; 1024];
+
+    let encoded = frame.encode(&mut buffer);
+
+    assert!(matches!(encoded, Err(TypesEnumError::ArrayTooLong)));
+}
+
+#[test]
+fn encode_borrowed_array_inst_like_owned_one() {
+    let readings = [1u16, 2, 3];
+    let labels = ["a", "bc"];
+    let frame = InstructionsRef::ArrayTest {
+        readings: Array::from(&readings[..]),
+        labels: Array::from(&labels[..]),
+    };
+    let owned = Instructions::ArrayTest {
+        readings: readings.to_vec(),
+        labels: labels.iter().map(|label| label.to_string()).collect(),
+    };
+    let mut buffer = [0u8; 1024];
+
+    let length = frame
+        .encode(&mut buffer)
+        .expect("Frame encoding should have worked");
+
+    assert_eq!(owned.to_bytes(), &buffer[..length]);
+}
+
+#[test]
+fn write_borrowed_optional_inst() {
+    let frame = InstructionsRef::OptionalTest {
+        level: None,
+        label: Some("ok"),
+        date: None,
+    };
+    let mut encoded = Vec::new();
+
+    frame
+        .write_to(&mut encoded)
+        .expect("Frame encoding should have worked");
+
+    match_buffers(&[11u8, 0, 1, b'o', b'k', 0, 0], &encoded);
+}
+
+struct SleepHandler {
+    slept: Vec<u32>,
+}
+
+impl Handler for SleepHandler {
+    fn on_get_version(&mut self) -> GetVersionFeedback {
+        GetVersionFeedback {
+            major: 1,
+            minor: 2,
+            patch: 3,
+        }
+    }
+    fn on_coverage_test(&mut self, _: String, _: u64, _: i64) -> CoverageTestFeedback {
+        unimplemented!()
+    }
+    fn on_sleep_pin(&mut self, _: u16, _: bool) -> SleepPinFeedback {
+        unimplemented!()
+    }
+    fn on_sleep_time(&mut self, _: u16, duration: u32) -> SleepTimeFeedback {
+        self.slept.push(duration);
+        SleepTimeFeedback { feedback: 0 }
+    }
+    fn on_array_test(&mut self, _: Vec<u16>, _: Vec<String>) -> ArrayTestFeedback {
+        unimplemented!()
+    }
+    fn on_fixed_array_test(
+        &mut self,
+        _: [u8; 16],
+        _: [i16; 3],
+        _: [u8; 40],
+    ) -> FixedArrayTestFeedback {
+        unimplemented!()
+    }
+    fn on_float_test(&mut self, _: f32, _: f64) -> FloatTestFeedback {
+        unimplemented!()
+    }
+    fn on_enum_test(&mut self, _: SleepMode, _: Offset) -> EnumTestFeedback {
+        unimplemented!()
+    }
+    fn on_struct_test(&mut self, _: RtcDate) -> StructTestFeedback {
+        unimplemented!()
+    }
+    fn on_bitfield_test(&mut self, _: Status) -> BitfieldTestFeedback {
+        unimplemented!()
+    }
+    fn on_optional_test(
+        &mut self,
+        _: Option<u8>,
+        _: Option<String>,
+        _: Option<RtcDate>,
+    ) -> OptionalTestFeedback {
+        unimplemented!()
+    }
+}
+
+#[test]
+fn dispatch_getversion_inst() {
+    let mut handler = SleepHandler { slept: Vec::new() };
+
+    let feedback = dispatch(&mut handler, &[0u8]).expect("Frame dispatch should have worked");
+
+    match_buffers(&[0u8, 1, 2, 3], &feedback);
+}
+
+#[test]
+fn dispatch_sleeptime_inst() {
+    let mut handler = SleepHandler { slept: Vec::new() };
+
+    let feedback = dispatch(&mut handler, &[4u8, 100, 0, 10, 0, 0, 0])
+        .expect("Frame dispatch should have worked");
+
+    match_buffers(&[4u8, 0], &feedback);
+    assert_eq!(handler.slept, vec![10]);
+}
+
+#[test]
+fn dispatch_unknown_inst() {
+    let mut handler = SleepHandler { slept: Vec::new() };
+
+    let feedback = dispatch(&mut handler, &[0xFEu8]);
+
+    assert!(matches!(feedback, Err(TypesEnumError::UnknownCode)));
+    assert!(handler.slept.is_empty());
+}
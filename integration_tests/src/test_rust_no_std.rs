@@ -0,0 +1,161 @@
+include!(concat!(env!("OUT_DIR"), "/test_output_no_std.rs"));
+
+#[test]
+fn encode_sleepin_inst() {
+    let frame = Instructions::SleepPin {
+        pre_sleep_time: 100,
+        wake_pin_active_state: true,
+    };
+    let mut buffer = [0u8; 8];
+
+    let len = frame
+        .encode(&mut buffer)
+        .expect("Frame encoding should have worked");
+
+    assert_eq!(&buffer[..len], &[3u8, 100, 0, 1]);
+}
+
+#[test]
+fn encode_into_too_small_buffer() {
+    let frame = Instructions::SleepPin {
+        pre_sleep_time: 100,
+        wake_pin_active_state: true,
+    };
+    let mut buffer = [0u8; 3];
+
+    assert_eq!(
+        frame.encode(&mut buffer),
+        Err(TypesEnumError::BufferTooSmall)
+    );
+}
+
+#[test]
+fn parse_coverage_fb_borrows_string() {
+    let encoded = [
+        1u8, 99u8, 0xbf, 0x07, 0xe0, 0xb3, 0xff, 0xff, b'T', b'h', b'i', b's', b' ', b'i', b's',
+        b' ', b'a', b' ', b't', b'e', b's', b't', b' ', b's', b't', b'r', b'i', b'n', b'g', 0,
+    ];
+    let expected = Feedbacks::CoverageTest {
+        a_i8: 99,
+        a_i16: 1983,
+        a_i32: -19488,
+        a_string: "This is a test string",
+    };
+
+    let decoded = Feedbacks::from_bytes(&encoded).expect("Should sucessfully decode this frame");
+
+    assert_eq!(expected, decoded);
+}
+
+#[test]
+fn encode_array_inst() {
+    let frame = Instructions::ArrayTest {
+        readings: [0x0102, 3][..].into(),
+        labels: ["a", "bc"][..].into(),
+    };
+    let mut buffer = [0u8; 16];
+
+    let len = frame
+        .encode(&mut buffer)
+        .expect("Frame encoding should have worked");
+
+    assert_eq!(
+        &buffer[..len],
+        &[5u8, 2, 0x02, 0x01, 3, 0, 2, 0, b'a', 0, b'b', b'c', 0]
+    );
+}
+
+#[test]
+fn parse_array_inst() {
+    let encoded = [5u8, 2, 0x02, 0x01, 3, 0, 2, 0, b'a', 0, b'b', b'c', 0];
+
+    let decoded = Instructions::from_bytes(&encoded).expect("Should sucessfully parse the frame");
+
+    let Instructions::ArrayTest { readings, labels } = decoded else {
+        panic!("Decoded frame should be an ArrayTest");
+    };
+    assert_eq!(readings.iter().collect::<Vec<u16>>(), vec![0x0102, 3]);
+    assert_eq!(labels.iter().collect::<Vec<&str>>(), vec!["a", "bc"]);
+    assert_eq!(
+        decoded,
+        Instructions::ArrayTest {
+            readings: [0x0102, 3][..].into(),
+            labels: ["a", "bc"][..].into(),
+        }
+    );
+}
+
+#[test]
+fn parse_array_longer_than_frame() {
+    let encoded = [5u8, 3, 0x02, 0x01, 3, 0, 0, 0];
+
+    assert!(Instructions::from_bytes(&encoded).is_err());
+}
+
+#[test]
+fn parse_struct_fb() {
+    let encoded = [
+        9u8, 0xe8, 0x07, 5, 17, b'u', b'p', 0, 1, 1, 0xe7, 0x07, 1, 2, b'x', 0, 7,
+    ];
+
+    let decoded = Feedbacks::from_bytes(&encoded).expect("Should sucessfully parse the frame");
+
+    let Feedbacks::StructTest { last, history } = decoded else {
+        panic!("Decoded frame should be a StructTest");
+    };
+    assert_eq!(last.label, "up");
+    assert_eq!(last.mode, SleepMode::Deep);
+    let previous = history
+        .iter()
+        .next()
+        .expect("History should hold one event");
+    assert_eq!(previous.date.year, 2023);
+    assert_eq!(previous.mode, SleepMode::Hibernate);
+    let mut buffer = [0u8; 32];
+    let len = decoded
+        .encode(&mut buffer)
+        .expect("Frame encoding should have worked");
+    assert_eq!(&buffer[..len], &encoded);
+}
+
+#[test]
+fn parse_unknown_enum_value() {
+    let encoded = [8u8, 2, 0x2c, 0x01];
+
+    assert_eq!(
+        Instructions::from_bytes(&encoded),
+        Err(TypesEnumError::UnknownEnumValue)
+    );
+}
+
+#[test]
+fn encode_optional_inst() {
+    let frame = Instructions::OptionalTest {
+        level: Some(3),
+        label: None,
+        date: Some(RtcDate {
+            year: 2024,
+            month: 5,
+            day: 17,
+        }),
+    };
+    let mut buffer = [0u8; 16];
+
+    let len = frame
+        .encode(&mut buffer)
+        .expect("Frame encoding should have worked");
+
+    assert_eq!(&buffer[..len], &[0x0bu8, 1, 3, 0, 1, 0xe8, 0x07, 5, 17]);
+    assert_eq!(Instructions::from_bytes(&buffer[..len]), Ok(frame));
+}
+
+#[test]
+fn encode_sync_length_too_long_frame() {
+    let frame = [0u8; 0x10000];
+    let mut stream = [0u8; 0x10004];
+
+    assert_eq!(
+        Framing::SyncLength.encode(&frame, &mut stream),
+        Err(TypesEnumError::FrameTooLong)
+    );
+}
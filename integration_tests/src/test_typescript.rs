@@ -0,0 +1,36 @@
+#[cfg(test)]
+use std::fs;
+#[cfg(test)]
+use std::process::Command;
+
+/// Compiles `test_typescript.ts` with the TypeScript module generated from test.yml, and runs
+/// its round trips
+#[test]
+fn typescript_frames_round_trip() {
+    let out_dir = env!("OUT_DIR");
+    let runner = format!("{out_dir}/test_typescript.ts");
+    let build_dir = format!("{out_dir}/typescript");
+    fs::copy("test_typescript.ts", &runner).expect("test_typescript.ts should be copied");
+
+    let output = Command::new("tsc")
+        .args(["--strict", "--target", "es2020", "--module", "commonjs"])
+        .args(["--outDir", &build_dir, &runner])
+        .arg(format!("{out_dir}/test_output.ts"))
+        .output()
+        .expect("tsc should be installed");
+    assert!(
+        output.status.success(),
+        "TypeScript compilation failed:\n{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+
+    let output = Command::new("node")
+        .arg(format!("{build_dir}/test_typescript.js"))
+        .output()
+        .expect("node should be installed");
+    assert!(
+        output.status.success(),
+        "TypeScript tests failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,171 @@
+"""Encodes and decodes frames with the Python module generated from test.yml
+
+Usage: python3 test_python.py GENERATED_MODULE
+"""
+
+import importlib.util
+import sys
+import unittest
+
+spec = importlib.util.spec_from_file_location("test_output", sys.argv.pop())
+codes = importlib.util.module_from_spec(spec)
+sys.modules["test_output"] = codes
+spec.loader.exec_module(codes)
+
+Instructions = codes.Instructions
+Feedbacks = codes.Feedbacks
+
+
+class EncodingTest(unittest.TestCase):
+    def test_generate_sleepin_inst(self):
+        frame = Instructions.SleepPin(pre_sleep_time=100, wake_pin_active_state=False)
+
+        self.assertEqual(bytes([3, 100, 0, 0]), frame.to_bytes())
+
+    def test_generate_sleeptime_inst(self):
+        frame = Instructions.SleepTime(pre_sleep_time=1000, duration=700)
+
+        self.assertEqual(bytes([4, 0xE8, 3, 0xBC, 2, 0, 0]), frame.to_bytes())
+
+    def test_generate_getversion_inst(self):
+        self.assertEqual(bytes([0]), Instructions.GetVersion().to_bytes())
+
+    def test_generate_array_inst(self):
+        frame = Instructions.ArrayTest(readings=[0x0102, 3], labels=["a", "bc"])
+
+        self.assertEqual(
+            bytes([5, 2, 0x02, 0x01, 3, 0, 2, 0]) + b"a\0bc\0",
+            frame.to_bytes(),
+        )
+
+    def test_generate_optional_inst(self):
+        frame = Instructions.OptionalTest(level=None, label="ok", date=None)
+
+        self.assertEqual(bytes([11, 0, 1]) + b"ok\0" + bytes([0]), frame.to_bytes())
+
+    def test_generate_bitfield_inst(self):
+        status = codes.Status()
+        status.charging = True
+        status.level = 5
+        status.error = True
+
+        self.assertEqual(bytes([10, 0x1B]), Instructions.BitfieldTest(status).to_bytes())
+
+    def test_generate_fixed_array_inst_without_counts(self):
+        frame = Instructions.FixedArrayTest(
+            device_id=list(range(16)), calibration=[-1, 0, 300], key=[0] * 40
+        )
+
+        self.assertEqual(
+            bytes([6, *range(16), 0xFF, 0xFF, 0, 0, 0x2C, 1]) + bytes(40), frame.to_bytes()
+        )
+
+    def test_generate_enum_inst_with_its_data_type(self):
+        frame = Instructions.EnumTest(mode=codes.SleepMode.Hibernate, offset=codes.Offset.Negative)
+
+        self.assertEqual(bytes([8, 7, 0xD4, 0xFE]), frame.to_bytes())
+
+    def test_generate_optional_struct_inst(self):
+        frame = Instructions.OptionalTest(
+            level=3, label=None, date=codes.RtcDate(year=2023, month=12, day=31)
+        )
+
+        self.assertEqual(bytes([11, 1, 3, 0, 1, 0xE7, 7, 12, 31]), frame.to_bytes())
+
+    def test_fixed_array_needs_its_count(self):
+        frame = Feedbacks.FixedArrayTest(labels=["a"], mac=[0] * 6)
+
+        with self.assertRaises(ValueError):
+            frame.to_bytes()
+
+
+class DecodingTest(unittest.TestCase):
+    def test_parse_sleepin_inst(self):
+        decoded = codes.parse_instruction(bytes([3, 100, 0, 1]))
+
+        self.assertEqual(
+            Instructions.SleepPin(pre_sleep_time=100, wake_pin_active_state=True),
+            decoded,
+        )
+
+    def test_parse_getversion_fb(self):
+        decoded = codes.parse_feedback(bytes([0, 50, 1, 9]))
+
+        self.assertEqual(Feedbacks.GetVersion(major=50, minor=1, patch=9), decoded)
+
+    def test_parse_array_fb_with_four_bytes_count(self):
+        decoded = Feedbacks.ArrayTest.from_bytes(
+            bytes([5, 2, 0, 0, 0, 0xFF, 0xFF, 0xFF, 0xFF, 7, 0, 0, 0, 1])
+        )
+
+        self.assertEqual(Feedbacks.ArrayTest(registers=[-1, 7], status=1), decoded)
+
+    def test_parse_optional_fb(self):
+        absent = codes.parse_feedback(bytes([11, 0, 4]))
+        present = codes.parse_feedback(bytes([11, 1, 1, 3, 1, 4]))
+
+        self.assertEqual(Feedbacks.OptionalTest(readings=None, status=4), absent)
+        self.assertEqual(Feedbacks.OptionalTest(readings=[259], status=4), present)
+
+    def test_unknown_code_is_rejected(self):
+        with self.assertRaisesRegex(codes.FrameError, "Unknown code 2"):
+            codes.parse_instruction(bytes([2]))
+
+    def test_unknown_enum_value_is_rejected(self):
+        with self.assertRaises(codes.FrameError):
+            codes.parse_instruction(bytes([8, 2, 0x2C, 1]))
+
+    def test_unterminated_string_is_rejected(self):
+        with self.assertRaisesRegex(codes.FrameError, "No string termination found"):
+            codes.parse_instruction(bytes([1]) + b"abc")
+
+    def test_too_short_frame_is_rejected(self):
+        with self.assertRaisesRegex(codes.FrameError, "Frame too short"):
+            codes.parse_instruction(bytes([4, 0xE8, 3, 0xBC]))
+
+    def test_frame_of_another_code_is_rejected(self):
+        with self.assertRaises(codes.FrameError):
+            Instructions.SleepTime.from_bytes(bytes([3, 100, 0, 1]))
+
+
+class RoundTripTest(unittest.TestCase):
+    def assert_round_trip(self, frame, parse):
+        self.assertEqual(frame, parse(frame.to_bytes()))
+
+    def test_instructions_round_trip(self):
+        for frame in [
+            Instructions.CoverageTest(a_string="hé", a_u64=2**64 - 1, a_i64=-(2**63)),
+            Instructions.ArrayTest(readings=[], labels=["x"] * 300),
+            Instructions.FixedArrayTest(
+                device_id=list(range(16)), calibration=[-1, 0, 1], key=[0xAA] * 40
+            ),
+            Instructions.FloatTest(setpoint=1.5, gain=-0.125),
+            Instructions.EnumTest(mode=codes.SleepMode.Hibernate, offset=codes.Offset.Negative),
+            Instructions.StructTest(date=codes.RtcDate(year=2024, month=2, day=29)),
+            Instructions.OptionalTest(
+                level=3, label=None, date=codes.RtcDate(year=2023, month=12, day=31)
+            ),
+        ]:
+            with self.subTest(frame=frame):
+                self.assert_round_trip(frame, codes.parse_instruction)
+
+    def test_feedbacks_round_trip(self):
+        event = codes.Event(
+            date=codes.RtcDate(year=2023, month=5, day=1),
+            label="up",
+            mode=codes.SleepMode.Deep,
+        )
+        for frame in [
+            Feedbacks.CoverageTest(a_i8=-128, a_i16=-300, a_i32=2**31 - 1, a_string=""),
+            Feedbacks.FixedArrayTest(labels=["a", "bc"], mac=[1, 2, 3, 4, 5, 6]),
+            Feedbacks.FloatTest(temperatures=[20.5, -4.25]),
+            Feedbacks.EnumTest(modes=[codes.SleepMode.Light, codes.SleepMode.Deep]),
+            Feedbacks.StructTest(last=event, history=[event, event]),
+            Feedbacks.BitfieldTest(history=[codes.Status(0x1F), codes.Status(0)]),
+        ]:
+            with self.subTest(frame=frame):
+                self.assert_round_trip(frame, codes.parse_feedback)
+
+
+if __name__ == "__main__":
+    unittest.main()
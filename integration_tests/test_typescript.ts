@@ -0,0 +1,257 @@
+// Encodes and decodes frames with the TypeScript module generated from test.yml
+//
+// Compiled next to the generated module by `tsc`, then run with `node`.
+
+import type { Feedbacks, Instructions } from "./test_output";
+import {
+  FrameError,
+  Offset,
+  SleepMode,
+  Status,
+  decodeFeedback,
+  decodeInstruction,
+  encodeFeedback,
+  encodeInstruction,
+} from "./test_output";
+
+declare const process: { exitCode?: number };
+
+const tests: [string, () => void][] = [];
+
+function test(name: string, body: () => void): void {
+  tests.push([name, body]);
+}
+
+function bytes(...values: (number | string)[]): Uint8Array {
+  const encoded: number[] = [];
+  values.forEach((value) => {
+    if (typeof value === "string") {
+      encoded.push(...new TextEncoder().encode(value), 0);
+    } else {
+      encoded.push(value);
+    }
+  });
+  return Uint8Array.from(encoded);
+}
+
+function describe(value: unknown): string {
+  return JSON.stringify(value, (_, item) => (typeof item === "bigint" ? `${item}n` : item));
+}
+
+function isEqual(expected: unknown, actual: unknown): boolean {
+  if (typeof expected !== "object" || expected === null) {
+    return Object.is(expected, actual);
+  }
+  if (typeof actual !== "object" || actual === null) {
+    return false;
+  }
+  if (Object.getPrototypeOf(expected) !== Object.getPrototypeOf(actual)) {
+    return false;
+  }
+  const expectedEntries = Object.entries(expected).filter(([, value]) => value !== undefined);
+  const actualEntries = Object.entries(actual).filter(([, value]) => value !== undefined);
+  return (
+    expectedEntries.length === actualEntries.length &&
+    expectedEntries.every(([key, value]) =>
+      isEqual(value, (actual as Record<string, unknown>)[key]),
+    )
+  );
+}
+
+function assertEqual(expected: unknown, actual: unknown): void {
+  if (!isEqual(expected, actual)) {
+    throw new Error(`Expected ${describe(expected)}, got ${describe(actual)}`);
+  }
+}
+
+function assertThrows(reason: string, body: () => void): void {
+  try {
+    body();
+  } catch (error) {
+    const actual =
+      error instanceof FrameError ? error.reason : error instanceof Error ? error.name : error;
+    assertEqual(reason, actual);
+    return;
+  }
+  throw new Error(`Expected ${reason} to be thrown`);
+}
+
+test("generate sleeppin inst", () => {
+  const frame: Instructions = {
+    kind: "SleepPin",
+    pre_sleep_time: 100,
+    wake_pin_active_state: false,
+  };
+
+  assertEqual(bytes(3, 100, 0, 0), encodeInstruction(frame));
+});
+
+test("generate sleeptime inst in little endian", () => {
+  const frame: Instructions = { kind: "SleepTime", pre_sleep_time: 1000, duration: 700 };
+
+  assertEqual(bytes(4, 0xe8, 3, 0xbc, 2, 0, 0), encodeInstruction(frame));
+});
+
+test("generate getversion inst", () => {
+  assertEqual(bytes(0), encodeInstruction({ kind: "GetVersion" }));
+});
+
+test("generate coverage inst with NUL terminated string", () => {
+  const frame: Instructions = { kind: "CoverageTest", a_string: "hé", a_u64: 1n, a_i64: -2n };
+
+  assertEqual(
+    bytes(1, "hé", 1, 0, 0, 0, 0, 0, 0, 0, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff),
+    encodeInstruction(frame),
+  );
+});
+
+test("generate array inst with its counts", () => {
+  const frame: Instructions = { kind: "ArrayTest", readings: [0x0102, 3], labels: ["a", "bc"] };
+
+  assertEqual(bytes(5, 2, 0x02, 0x01, 3, 0, 2, 0, "a", "bc"), encodeInstruction(frame));
+});
+
+test("generate optional inst with presence bytes", () => {
+  const frame: Instructions = { kind: "OptionalTest", label: "ok" };
+
+  assertEqual(bytes(11, 0, 1, "ok", 0), encodeInstruction(frame));
+});
+
+test("generate bitfield inst", () => {
+  const status = new Status();
+  status.charging = true;
+  status.level = 5;
+  status.error = true;
+
+  assertEqual(bytes(10, 0x1b), encodeInstruction({ kind: "BitfieldTest", status }));
+});
+
+test("generate fixed array inst without counts", () => {
+  const frame: Instructions = {
+    kind: "FixedArrayTest",
+    device_id: [...Array(16).keys()],
+    calibration: [-1, 0, 300],
+    key: new Array(40).fill(0),
+  };
+
+  assertEqual(
+    bytes(6, ...Array(16).keys(), 0xff, 0xff, 0, 0, 0x2c, 1, ...new Array(40).fill(0)),
+    encodeInstruction(frame),
+  );
+});
+
+test("generate enum inst with its data type", () => {
+  const frame: Instructions = {
+    kind: "EnumTest",
+    mode: SleepMode.Hibernate,
+    offset: Offset.Negative,
+  };
+
+  assertEqual(bytes(8, 7, 0xd4, 0xfe), encodeInstruction(frame));
+});
+
+test("generate optional struct inst", () => {
+  const frame: Instructions = {
+    kind: "OptionalTest",
+    level: 3,
+    date: { year: 2023, month: 12, day: 31 },
+  };
+
+  assertEqual(bytes(11, 1, 3, 0, 1, 0xe7, 7, 12, 31), encodeInstruction(frame));
+});
+
+test("array too long for its count is rejected", () => {
+  const frame: Instructions = { kind: "ArrayTest", readings: new Array(256).fill(1), labels: [] };
+
+  assertThrows("RangeError", () => encodeInstruction(frame));
+});
+
+test("fixed array needs its count", () => {
+  const frame: Feedbacks = { kind: "FixedArrayTest", labels: ["a"], mac: [0, 0, 0, 0, 0, 0] };
+
+  assertThrows("RangeError", () => encodeFeedback(frame));
+});
+
+test("parse sleeppin inst", () => {
+  assertEqual(
+    { kind: "SleepPin", pre_sleep_time: 100, wake_pin_active_state: true },
+    decodeInstruction(bytes(3, 100, 0, 1)),
+  );
+});
+
+test("parse array fb with four bytes count", () => {
+  assertEqual(
+    { kind: "ArrayTest", registers: [-1, 7], status: 1 },
+    decodeFeedback(bytes(5, 2, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, 7, 0, 0, 0, 1)),
+  );
+});
+
+test("parse optional fb with presence bytes", () => {
+  assertEqual({ kind: "OptionalTest", status: 4 }, decodeFeedback(bytes(11, 0, 4)));
+  assertEqual(
+    { kind: "OptionalTest", readings: [259], status: 4 },
+    decodeFeedback(bytes(11, 1, 1, 3, 1, 4)),
+  );
+});
+
+test("unknown code is rejected", () => {
+  assertThrows("UnknownCode", () => decodeInstruction(bytes(2)));
+});
+
+test("unknown enum value is rejected", () => {
+  assertThrows("UnknownEnumValue", () => decodeInstruction(bytes(8, 2, 0x2c, 1)));
+});
+
+test("unterminated string is rejected", () => {
+  assertThrows("NoStringTerminationFound", () => decodeInstruction(bytes(1, 0x61, 0x62)));
+});
+
+test("invalid UTF-8 string is rejected", () => {
+  assertThrows("FailedToDecodeString", () => decodeInstruction(bytes(1, 0xff, 0)));
+});
+
+test("too short frame is rejected", () => {
+  assertThrows("TooShort", () => decodeInstruction(bytes(4, 0xe8, 3, 0xbc)));
+  assertThrows("TooShort", () => decodeInstruction(bytes()));
+});
+
+test("instructions round trip", () => {
+  const frames: Instructions[] = [
+    { kind: "CoverageTest", a_string: "", a_u64: 2n ** 64n - 1n, a_i64: -(2n ** 63n) },
+    { kind: "ArrayTest", readings: [], labels: new Array(300).fill("x") },
+    {
+      kind: "FixedArrayTest",
+      device_id: [...Array(16).keys()],
+      calibration: [-1, 0, 1],
+      key: new Array(40).fill(0xaa),
+    },
+    { kind: "FloatTest", setpoint: 1.5, gain: -0.125 },
+    { kind: "EnumTest", mode: SleepMode.Hibernate, offset: Offset.Negative },
+    { kind: "StructTest", date: { year: 2024, month: 2, day: 29 } },
+    { kind: "OptionalTest", level: 3, date: { year: 2023, month: 12, day: 31 } },
+  ];
+  frames.forEach((frame) => assertEqual(frame, decodeInstruction(encodeInstruction(frame))));
+});
+
+test("feedbacks round trip", () => {
+  const event = { date: { year: 2023, month: 5, day: 1 }, label: "up", mode: SleepMode.Deep };
+  const frames: Feedbacks[] = [
+    { kind: "CoverageTest", a_i8: -128, a_i16: -300, a_i32: 2 ** 31 - 1, a_string: "hé" },
+    { kind: "FixedArrayTest", labels: ["a", "bc"], mac: [1, 2, 3, 4, 5, 6] },
+    { kind: "FloatTest", temperatures: [20.5, -4.25] },
+    { kind: "EnumTest", modes: [SleepMode.Light, SleepMode.Deep] },
+    { kind: "StructTest", last: event, history: [event, event] },
+    { kind: "BitfieldTest", history: [new Status(0x1f), new Status(0)] },
+  ];
+  frames.forEach((frame) => assertEqual(frame, decodeFeedback(encodeFeedback(frame))));
+});
+
+tests.forEach(([name, body]) => {
+  try {
+    body();
+    console.log(`ok - ${name}`);
+  } catch (error) {
+    console.error(`FAILED - ${name}: ${error instanceof Error ? error.message : error}`);
+    process.exitCode = 1;
+  }
+});
@@ -0,0 +1,173 @@
+use crate::yaml_parser::ValidationErrors;
+use std::error;
+use std::fmt;
+use std::io;
+
+/// Failure of the processing of an input file, by step
+#[derive(Debug)]
+pub enum Error {
+    /// The command line arguments are invalid, reported with the usage when they can't be
+    /// parsed
+    Usage(String),
+    /// The input file can't be read
+    Io { path: String, source: io::Error },
+    /// The input file isn't a valid YAML protocol description. The 1-based `line` and
+    /// `column` of the faulty item are known for most syntax and type errors.
+    Parse {
+        path: String,
+        location: Option<(usize, usize)>,
+        message: String,
+    },
+    /// The protocol description would produce broken code
+    Validation {
+        path: String,
+        errors: ValidationErrors,
+    },
+    /// An output file can't be written
+    Generation { path: String, source: io::Error },
+}
+
+impl Error {
+    pub fn parse(path: &str, error: serde_yaml::Error) -> Self {
+        let location = error
+            .location()
+            .map(|location| (location.line(), location.column()));
+        let mut message = error.to_string();
+        // The location is reported as a prefix, in the usual compiler diagnostics format
+        if let Some((line, column)) = location {
+            let suffix = format!(" at line {line} column {column}");
+            if let Some(stripped) = message.strip_suffix(&suffix) {
+                message = stripped.to_string();
+            }
+        }
+        Error::Parse {
+            path: path.to_string(),
+            location,
+            message,
+        }
+    }
+
+    /// Exit status of the command line tool
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Usage(_) => 1,
+            Error::Io { .. } => 2,
+            Error::Parse { .. } => 3,
+            Error::Validation { .. } => 4,
+            Error::Generation { .. } => 5,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Usage(message) => write!(f, "{message}"),
+            Error::Io { path, source } => write!(f, "{path}: can't read the input file: {source}"),
+            Error::Parse {
+                path,
+                location: Some((line, column)),
+                message,
+            } => write!(f, "{path}:{line}:{column}: {message}"),
+            Error::Parse {
+                path,
+                location: None,
+                message,
+            } => write!(f, "{path}: {message}"),
+            Error::Validation { path, errors } => {
+                let lines = errors
+                    .0
+                    .iter()
+                    .map(|e| format!("{path}: {e}"))
+                    .collect::<Vec<String>>();
+                write!(f, "{}", lines.join("\n"))
+            }
+            Error::Generation { path, source } => {
+                write!(f, "{path}: can't write the generated file: {source}")
+            }
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } | Error::Generation { source, .. } => Some(source),
+            Error::Validation { errors, .. } => Some(errors),
+            Error::Usage(_) | Error::Parse { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::yaml_parser::{CodesFile, ValidationError};
+
+    fn parse_error(input: &str) -> Error {
+        let error = serde_yaml::from_str::<CodesFile>(input).unwrap_err();
+        Error::parse("codes.yml", error)
+    }
+
+    #[test]
+    fn parse_error_reports_line_and_column() {
+        let error = parse_error(
+            "
+codes:
+  0x01:
+    name: Reset
+    instruction:
+      description: Resets
+      parameters:
+        - name: delay
+          data_type: u7
+          description: Delay
+",
+        );
+
+        match &error {
+            Error::Parse { location, .. } => assert_eq!(&Some((9, 22)), location),
+            _ => panic!("Unexpected error {error:?}"),
+        }
+        assert!(
+            error
+                .to_string()
+                .starts_with("codes.yml:9:22: codes.0x01.instruction.parameters[0].data_type:")
+        );
+        assert_eq!(3, error.exit_code());
+    }
+
+    #[test]
+    fn syntax_error_reports_line_and_column() {
+        let error = parse_error("codes:\n  0x01: [\n");
+
+        assert_eq!(
+            "codes.yml:3:1: while parsing a node, did not find expected node content",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn validation_errors_are_reported_one_per_line() {
+        let error = Error::Validation {
+            path: "codes.yml".into(),
+            errors: ValidationErrors(vec![
+                ValidationError {
+                    path: "codes.0x100".into(),
+                    message: "code doesn't fit on one byte".into(),
+                },
+                ValidationError {
+                    path: "types.Point.x".into(),
+                    message: "name is used more than once".into(),
+                },
+            ]),
+        };
+
+        assert_eq!(
+            "codes.yml: codes.0x100: code doesn't fit on one byte\n\
+             codes.yml: types.Point.x: name is used more than once",
+            error.to_string()
+        );
+        assert_eq!(4, error.exit_code());
+    }
+}
@@ -8,6 +8,7 @@ const HEADER_TEMPLATE: &str = include_str!("./templates/c_header.h");
 
 pub struct CppHeaderGenerator {
     file: Box<dyn Write>,
+    no_alloc: bool,
 }
 impl FileGenerator for CppHeaderGenerator {
     fn build_file(&mut self, codes: &yaml_parser::CodesFile) -> Result<(), io::Error> {
@@ -16,6 +17,8 @@ impl FileGenerator for CppHeaderGenerator {
                 HEADER_TEMPLATE,
                 &language_models::CppLanguageModel {
                     headerfile_name: None,
+                    platform: language_models::CPlatform::default(),
+                    no_alloc: self.no_alloc,
                 },
                 codes,
             )
@@ -29,9 +32,10 @@ impl FileGenerator for CppHeaderGenerator {
 }
 
 impl CppHeaderGenerator {
-    pub fn new(header_file_name: &String) -> Result<Self, io::Error> {
+    pub fn new(header_file_name: &String, no_alloc: bool) -> Result<Self, io::Error> {
         Ok(CppHeaderGenerator {
             file: Box::new(File::create(header_file_name)?),
+            no_alloc,
         })
     }
 }
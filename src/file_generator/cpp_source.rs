@@ -10,6 +10,8 @@ const C_SOURCE_TEMPLATE: &str = include_str!("./templates/c_template.c");
 pub struct CppFileGenerator {
     writer: Box<dyn Write>,
     headerfile_name: String,
+    platform: language_models::CPlatform,
+    no_alloc: bool,
 }
 
 impl FileGenerator for CppFileGenerator {
@@ -19,6 +21,8 @@ impl FileGenerator for CppFileGenerator {
                 C_SOURCE_TEMPLATE,
                 &language_models::CppLanguageModel {
                     headerfile_name: Some(self.headerfile_name.clone()),
+                    platform: self.platform,
+                    no_alloc: self.no_alloc,
                 },
                 codes,
             )
@@ -31,6 +35,8 @@ impl CppFileGenerator {
     pub fn new(
         source_file_name: &String,
         header_file: &Option<String>,
+        platform: language_models::CPlatform,
+        no_alloc: bool,
     ) -> Result<CppFileGenerator, io::Error> {
         //// Create source code file
         let file = File::create(source_file_name)?;
@@ -46,6 +52,8 @@ impl CppFileGenerator {
         Ok(CppFileGenerator {
             writer: Box::new(file),
             headerfile_name,
+            platform,
+            no_alloc,
         })
     }
 }
@@ -1,20 +1,115 @@
 use crate::file_generator::FrameType;
 use crate::file_generator::LanguageModel;
 use crate::yaml_parser;
+use std::str::FromStr;
+
+/// Platform the generated C code runs on, providing its memory allocator
+#[derive(PartialEq, Eq, Clone, Copy, Default, Debug)]
+pub enum CPlatform {
+    /// Zephyr RTOS kernel heap, `k_malloc` and `k_free`
+    #[default]
+    Zephyr,
+    /// Standard C library `malloc` and `free`
+    Libc,
+    /// FreeRTOS heap, `pvPortMalloc` and `vPortFree`
+    FreeRtos,
+    /// `FRAME_MALLOC(size)` and `FRAME_FREE(pointer)` macros defined by the user
+    Custom,
+}
+
+impl FromStr for CPlatform {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "zephyr" => Ok(Self::Zephyr),
+            "libc" => Ok(Self::Libc),
+            "freertos" => Ok(Self::FreeRtos),
+            "custom" => Ok(Self::Custom),
+            _ => Err(format!(
+                "C platform must be zephyr, libc, freertos or custom, not {value}"
+            )),
+        }
+    }
+}
+
+impl CPlatform {
+    /// Includes and allocator macros of the generated source, the macros being kept when
+    /// already defined by the user
+    fn allocator(&self) -> String {
+        let (include, malloc, free) = match self {
+            Self::Zephyr => ("#include <zephyr/zephyr.h>\n", "k_malloc", "k_free"),
+            Self::Libc => ("", "malloc", "free"),
+            Self::FreeRtos => ("#include \"FreeRTOS.h\"\n", "pvPortMalloc", "vPortFree"),
+            Self::Custom => {
+                return r#"#if !defined(FRAME_MALLOC) || !defined(FRAME_FREE)
+#error "Define FRAME_MALLOC(size) and FRAME_FREE(pointer) for the custom platform"
+#endif
+"#
+                .to_string();
+            }
+        };
+        format!(
+            r#"{include}#ifndef FRAME_MALLOC
+#define FRAME_MALLOC(size) {malloc}(size)
+#endif
+#ifndef FRAME_FREE
+#define FRAME_FREE(pointer) {free}(pointer)
+#endif
+"#
+        )
+    }
+}
 
 pub struct CppLanguageModel {
     pub headerfile_name: Option<String>,
+    pub platform: CPlatform,
+    /// Strings and arrays are stored in place, and the frames are parsed without allocating
+    pub no_alloc: bool,
 }
 
 impl LanguageModel for CppLanguageModel {
-    fn custom_footer(&self) -> String {
+    fn custom_footer(&self, codes: &yaml_parser::CodesFile) -> String {
         if self.headerfile_name.is_none() {
-            r#"
+            [
+                format!(
+                    r#"
 
 // External parsing functions
-extern int parse_feedback_frame(char* buffer, int len, Feedbacks* code, void **parameters);
-extern int parse_instruction_frame(char* buffer, int len, Instructions* code, void **parameters);"#
-                .to_string()
+extern int parse_feedback_frame(char* buffer, int len, Feedbacks* code, {});
+extern int parse_instruction_frame(char* buffer, int len, Instructions* code, {});"#,
+                    self.parameters_argument(FrameType::Feedback),
+                    self.parameters_argument(FrameType::Instruction)
+                ),
+                if codes.framing.is_some() {
+                    format!(
+                        r#"
+
+// Incremental decoder of the frames received on a byte stream
+typedef struct {{
+    char *buffer;       // Caller-owned storage of the frame being received
+    size_t capacity;    // Size of the buffer
+    size_t len;         // Number of bytes stored in the buffer
+    size_t expected;    // Frame length announced by the sync header
+    char header[2];     // Length bytes of the sync header
+    size_t header_len;  // Number of sync header bytes received
+    bool escaped;       // Last SLIP byte was an escape
+    bool invalid;       // Current frame is dropped, badly encoded or too long
+}} stream_decoder;
+
+extern void stream_decoder_init(stream_decoder *decoder, char *buffer, size_t capacity);
+extern int stream_decoder_push(stream_decoder *decoder, char byte);
+extern int encode_stream_frame(const char *frame, int len, char *stream, int *stream_len);
+extern int stream_decode_feedback(stream_decoder *decoder, char *chunk, size_t len, size_t *consumed, Feedbacks* code, {});
+extern int stream_decode_instruction(stream_decoder *decoder, char *chunk, size_t len, size_t *consumed, Instructions* code, {});"#,
+                        self.parameters_argument(FrameType::Feedback),
+                        self.parameters_argument(FrameType::Instruction)
+                    )
+                } else {
+                    String::new()
+                },
+            ]
+            .concat()
         } else {
             String::new()
         }
@@ -24,18 +119,95 @@ extern int parse_instruction_frame(char* buffer, int len, Instructions* code, vo
         if let Some(header_filename) = &self.headerfile_name {
             format!(
                 r#"#include "{header_filename}"
-    "#
+{}"#,
+                if self.no_alloc {
+                    String::new()
+                } else {
+                    self.platform.allocator()
+                }
             )
         } else {
             String::new()
         }
     }
 
+    fn declare_settings(&self, codes: &yaml_parser::CodesFile) -> String {
+        let (checksum_size, checksum_function) = match codes.checksum {
+            Some(checksum) => (
+                checksum.size(),
+                format!("compute_{}", format!("{checksum:?}").to_lowercase()),
+            ),
+            None => (0, "compute_no_checksum".to_string()),
+        };
+        let framing = match codes.framing {
+            Some(yaml_parser::Framing::Cobs) => "STREAM_FRAMING_COBS",
+            Some(yaml_parser::Framing::Slip) => "STREAM_FRAMING_SLIP",
+            Some(yaml_parser::Framing::SyncLength) => "STREAM_FRAMING_SYNC_LENGTH",
+            None => "STREAM_FRAMING_NONE",
+        };
+        format!(
+            r#"#define WIRE_BIG_ENDIAN {}
+#define FRAME_CODE_SIZE {}
+#define FRAME_CHECKSUM_SIZE {checksum_size}
+#define FRAME_CHECKSUM {checksum_function}
+#define STREAM_FRAMING {framing}
+#define FRAME_NO_ALLOC {}
+"#,
+            (codes.endianness == yaml_parser::Endianness::Big) as u8,
+            codes.code_width.size(),
+            self.no_alloc as u8
+        )
+    }
+
+    fn declare_types(&self, codes: &yaml_parser::CodesFile) -> String {
+        codes
+            .enums
+            .iter()
+            .map(|(name, enumeration)| self.declare_enum(name, enumeration))
+            .chain(
+                codes
+                    .bitfields
+                    .iter()
+                    .map(|(name, bitfield)| self.declare_bitfield(name, bitfield)),
+            )
+            .chain(
+                codes
+                    .types_in_dependency_order()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(name, structure)| self.declare_struct(name, structure)),
+            )
+            .collect::<String>()
+    }
+
+    fn implement_types(&self, codes: &yaml_parser::CodesFile) -> String {
+        codes
+            .enums
+            .iter()
+            .map(|(name, enumeration)| self.implement_enum(name, enumeration))
+            .chain(
+                codes
+                    .bitfields
+                    .iter()
+                    .map(|(name, bitfield)| self.implement_bitfield(name, bitfield)),
+            )
+            .chain(
+                codes
+                    .types_in_dependency_order()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(name, structure)| self.implement_struct(name, structure, codes)),
+            )
+            .collect::<String>()
+    }
+
     fn implement_feedbacks(&self, codes: &yaml_parser::CodesFile) -> String {
         [
             self.implement_feedbacks_builders(codes),
             self.implement_feedbacks_decoders(codes),
+            self.implement_frames_free(FrameType::Feedback, &codes.get_feedbacks(), codes),
             self.implement_feedback_frames_dispatch(codes),
+            self.implement_stream_decoder(FrameType::Feedback, codes),
         ]
         .join("")
     }
@@ -44,7 +216,10 @@ extern int parse_instruction_frame(char* buffer, int len, Instructions* code, vo
         [
             self.implement_instructions_builders(codes),
             self.implement_instructions_decoders(codes),
+            self.implement_frames_free(FrameType::Instruction, &codes.get_instructions(), codes),
             self.implement_instruction_frames_dispatch(codes),
+            self.implement_instruction_handlers_dispatch(codes),
+            self.implement_stream_decoder(FrameType::Instruction, codes),
         ]
         .join("")
     }
@@ -53,7 +228,10 @@ extern int parse_instruction_frame(char* buffer, int len, Instructions* code, vo
         [
             self.declare_instructions_enum(codes),
             self.declare_instructions_structures(codes),
+            self.declare_parameters_union(FrameType::Instruction, &codes.get_instructions()),
             self.declare_instructions_functions(codes),
+            self.declare_free_functions(FrameType::Instruction, &codes.get_instructions()),
+            self.declare_instruction_handlers(codes),
         ]
         .join("")
     }
@@ -62,13 +240,197 @@ extern int parse_instruction_frame(char* buffer, int len, Instructions* code, vo
         [
             self.declare_feedback_enum(codes),
             self.declare_feedback_structures(codes),
+            self.declare_parameters_union(FrameType::Feedback, &codes.get_feedbacks()),
             self.declare_feedbacks_functions(codes),
+            self.declare_free_functions(FrameType::Feedback, &codes.get_feedbacks()),
         ]
         .join("")
     }
 }
 
 impl CppLanguageModel {
+    fn declare_enum(&self, name: &str, enumeration: &yaml_parser::EnumType) -> String {
+        format!(
+            r#"
+// {}
+typedef enum __{}_enum {{
+    {}
+}} {name};
+"#,
+            enumeration.description.trim_end().replace('\n', "\n// "),
+            name.to_lowercase(),
+            enumeration
+                .values
+                .iter()
+                .map(|v| format!(
+                    "{}_{} = {},\t// {}",
+                    name.to_uppercase(),
+                    v.name.to_uppercase(),
+                    v.value,
+                    v.description
+                ))
+                .collect::<Vec<String>>()
+                .join("\n\t")
+        )
+    }
+
+    fn implement_enum(&self, name: &str, enumeration: &yaml_parser::EnumType) -> String {
+        let repr = enumeration.data_type.to_cpp_type_string();
+        let wire_type = enumeration.data_type.to_rust_type_string();
+        format!(
+            r#"
+inline buffer_slice write_{name}_to_buffer(buffer_slice in, {name} value) {{
+  return write_{wire_type}_to_buffer(in, ({repr})value);
+}}
+
+inline {name} parse_{name}_from_buffer(buffer_slice *slice) {{
+  {repr} value = parse_{wire_type}_from_buffer(slice);
+  if (slice->valid) {{
+    switch (value) {{
+    {}
+      break;
+    default:
+      invalidate_buffer_slice(slice, FRAME_ERROR_UNKNOWN_ENUM_VALUE);
+    }}
+  }}
+  return ({name})value;
+}}
+"#,
+            enumeration
+                .values
+                .iter()
+                .map(|v| format!("case {}_{}:", name.to_uppercase(), v.name.to_uppercase()))
+                .collect::<Vec<String>>()
+                .join("\n    ")
+        )
+    }
+
+    fn declare_bitfield(&self, name: &str, bitfield: &yaml_parser::BitfieldType) -> String {
+        let prefix = name.to_uppercase();
+        // Keeps the shifted masks of 64 bits bitfields from overflowing an int
+        let suffix = if bitfield.data_type == yaml_parser::ParameterType::Uint64 {
+            "ULL"
+        } else {
+            "U"
+        };
+        format!(
+            r#"
+// {}
+typedef {} {name};
+{}
+"#,
+            bitfield.description.trim_end().replace('\n', "\n// "),
+            bitfield.data_type.to_cpp_type_string(),
+            bitfield
+                .fields_with_offsets()
+                .into_iter()
+                .map(|(field, offset)| {
+                    let field_prefix = format!("{prefix}_{}", field.name.to_uppercase());
+                    format!(
+                        r#"
+// {}
+#define {field_prefix}_SHIFT {offset}
+#define {field_prefix}_MASK {:#x}{suffix}
+#define {prefix}_GET_{}(value) (((value) >> {field_prefix}_SHIFT) & {field_prefix}_MASK)
+#define {prefix}_SET_{}(value, field) (((value) & ~({field_prefix}_MASK << {field_prefix}_SHIFT)) | (((field) & {field_prefix}_MASK) << {field_prefix}_SHIFT))"#,
+                        field.description,
+                        field.mask(),
+                        field.name.to_uppercase(),
+                        field.name.to_uppercase()
+                    )
+                })
+                .collect::<String>()
+        )
+    }
+
+    fn implement_bitfield(&self, name: &str, bitfield: &yaml_parser::BitfieldType) -> String {
+        let wire_type = bitfield.data_type.to_rust_type_string();
+        format!(
+            r#"
+inline buffer_slice write_{name}_to_buffer(buffer_slice in, {name} value) {{
+  return write_{wire_type}_to_buffer(in, value);
+}}
+
+inline {name} parse_{name}_from_buffer(buffer_slice *slice) {{
+  return parse_{wire_type}_from_buffer(slice);
+}}
+"#
+        )
+    }
+
+    fn declare_struct(&self, name: &str, structure: &yaml_parser::InstFeedback) -> String {
+        format!(
+            r#"
+// {}
+typedef struct __{}_struct {{
+    {}
+}} {name};
+"#,
+            structure.description.trim_end().replace('\n', "\n// "),
+            name.to_lowercase(),
+            structure
+                .parameters
+                .iter()
+                .map(|p| self.c_parameter_definition_with_comment(p))
+                .collect::<Vec<String>>()
+                .join("\n\t")
+        )
+    }
+
+    fn implement_struct(
+        &self,
+        name: &str,
+        structure: &yaml_parser::InstFeedback,
+        codes: &yaml_parser::CodesFile,
+    ) -> String {
+        let free_function = if !self.no_alloc
+            && yaml_parser::ParameterType::Custom(name.to_string()).needs_free(codes)
+        {
+            format!(
+                r#"
+inline void free_{name}({name} *value) {{{}
+}}
+"#,
+                structure
+                    .parameters
+                    .iter()
+                    .filter_map(|p| free_parameter(p, "value->", codes))
+                    .collect::<String>()
+            )
+        } else {
+            String::new()
+        };
+
+        format!(
+            r#"{free_function}
+inline buffer_slice write_{name}_to_buffer(buffer_slice slice, {name} value) {{
+{}
+  return slice;
+}}
+
+inline {name} parse_{name}_from_buffer(buffer_slice *slice_ptr) {{
+  {name} value;
+  memset(&value, 0, sizeof(value));
+  buffer_slice slice = *slice_ptr;
+{}
+  *slice_ptr = slice;
+  return value;
+}}
+"#,
+            structure
+                .parameters
+                .iter()
+                .map(|p| write_parameter_to_buffer(p, "value.", self.no_alloc))
+                .collect::<String>(),
+            structure
+                .parameters
+                .iter()
+                .map(|p| parse_parameter_from_buffer(p, "value.", self.no_alloc))
+                .collect::<Vec<String>>()
+                .join("\n")
+        )
+    }
+
     fn declare_feedback_enum(&self, parameters: &crate::CodesFile) -> String {
         [
             self.enumerations_header("Feedbacks"),
@@ -154,7 +516,7 @@ struct s_{}_{}_params {{
             parameters
                 .parameters
                 .iter()
-                .map(|p| { p.c_parameter_definition_with_comment() })
+                .map(|p| self.c_parameter_definition_with_comment(p))
                 .collect::<Vec<String>>()
                 .join("\n\t")
         )
@@ -164,7 +526,7 @@ struct s_{}_{}_params {{
         codes
             .get_feedbacks()
             .iter()
-            .map(|(_, name, _)| self.declare_build_function(name, "feedback", "fb"))
+            .map(|(_, name, _)| self.declare_frame_functions(name, "feedback", "fb"))
             .collect::<String>()
     }
 
@@ -172,18 +534,392 @@ struct s_{}_{}_params {{
         codes
             .get_instructions()
             .iter()
-            .map(|(_, name, _)| self.declare_build_function(name, "instruction", "inst"))
+            .map(|(_, name, _)| self.declare_frame_functions(name, "instruction", "inst"))
             .collect::<String>()
     }
 
-    fn declare_build_function(&self, name: &str, inst: &str, short: &str) -> String {
+    /// Builder and parser of one frame, the parser zeroing the parameters first
+    fn declare_frame_functions(&self, name: &str, inst: &str, short: &str) -> String {
+        let name = name.to_lowercase();
+        format!(
+            r#"
+int build_{inst}_{name}_frame(char* buffer, int *len, struct s_{short}_{name}_params* parameters);
+int parse_{inst}_{name}_frame(char* buffer, int len, struct s_{short}_{name}_params* parameters);
+"#
+        )
+    }
+
+    /// Declaration of the C field of a parameter, stored in place when not allocating
+    fn c_parameter_definition_with_comment(
+        &self,
+        p: &yaml_parser::InstFeedbackParameter,
+    ) -> String {
+        if self.no_alloc {
+            p.c_bounded_parameter_definition_with_comment()
+        } else {
+            p.c_parameter_definition_with_comment()
+        }
+    }
+
+    /// Parameters of every frame in a union, tagged by the code of the parsed frame. The
+    /// union comes first so that a pointer to the parameters points to those of the frame.
+    fn declare_parameters_union(
+        &self,
+        frame_type: FrameType,
+        frames: &[(u32, String, yaml_parser::InstFeedback)],
+    ) -> String {
+        let short = frame_type.short();
+        format!(
+            r#"
+// Parameters of any {} frame, the member of params matching the code being filled
+typedef struct {{
+    union {{
+        {}
+    }} params;
+    {} code;
+}} {}_params;
+"#,
+            frame_type.long(),
+            frames
+                .iter()
+                .map(|(_, name, _)| {
+                    let lowercase_name = name.to_lowercase();
+                    format!("struct s_{short}_{lowercase_name}_params {lowercase_name};")
+                })
+                .collect::<Vec<String>>()
+                .join("\n\t\t"),
+            frame_type.struct_name(),
+            frame_type.struct_name()
+        )
+    }
+
+    /// Functions releasing the strings and arrays allocated while parsing the frames
+    fn declare_free_functions(
+        &self,
+        frame_type: FrameType,
+        frames: &[(u32, String, yaml_parser::InstFeedback)],
+    ) -> String {
+        if self.no_alloc {
+            return String::new();
+        }
+        let type_long = frame_type.long();
+        let type_short = frame_type.short();
+        let struct_name = frame_type.struct_name();
         format!(
             r#"
-int build_{inst}_{}_frame(char* buffer, int *len, struct s_{short}_{}_params* parameters);
+// Release the strings and arrays of {type_long} parameters, resetting their pointers and
+// counts so that releasing them again does nothing. The parsing functions release what
+// they allocated and return no parameters when they fail, leaving nothing to release.
+// free_{type_long}_params also releases the parameters returned by parse_{type_long}_frame.{}
+void free_{type_long}_params({struct_name}_params* parameters);
 "#,
-            name.to_lowercase(),
-            name.to_lowercase()
+            frames
+                .iter()
+                .map(|(_, name, _)| {
+                    let lowercase_name = name.to_lowercase();
+                    format!(
+                        "\nvoid free_{type_long}_{lowercase_name}_params(struct s_{type_short}_{lowercase_name}_params* parameters);"
+                    )
+                })
+                .collect::<String>()
+        )
+    }
+
+    /// Table of the functions handling each instruction, those of the codes with a feedback
+    /// filling its parameters
+    fn declare_instruction_handlers(&self, codes: &crate::CodesFile) -> String {
+        format!(
+            r#"
+// Handlers of the received instructions, called by dispatch_instruction_frame with its
+// ctx argument. They return 0 to send the feedback they filled, FRAME_NO_FEEDBACK to
+// send none, or a negative error code.
+typedef struct {{
+    {}
+}} instruction_handlers;
+
+int dispatch_instruction_frame(char* buffer, int len, const instruction_handlers* handlers, void *ctx, char* feedback, int *feedback_len);
+"#,
+            codes
+                .get_instructions()
+                .iter()
+                .map(|(id, name, _)| {
+                    let lowercase_name = name.to_lowercase();
+                    let feedback = if codes.has_feedback(*id) {
+                        format!(" struct s_fb_{lowercase_name}_params* feedback,")
+                    } else {
+                        String::new()
+                    };
+                    format!(
+                        "int (*on_{lowercase_name})(const struct s_inst_{lowercase_name}_params* instruction,{feedback} void *ctx);"
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join("\n\t")
+        )
+    }
+
+    /// Parsing functions argument receiving the parameters of the frame, allocated unless
+    /// parsing without allocating
+    fn parameters_argument(&self, frame_type: FrameType) -> String {
+        if self.no_alloc {
+            format!("{}_params *parameters", frame_type.struct_name())
+        } else {
+            format!("{}_params **parameters", frame_type.struct_name())
+        }
+    }
+}
+
+impl yaml_parser::ParameterType {
+    /// Statement writing `value` to the `slice` buffer
+    fn to_c_writing_statement(&self, value: &str, no_alloc: bool) -> String {
+        if no_alloc && *self == yaml_parser::ParameterType::String {
+            return format!(
+                "slice = write_bounded_String_to_buffer(slice, {value}, sizeof({value}));"
+            );
+        }
+        format!(
+            "slice = write_{}_to_buffer(slice, {value});",
+            self.to_rust_type_string()
+        )
+    }
+
+    /// Statement parsing `target` from the `slice` buffer
+    fn to_c_parsing_statement(&self, target: &str, no_alloc: bool) -> String {
+        if no_alloc && *self == yaml_parser::ParameterType::String {
+            return format!(
+                "parse_bounded_String_from_buffer(&slice, {target}, sizeof({target}));"
+            );
+        }
+        format!(
+            "{target} = parse_{}_from_buffer(&slice);",
+            self.to_rust_type_string()
+        )
+    }
+
+    /// Whether parsing a value of this type allocates memory to release afterwards
+    fn needs_free(&self, codes: &yaml_parser::CodesFile) -> bool {
+        match self {
+            yaml_parser::ParameterType::String | yaml_parser::ParameterType::Array { .. } => true,
+            yaml_parser::ParameterType::FixedArray { item, .. } => item.needs_free(codes),
+            yaml_parser::ParameterType::Custom(name) => {
+                codes.types.get(name).is_some_and(|structure| {
+                    structure
+                        .parameters
+                        .iter()
+                        .any(|p| p.data_type.needs_free(codes))
+                })
+            }
+            _ => false,
+        }
+    }
+
+    /// Statement releasing the memory held by the `target` item, if any
+    fn to_c_free_statement(&self, target: &str, codes: &yaml_parser::CodesFile) -> Option<String> {
+        match self {
+            yaml_parser::ParameterType::String => {
+                Some(format!("FRAME_FREE({target}); {target} = NULL;"))
+            }
+            yaml_parser::ParameterType::Custom(name) if self.needs_free(codes) => {
+                Some(format!("free_{name}(&{target});"))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Statements writing the `p` field of `owner` (e.g. `parameters->`) to the `slice` buffer
+fn write_parameter_to_buffer(
+    p: &yaml_parser::InstFeedbackParameter,
+    owner: &str,
+    no_alloc: bool,
+) -> String {
+    if p.optional {
+        let presence = format!("{owner}{}", p.c_presence_name());
+        format!(
+            r#"		slice = write_bool_to_buffer(slice, {presence});
+		if ({presence}) {{
+{}		}}
+"#,
+            write_value_to_buffer(p, owner, no_alloc)
+        )
+    } else {
+        write_value_to_buffer(p, owner, no_alloc)
+    }
+}
+
+fn write_value_to_buffer(
+    p: &yaml_parser::InstFeedbackParameter,
+    owner: &str,
+    no_alloc: bool,
+) -> String {
+    let value = format!("{owner}{}", p.name);
+    match &p.data_type {
+        yaml_parser::ParameterType::Array { item, length } => {
+            let count = format!("{owner}{}", p.c_array_count_name());
+            format!(
+                r#"{}		slice = write_length_to_buffer(slice, {count}, {});
+		for (size_t i = 0; slice.valid && i < {count}; i++) {{
+			{}
+		}}
+"#,
+                bounded_count_check(p, &count, "slice.valid = false", no_alloc),
+                length.size(),
+                item.to_c_writing_statement(&format!("{value}[i]"), no_alloc)
+            )
+        }
+        yaml_parser::ParameterType::FixedArray { item, count } => format!(
+            r#"		for (size_t i = 0; i < {count}; i++) {{
+			{}
+		}}
+"#,
+            item.to_c_writing_statement(&format!("{value}[i]"), no_alloc)
+        ),
+        data_type => format!(
+            "\t\t{}\n",
+            data_type.to_c_writing_statement(&value, no_alloc)
+        ),
+    }
+}
+
+/// Statements parsing the `p` field of `owner` (e.g. `parameters->`) from the `slice` buffer
+fn parse_parameter_from_buffer(
+    p: &yaml_parser::InstFeedbackParameter,
+    owner: &str,
+    no_alloc: bool,
+) -> String {
+    if p.optional {
+        let presence = format!("{owner}{}", p.c_presence_name());
+        format!(
+            r#"		{presence} = parse_bool_from_buffer(&slice);
+		if (slice.valid && {presence}) {{
+{}
+		}}"#,
+            parse_value_from_buffer(p, owner, no_alloc)
         )
+    } else {
+        parse_value_from_buffer(p, owner, no_alloc)
+    }
+}
+
+fn parse_value_from_buffer(
+    p: &yaml_parser::InstFeedbackParameter,
+    owner: &str,
+    no_alloc: bool,
+) -> String {
+    let target = format!("{owner}{}", p.name);
+    match &p.data_type {
+        yaml_parser::ParameterType::Array { item, length } if no_alloc => {
+            let count = format!("{owner}{}", p.c_array_count_name());
+            format!(
+                r#"		{count} = parse_length_from_buffer(&slice, {});
+{}		for (size_t i = 0; slice.valid && i < {count}; i++) {{
+			{}
+		}}"#,
+                length.size(),
+                bounded_count_check(
+                    p,
+                    &count,
+                    "invalidate_buffer_slice(&slice, FRAME_ERROR_TOO_LONG)",
+                    no_alloc
+                ),
+                item.to_c_parsing_statement(&format!("{target}[i]"), no_alloc)
+            )
+        }
+        yaml_parser::ParameterType::Array { item, length } => {
+            let count = format!("{owner}{}", p.c_array_count_name());
+            format!(
+                r#"		{count} = parse_length_from_buffer(&slice, {});
+		{target} = ({})allocate_array_from_buffer(&slice, {count}, {}, sizeof({}));
+		for (size_t i = 0; slice.valid && i < {count}; i++) {{
+			{}
+		}}"#,
+                length.size(),
+                p.data_type.to_cpp_type_string(),
+                item.size().max(1),
+                item.to_cpp_type_string(),
+                item.to_c_parsing_statement(&format!("{target}[i]"), no_alloc)
+            )
+        }
+        yaml_parser::ParameterType::FixedArray { item, count } => format!(
+            r#"		for (size_t i = 0; slice.valid && i < {count}; i++) {{
+			{}
+		}}"#,
+            item.to_c_parsing_statement(&format!("{target}[i]"), no_alloc)
+        ),
+        data_type => format!(
+            "\t\t{}",
+            data_type.to_c_parsing_statement(&target, no_alloc)
+        ),
+    }
+}
+
+/// Statement running `action` when the `count` of an array stored in place exceeds its
+/// `max_count`
+fn bounded_count_check(
+    p: &yaml_parser::InstFeedbackParameter,
+    count: &str,
+    action: &str,
+    no_alloc: bool,
+) -> String {
+    match p.max_count {
+        Some(max_count) if no_alloc => format!(
+            r#"		if ({count} > {max_count}) {{
+			{action};
+		}}
+"#
+        ),
+        _ => String::new(),
+    }
+}
+
+/// Releases the memory allocated while parsing the `p` field of `owner`, if any
+fn free_parameter(
+    p: &yaml_parser::InstFeedbackParameter,
+    owner: &str,
+    codes: &yaml_parser::CodesFile,
+) -> Option<String> {
+    let target = format!("{owner}{}", p.name);
+    match &p.data_type {
+        yaml_parser::ParameterType::String => Some(format!(
+            r#"
+        if ({target} != NULL) {{
+            FRAME_FREE({target});
+            {target} = NULL;
+        }}"#
+        )),
+        yaml_parser::ParameterType::Array { item, .. } => {
+            let count = format!("{owner}{}", p.c_array_count_name());
+            let free_items = match item.to_c_free_statement(&format!("{target}[i]"), codes) {
+                Some(statement) => format!(
+                    r#"
+            for (size_t i = 0; i < {count}; i++) {{
+                {statement}
+            }}"#
+                ),
+                None => String::new(),
+            };
+            Some(format!(
+                r#"
+        if ({target} != NULL) {{{free_items}
+            FRAME_FREE({target});
+            {target} = NULL;
+            {count} = 0;
+        }}"#
+            ))
+        }
+        yaml_parser::ParameterType::FixedArray { item, count } => item
+            .to_c_free_statement(&format!("{target}[i]"), codes)
+            .map(|statement| {
+                format!(
+                    r#"
+        for (size_t i = 0; i < {count}; i++) {{
+            {statement}
+        }}"#
+                )
+            }),
+        data_type => data_type
+            .to_c_free_statement(&target, codes)
+            .map(|statement| format!("\n        {statement}")),
     }
 }
 
@@ -192,6 +928,8 @@ struct WriteFrameBuilder<'a> {
     key: String,
     name: &'a str,
     instruction: &'a yaml_parser::InstFeedback,
+    codes: &'a yaml_parser::CodesFile,
+    no_alloc: bool,
 }
 
 impl<'a> WriteFrameBuilder<'a> {
@@ -199,6 +937,8 @@ impl<'a> WriteFrameBuilder<'a> {
         builder_type: FrameType,
         name: &'a str,
         instruction: &'a yaml_parser::InstFeedback,
+        codes: &'a yaml_parser::CodesFile,
+        no_alloc: bool,
     ) -> Self {
         let builder_type_upper = builder_type.short().to_uppercase();
         let instruction_name_upper = name.to_uppercase();
@@ -208,6 +948,8 @@ impl<'a> WriteFrameBuilder<'a> {
             key,
             name,
             instruction,
+            codes,
+            no_alloc,
         }
     }
 
@@ -228,27 +970,20 @@ int build_{type_long}_{lowercase_name}_frame(char* buffer, int *len, struct s_{t
     
     buffer_slice slice = {{.head=buffer, .len= (size_t) *len, .valid = true}};
 
-    if (*len > 0) buffer[0] = {key};
-    else return -1;
-
-    slice = move_buffer_slice(slice, 1);
+    if (*len < FRAME_CODE_SIZE) return -1;
+    slice = write_uint_to_buffer(slice, {key}, FRAME_CODE_SIZE);
 "#)
             ,
 
             self.instruction
                 .parameters
                 .iter()
-                .map(|p| {
-                    format!(
-                        "\t\tslice = write_{}_to_buffer(slice, parameters->{});\n",
-                        p.data_type.to_rust_type_string(),
-                        p.name
-                    )
-                })
+                .map(|p| write_parameter_to_buffer(p, "parameters->", self.no_alloc))
                 .collect::<Vec<String>>()
                 .join("\n"),
 
             r#"
+    slice = write_checksum_to_buffer(slice, buffer);
     if (!slice.valid) {{
       return -1;
     }}
@@ -276,73 +1011,163 @@ int parse_{type_long}_{lowercase_name}_frame(char* buffer, int len, struct s_{ty
 
     if ((p_size > 0) && (parameters == NULL))
         return -1;
-        
+
+    // Nothing is allocated yet, a failure only releases what this parsing allocates
+    if (parameters != NULL)
+        memset(parameters, 0, p_size);
+
     // Check the code
-    if (buffer[0] != {key}) return -1;
+    if ((len < FRAME_CODE_SIZE) || (parse_frame_code(buffer) != {key})) return -1;
+
+    int checksum_result = verify_frame_checksum(buffer, &len);
+    if (checksum_result < 0) return checksum_result;
 
     buffer_slice slice = {{.head=buffer, .len=(size_t)len, .valid=true}};
-    slice = move_buffer_slice(slice, 1);
+    slice = move_buffer_slice(slice, FRAME_CODE_SIZE);
 "#
             ),
 
             inst.parameters
                 .iter()
-                .map(|p| {
-                    format!(
-                        "\t\tparameters->{} = parse_{}_from_buffer(&slice);",
-                        p.name,
-                        p.data_type.to_rust_type_string()
-                    )
-                })
+                .map(|p| parse_parameter_from_buffer(p, "parameters->", self.no_alloc))
                 .collect::<Vec<String>>()
                 .join("\n"),
 
             format!(
                 r#"
     if (!slice.valid) {{
-       {}return -1;
+       {}return (slice.error < 0) ? slice.error : -1;
     }}
     return 0;
 }}
         "#,
-                inst.parameters
-                    .iter()
-                    .filter_map(|p| if yaml_parser::ParameterType::String == p.data_type {
-                        Some(format!(
-                            r#"
-        if (parameters->{} != NULL) {{
-            free(parameters->{});
-        }}"#,
-                            p.name, p.name
-                        ))
-                    } else {
-                        None
-                    })
-                    .collect::<Vec<String>>()
-                    .join("\n\t\t\t")
+                if self.no_alloc {
+                    String::new()
+                } else {
+                    format!("free_{type_long}_{lowercase_name}_params(parameters);\n       ")
+                }
             )
         ].join("")
     }
 
+    /// Releases the strings and arrays of the parameters, but not the parameters themselves
+    pub fn build_frame_free(&self) -> String {
+        let lowercase_name = self.name.to_lowercase();
+        let type_long = self.builder_type.long();
+        let type_short = self.builder_type.short();
+
+        format!(
+            r#"
+void free_{type_long}_{lowercase_name}_params(struct s_{type_short}_{lowercase_name}_params* parameters)
+{{
+    if (parameters != NULL) {{{}
+    }}
+}}
+"#,
+            self.instruction
+                .parameters
+                .iter()
+                .filter_map(|p| free_parameter(p, "parameters->", self.codes))
+                .collect::<String>()
+        )
+    }
+
+    pub fn build_free_case(&self) -> String {
+        let lowercase_name = self.name.to_lowercase();
+        let type_long = self.builder_type.long();
+
+        format!(
+            r#"
+        case {}:
+            free_{type_long}_{lowercase_name}_params(&parameters->params.{lowercase_name});
+            break;"#,
+            self.key
+        )
+    }
+
+    /// Case of `dispatch_instruction_frame` calling the handler of the instruction, and
+    /// building the `feedback` it answers with when `with_feedback`, before releasing the
+    /// instruction the feedback can point to
+    pub fn build_handler_case(&self, with_feedback: bool) -> String {
+        let lowercase_name = self.name.to_lowercase();
+        let key = &self.key;
+        let free_instruction = if self.no_alloc {
+            String::new()
+        } else {
+            format!("\n                free_instruction_{lowercase_name}_params(&instruction);")
+        };
+        let (declare_response, response_argument, build_response) = if with_feedback {
+            (
+                format!(
+                    r#"
+                struct s_fb_{lowercase_name}_params response;
+                memset(&response, 0, sizeof(response));"#
+                ),
+                " &response,",
+                format!(
+                    r#"
+                if ((result == 0) && (feedback != NULL)) {{
+                    *feedback_len = capacity;
+                    result = build_feedback_{lowercase_name}_frame(feedback, feedback_len, &response);
+                    if (result < 0)
+                        *feedback_len = 0;
+                }}"#
+                ),
+            )
+        } else {
+            (String::new(), "", String::new())
+        };
+
+        format!(
+            r#"
+        case {key}:
+            {{
+                if (handlers->on_{lowercase_name} == NULL)
+                    return FRAME_ERROR_NO_HANDLER;
+                struct s_inst_{lowercase_name}_params instruction;
+                memset(&instruction, 0, sizeof(instruction));
+                int result = parse_instruction_{lowercase_name}_frame(buffer, len, &instruction);
+                if (result < 0)
+                    return result;{declare_response}
+                result = handlers->on_{lowercase_name}(&instruction,{response_argument} ctx);{build_response}{free_instruction}
+                return (result < 0) ? result : 0;
+            }}"#
+        )
+    }
+
     pub fn build_dispatch_case(&self) -> String {
         let lowercase_name = self.name.to_lowercase();
         let uppercase_name = self.name.to_uppercase();
         let frametype_upper = self.builder_type.short().to_uppercase();
-        let frametype_lower = self.builder_type.short();
         let frametype_long = self.builder_type.long();
+        let struct_name = self.builder_type.struct_name();
         let key = format!("{frametype_upper}_{uppercase_name}");
 
+        if self.no_alloc {
+            return format!(
+                r#"
+        case {key}:
+            memset(parameters, 0, sizeof(*parameters));
+            parameters->code = {key};
+            *code = {key};
+            return parse_{frametype_long}_{lowercase_name}_frame(buffer, len, &parameters->params.{lowercase_name});
+        "#
+            );
+        }
+
         format!(
             r#"
         case {key}:
             {{
-                const size_t psize = sizeof(struct s_{frametype_lower}_{lowercase_name}_params);
-                *parameters = k_malloc(psize);
-                memset(*parameters, 0, psize);
-                *code = {frametype_upper}_{uppercase_name};
-                int result =  parse_{frametype_long}_{lowercase_name}_frame(buffer, len, (struct s_{frametype_lower}_{lowercase_name}_params*)*parameters);
+                *parameters = ({struct_name}_params*)FRAME_MALLOC(sizeof({struct_name}_params));
+                if (*parameters == NULL)
+                    return -1;
+                memset(*parameters, 0, sizeof({struct_name}_params));
+                (*parameters)->code = {key};
+                *code = {key};
+                int result =  parse_{frametype_long}_{lowercase_name}_frame(buffer, len, &(*parameters)->params.{lowercase_name});
                 if (result < 0) {{
-                   k_free(*parameters);
+                   FRAME_FREE(*parameters);
                    *parameters=NULL;
                 }}
                 return result;
@@ -358,8 +1183,16 @@ impl CppLanguageModel {
         _key: u32,
         name: &str,
         instruction: &yaml_parser::InstFeedback,
+        codes: &yaml_parser::CodesFile,
     ) -> String {
-        WriteFrameBuilder::new(FrameType::Instruction, name, instruction).build_frame()
+        WriteFrameBuilder::new(
+            FrameType::Instruction,
+            name,
+            instruction,
+            codes,
+            self.no_alloc,
+        )
+        .build_frame()
     }
 
     fn write_feedback_frame_builder(
@@ -367,8 +1200,10 @@ impl CppLanguageModel {
         _key: u32,
         name: &str,
         instruction: &yaml_parser::InstFeedback,
+        codes: &yaml_parser::CodesFile,
     ) -> String {
-        WriteFrameBuilder::new(FrameType::Feedback, name, instruction).build_frame()
+        WriteFrameBuilder::new(FrameType::Feedback, name, instruction, codes, self.no_alloc)
+            .build_frame()
     }
 
     fn write_feedback_frame_parser(
@@ -376,8 +1211,10 @@ impl CppLanguageModel {
         _key: u32,
         name: &str,
         fb: &yaml_parser::InstFeedback,
+        codes: &yaml_parser::CodesFile,
     ) -> String {
-        WriteFrameBuilder::new(FrameType::Feedback, name, fb).build_frame_parser()
+        WriteFrameBuilder::new(FrameType::Feedback, name, fb, codes, self.no_alloc)
+            .build_frame_parser()
     }
 
     fn write_instruction_frame_parser(
@@ -385,34 +1222,42 @@ impl CppLanguageModel {
         _key: u32,
         name: &str,
         inst: &yaml_parser::InstFeedback,
+        codes: &yaml_parser::CodesFile,
     ) -> String {
-        WriteFrameBuilder::new(FrameType::Instruction, name, inst).build_frame_parser()
+        WriteFrameBuilder::new(FrameType::Instruction, name, inst, codes, self.no_alloc)
+            .build_frame_parser()
     }
 
     fn write_frames_dispatch(
         &self,
         builder_type: FrameType,
         instructions: Vec<(u32, String, yaml_parser::InstFeedback)>,
+        codes: &yaml_parser::CodesFile,
     ) -> String {
         let dispatch_type = builder_type.long();
         let struct_name = builder_type.struct_name();
+        let parameters_argument = self.parameters_argument(builder_type);
         // To the frame decoding hub
         [
             format!(
                 r#"
-int parse_{dispatch_type}_frame(char* buffer, int len, {struct_name}* code, void **parameters)
+int parse_{dispatch_type}_frame(char* buffer, int len, {struct_name}* code, {parameters_argument})
 {{
     if ((buffer == NULL) || (code == NULL) || (parameters == NULL))
         return -3;
 
-    switch (buffer[0])
+    if (len < FRAME_CODE_SIZE)
+        return -1;
+
+    switch (parse_frame_code(buffer))
     {{
     "#
             ),
             instructions
                 .iter()
                 .map(|(_k, name, code)| {
-                    WriteFrameBuilder::new(builder_type, name, code).build_dispatch_case()
+                    WriteFrameBuilder::new(builder_type, name, code, codes, self.no_alloc)
+                        .build_dispatch_case()
                 })
                 .collect::<Vec<String>>()
                 .join(""),
@@ -428,19 +1273,149 @@ int parse_{dispatch_type}_frame(char* buffer, int len, {struct_name}* code, void
         .join("")
     }
 
+    /// Decoding of a received instruction passed to its handler, building the feedback it
+    /// answers with into the optional `feedback` buffer
+    fn implement_instruction_handlers_dispatch(&self, codes: &yaml_parser::CodesFile) -> String {
+        let instructions = codes.get_instructions();
+        format!(
+            r#"
+int dispatch_instruction_frame(char* buffer, int len, const instruction_handlers* handlers, void *ctx, char* feedback, int *feedback_len)
+{{
+    if ((buffer == NULL) || (handlers == NULL) || ((feedback != NULL) && (feedback_len == NULL)))
+        return -3;
+
+    {}
+
+    if (len < FRAME_CODE_SIZE)
+        return -1;
+
+    switch (parse_frame_code(buffer))
+    {{{}
+    default:
+        return -2;
+    }}
+}}
+"#,
+            // The capacity of the feedback buffer is only needed by the codes with a feedback
+            if instructions
+                .iter()
+                .any(|(id, _, _)| codes.has_feedback(*id))
+            {
+                r#"int capacity = 0;
+    if (feedback != NULL) {
+        capacity = *feedback_len;
+        *feedback_len = 0;
+    }"#
+            } else {
+                r#"if (feedback != NULL)
+        *feedback_len = 0;"#
+            },
+            instructions
+                .iter()
+                .map(|(id, name, instruction)| {
+                    WriteFrameBuilder::new(
+                        FrameType::Instruction,
+                        name,
+                        instruction,
+                        codes,
+                        self.no_alloc,
+                    )
+                    .build_handler_case(codes.has_feedback(*id))
+                })
+                .collect::<String>()
+        )
+    }
+
+    /// Releasing of the parameters allocated by the frames parsing functions
+    fn implement_frames_free(
+        &self,
+        frame_type: FrameType,
+        frames: &[(u32, String, yaml_parser::InstFeedback)],
+        codes: &yaml_parser::CodesFile,
+    ) -> String {
+        if self.no_alloc {
+            return String::new();
+        }
+        let type_long = frame_type.long();
+        let struct_name = frame_type.struct_name();
+        let builders = frames.iter().map(|(_, name, frame)| {
+            WriteFrameBuilder::new(frame_type, name, frame, codes, self.no_alloc)
+        });
+
+        format!(
+            r#"{}
+void free_{type_long}_params({struct_name}_params* parameters)
+{{
+    if (parameters == NULL)
+        return;
+
+    switch (parameters->code)
+    {{{}
+    default:
+        break;
+    }}
+    FRAME_FREE(parameters);
+}}
+"#,
+            builders
+                .clone()
+                .map(|builder| builder.build_frame_free())
+                .collect::<String>(),
+            builders
+                .map(|builder| builder.build_free_case())
+                .collect::<String>()
+        )
+    }
+
+    /// Decoding of the frames received on a byte stream, when a framing is configured
+    fn implement_stream_decoder(
+        &self,
+        frame_type: FrameType,
+        codes: &yaml_parser::CodesFile,
+    ) -> String {
+        if codes.framing.is_none() {
+            return String::new();
+        }
+
+        let type_long = frame_type.long();
+        let struct_name = frame_type.struct_name();
+        let parameters_argument = self.parameters_argument(frame_type);
+        format!(
+            r#"
+int stream_decode_{type_long}(stream_decoder *decoder, char *chunk, size_t len, size_t *consumed, {struct_name}* code, {parameters_argument})
+{{
+    if ((decoder == NULL) || (chunk == NULL) || (consumed == NULL))
+        return -3;
+
+    for (size_t i = 0; i < len; i++) {{
+        int frame_len = stream_decoder_push(decoder, chunk[i]);
+        if (frame_len != 0) {{
+            *consumed = i + 1;
+            if (frame_len < 0)
+                return frame_len;
+            return parse_{type_long}_frame(decoder->buffer, frame_len, code, parameters);
+        }}
+    }}
+    *consumed = len;
+    return FRAME_STREAM_INCOMPLETE;
+}}
+"#
+        )
+    }
+
     fn implement_feedback_frames_dispatch(&self, codes: &yaml_parser::CodesFile) -> String {
-        self.write_frames_dispatch(FrameType::Feedback, codes.get_feedbacks())
+        self.write_frames_dispatch(FrameType::Feedback, codes.get_feedbacks(), codes)
     }
 
     fn implement_instruction_frames_dispatch(&self, codes: &yaml_parser::CodesFile) -> String {
-        self.write_frames_dispatch(FrameType::Instruction, codes.get_instructions())
+        self.write_frames_dispatch(FrameType::Instruction, codes.get_instructions(), codes)
     }
 
     fn implement_feedbacks_builders(&self, codes: &crate::CodesFile) -> String {
         codes
             .get_feedbacks()
             .iter()
-            .map(|(k, name, inst)| self.write_feedback_frame_builder(*k, name, inst))
+            .map(|(k, name, inst)| self.write_feedback_frame_builder(*k, name, inst, codes))
             .collect::<Vec<String>>()
             .join("")
     }
@@ -449,7 +1424,7 @@ int parse_{dispatch_type}_frame(char* buffer, int len, {struct_name}* code, void
         codes
             .get_instructions()
             .iter()
-            .map(|(k, name, inst)| self.write_instruction_frame_builder(*k, name, inst))
+            .map(|(k, name, inst)| self.write_instruction_frame_builder(*k, name, inst, codes))
             .collect::<Vec<String>>()
             .join("")
     }
@@ -458,7 +1433,7 @@ int parse_{dispatch_type}_frame(char* buffer, int len, {struct_name}* code, void
         codes
             .get_feedbacks()
             .iter()
-            .map(|(k, name, fb)| self.write_feedback_frame_parser(*k, name, fb))
+            .map(|(k, name, fb)| self.write_feedback_frame_parser(*k, name, fb, codes))
             .collect::<Vec<String>>()
             .join("")
     }
@@ -467,8 +1442,131 @@ int parse_{dispatch_type}_frame(char* buffer, int len, {struct_name}* code, void
         codes
             .get_instructions()
             .iter()
-            .map(|(k, name, inst)| self.write_instruction_frame_parser(*k, name, inst))
+            .map(|(k, name, inst)| self.write_instruction_frame_parser(*k, name, inst, codes))
             .collect::<Vec<String>>()
             .join("")
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn model(platform: CPlatform, no_alloc: bool) -> CppLanguageModel {
+        CppLanguageModel {
+            headerfile_name: Some("codes.h".to_string()),
+            platform,
+            no_alloc,
+        }
+    }
+
+    #[test]
+    fn platforms_provide_their_allocator() {
+        for (platform, include, malloc, free) in [
+            (
+                CPlatform::Zephyr,
+                "#include <zephyr/zephyr.h>\n",
+                "k_malloc",
+                "k_free",
+            ),
+            (CPlatform::Libc, "", "malloc", "free"),
+            (
+                CPlatform::FreeRtos,
+                "#include \"FreeRTOS.h\"\n",
+                "pvPortMalloc",
+                "vPortFree",
+            ),
+        ] {
+            assert_eq!(
+                format!(
+                    "#include \"codes.h\"\n{include}#ifndef FRAME_MALLOC\n\
+                     #define FRAME_MALLOC(size) {malloc}(size)\n#endif\n\
+                     #ifndef FRAME_FREE\n#define FRAME_FREE(pointer) {free}(pointer)\n#endif\n"
+                ),
+                model(platform, false).custom_includes(),
+                "{platform:?} includes"
+            );
+        }
+    }
+
+    #[test]
+    fn custom_platform_requires_the_allocator_macros() {
+        let includes = model(CPlatform::Custom, false).custom_includes();
+
+        assert_eq!(
+            "#include \"codes.h\"\n#if !defined(FRAME_MALLOC) || !defined(FRAME_FREE)\n\
+             #error \"Define FRAME_MALLOC(size) and FRAME_FREE(pointer) for the custom platform\"\n\
+             #endif\n",
+            includes
+        );
+        assert!(!includes.contains("#define"));
+    }
+
+    #[test]
+    fn allocation_free_code_has_no_allocator() {
+        for platform in [
+            CPlatform::Zephyr,
+            CPlatform::Libc,
+            CPlatform::FreeRtos,
+            CPlatform::Custom,
+        ] {
+            assert_eq!(
+                "#include \"codes.h\"\n",
+                model(platform, true).custom_includes(),
+                "{platform:?} includes"
+            );
+        }
+    }
+
+    #[test]
+    fn platforms_are_parsed_case_insensitively() {
+        assert_eq!(Ok(CPlatform::Zephyr), "zephyr".parse());
+        assert_eq!(Ok(CPlatform::Libc), "LibC".parse());
+        assert_eq!(Ok(CPlatform::FreeRtos), "FreeRTOS".parse());
+        assert_eq!(Ok(CPlatform::Custom), "custom".parse());
+    }
+
+    #[test]
+    fn unknown_platforms_are_rejected() {
+        assert_eq!(
+            Err("C platform must be zephyr, libc, freertos or custom, not posix".to_string()),
+            "posix".parse::<CPlatform>()
+        );
+        assert_eq!(
+            Err("C platform must be zephyr, libc, freertos or custom, not ".to_string()),
+            "".parse::<CPlatform>()
+        );
+    }
+
+    #[test]
+    fn c_platform_option_is_checked() {
+        let arguments = |platform: Option<&str>| {
+            let mut argv = vec!["codes-parser", "-i", "codes.yml", "-c", "codes.c"];
+            if let Some(platform) = platform {
+                argv.extend(["--c-platform", platform]);
+            }
+            crate::Arguments::new(argv.into_iter().map(String::from).collect())
+        };
+
+        assert_eq!(
+            Some(CPlatform::Zephyr),
+            arguments(None).ok().map(|arguments| arguments.c_platform)
+        );
+        assert_eq!(
+            Some(CPlatform::FreeRtos),
+            arguments(Some("freertos"))
+                .ok()
+                .map(|arguments| arguments.c_platform)
+        );
+        match arguments(Some("posix")) {
+            Err(error @ crate::Error::Usage(_)) => {
+                assert_eq!(
+                    "C platform must be zephyr, libc, freertos or custom, not posix",
+                    error.to_string()
+                );
+                assert_eq!(1, error.exit_code());
+            }
+            _ => panic!("The posix platform should be rejected"),
+        }
+    }
+}
@@ -1,5 +1,14 @@
 mod cpp;
+mod python;
 mod rust;
+mod rust_no_std;
+mod rust_ref;
+mod typescript;
+mod wireshark;
 
-pub use cpp::CppLanguageModel;
+pub use cpp::{CPlatform, CppLanguageModel};
+pub use python::PythonLanguageModel;
 pub use rust::RustLanguageModel;
+pub use rust_no_std::RustNoStdLanguageModel;
+pub use typescript::TypeScriptLanguageModel;
+pub use wireshark::WiresharkLanguageModel;
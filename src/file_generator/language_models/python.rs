@@ -0,0 +1,464 @@
+use crate::file_generator::{FrameType, LanguageModel};
+use crate::yaml_parser::{self, ParameterType};
+
+impl yaml_parser::ParameterType {
+    fn to_python_type_string(&self) -> String {
+        match self {
+            ParameterType::Array { item, .. } | ParameterType::FixedArray { item, .. } => {
+                format!("List[{}]", item.to_python_type_string())
+            }
+            ParameterType::Custom(name) => name.clone(),
+            ParameterType::String => "str".to_string(),
+            ParameterType::Float32 | ParameterType::Float64 => "float".to_string(),
+            ParameterType::Bool => "bool".to_string(),
+            _ => "int".to_string(),
+        }
+    }
+
+    /// `struct` module format character of the scalar types
+    fn to_python_struct_format(&self) -> &str {
+        match self {
+            ParameterType::Uint8 => "B",
+            ParameterType::Int8 => "b",
+            ParameterType::Uint16 => "H",
+            ParameterType::Int16 => "h",
+            ParameterType::Uint32 => "I",
+            ParameterType::Int32 => "i",
+            ParameterType::Uint64 => "Q",
+            ParameterType::Int64 => "q",
+            ParameterType::Float32 => "f",
+            ParameterType::Float64 => "d",
+            ParameterType::Bool => "?",
+            _ => unreachable!("{self} isn't a scalar type"),
+        }
+    }
+
+    /// Expression parsing a value of this type from the `reader`
+    fn to_python_parsing_call(&self) -> String {
+        match self {
+            ParameterType::Array { item, length } => format!(
+                "reader.array(\"{}\", lambda: {})",
+                length.to_python_struct_format(),
+                item.to_python_parsing_call()
+            ),
+            ParameterType::FixedArray { item, count } => format!(
+                "reader.fixed_array({count}, lambda: {})",
+                item.to_python_parsing_call()
+            ),
+            ParameterType::Custom(name) => format!("{name}._read(reader)"),
+            ParameterType::String => "reader.string()".to_string(),
+            _ => format!("reader.unpack(\"{}\")", self.to_python_struct_format()),
+        }
+    }
+
+    /// Expression encoding the `value` of this type into `bytes`
+    fn to_python_writing_call(&self, value: &str) -> String {
+        match self {
+            ParameterType::Array { item, length } => format!(
+                "_pack_array(\"{}\", [{} for item in {value}])",
+                length.to_python_struct_format(),
+                item.to_python_writing_call("item")
+            ),
+            ParameterType::FixedArray { item, count } => format!(
+                "_pack_fixed_array({count}, [{} for item in {value}])",
+                item.to_python_writing_call("item")
+            ),
+            ParameterType::Custom(_) => format!("{value}._write()"),
+            ParameterType::String => format!("_pack_string({value})"),
+            _ => format!("_pack(\"{}\", {value})", self.to_python_struct_format()),
+        }
+    }
+}
+
+impl yaml_parser::InstFeedbackParameter {
+    fn to_python_type_string(&self) -> String {
+        if self.optional {
+            format!("Optional[{}]", self.data_type.to_python_type_string())
+        } else {
+            self.data_type.to_python_type_string()
+        }
+    }
+
+    /// Expression parsing this parameter, preceded by its presence flag when optional
+    fn to_python_parsing_call(&self) -> String {
+        if self.optional {
+            format!(
+                "reader.optional(lambda: {})",
+                self.data_type.to_python_parsing_call()
+            )
+        } else {
+            self.data_type.to_python_parsing_call()
+        }
+    }
+
+    /// Expression encoding the `value` of this parameter into `bytes`
+    fn to_python_writing_call(&self, value: &str) -> String {
+        if self.optional {
+            format!(
+                "_pack_optional({value}, lambda value: {})",
+                self.data_type.to_python_writing_call("value")
+            )
+        } else {
+            self.data_type.to_python_writing_call(value)
+        }
+    }
+}
+
+pub struct PythonLanguageModel {}
+
+impl LanguageModel for PythonLanguageModel {
+    fn custom_includes(&self) -> String {
+        String::new()
+    }
+
+    fn custom_footer(&self, _codes: &crate::CodesFile) -> String {
+        String::new()
+    }
+
+    fn declare_settings(&self, codes: &crate::CodesFile) -> String {
+        format!(
+            "BYTE_ORDER = \"{}\"\nCODE_FORMAT = \"{}\"\nCHECKSUM: Optional[str] = {}\n",
+            match codes.endianness {
+                yaml_parser::Endianness::Little => "<",
+                yaml_parser::Endianness::Big => ">",
+            },
+            codes.code_width.data_type().to_python_struct_format(),
+            match codes.checksum {
+                Some(checksum) => format!("\"{}\"", format!("{checksum:?}").to_lowercase()),
+                None => "None".to_string(),
+            }
+        )
+    }
+
+    fn declare_types(&self, codes: &crate::CodesFile) -> String {
+        codes
+            .enums
+            .iter()
+            .map(|(name, enumeration)| Self::declare_enum(name, enumeration))
+            .chain(
+                codes
+                    .bitfields
+                    .iter()
+                    .map(|(name, bitfield)| Self::declare_bitfield(name, bitfield)),
+            )
+            .chain(
+                codes
+                    .types
+                    .iter()
+                    .map(|(name, structure)| Self::declare_struct(name, structure)),
+            )
+            .collect::<String>()
+    }
+
+    /// Python classes hold their methods, see `declare_types`
+    fn implement_types(&self, _codes: &crate::CodesFile) -> String {
+        String::new()
+    }
+
+    fn declare_feedbacks(&self, codes: &crate::CodesFile) -> String {
+        Self::declare_frames(FrameType::Feedback, &codes.get_feedbacks())
+    }
+
+    fn declare_instructions(&self, codes: &crate::CodesFile) -> String {
+        Self::declare_frames(FrameType::Instruction, &codes.get_instructions())
+    }
+
+    fn implement_feedbacks(&self, codes: &crate::CodesFile) -> String {
+        Self::implement_dispatch(FrameType::Feedback, &codes.get_feedbacks())
+    }
+
+    fn implement_instructions(&self, codes: &crate::CodesFile) -> String {
+        Self::implement_dispatch(FrameType::Instruction, &codes.get_instructions())
+    }
+}
+
+impl PythonLanguageModel {
+    fn declare_enum(name: &str, enumeration: &yaml_parser::EnumType) -> String {
+        let format = enumeration.data_type.to_python_struct_format();
+        format!(
+            r#"
+
+class {name}(IntEnum):
+    {}
+
+    {}
+
+    def _write(self) -> bytes:
+        return _pack("{format}", self)
+
+    @classmethod
+    def _read(cls, reader: _Reader) -> {name}:
+        return reader.enum(cls, "{format}")
+"#,
+            docstring(&enumeration.description, 1),
+            enumeration
+                .values
+                .iter()
+                .map(|v| format!("{} = {}  # {}", v.name, v.value, v.description))
+                .collect::<Vec<String>>()
+                .join("\n    ")
+        )
+    }
+
+    fn declare_bitfield(name: &str, bitfield: &yaml_parser::BitfieldType) -> String {
+        let format = bitfield.data_type.to_python_struct_format();
+        format!(
+            r#"
+
+@dataclass
+class {name}:
+    {}
+
+    value: int = 0
+
+    {}
+
+    def _write(self) -> bytes:
+        return _pack("{format}", self.value)
+
+    @classmethod
+    def _read(cls, reader: _Reader) -> {name}:
+        return cls(reader.unpack("{format}"))
+"#,
+            docstring(&bitfield.description, 1),
+            bitfield
+                .fields_with_offsets()
+                .into_iter()
+                .map(|(field, offset)| Self::bitfield_accessors(field, offset))
+                .collect::<Vec<String>>()
+                .join("\n\n    ")
+        )
+    }
+
+    /// Property of a bitfield field, single bits are accessed as `bool`
+    fn bitfield_accessors(field: &yaml_parser::BitfieldField, offset: u32) -> String {
+        let name = &field.name;
+        let mask = format!("{:#x}", field.mask());
+        let (shifted, cleared, stored) = if offset == 0 {
+            (
+                "self.value".to_string(),
+                format!("~{mask}"),
+                format!("int(field) & {mask}"),
+            )
+        } else {
+            (
+                format!("(self.value >> {offset})"),
+                format!("~({mask} << {offset})"),
+                format!("(int(field) & {mask}) << {offset}"),
+            )
+        };
+        let (field_type, getter) = if field.bits == 1 {
+            ("bool", format!("{shifted} & 1 != 0"))
+        } else {
+            ("int", format!("{shifted} & {mask}"))
+        };
+
+        format!(
+            r#"@property
+    def {name}(self) -> {field_type}:
+        {}
+        return {getter}
+
+    @{name}.setter
+    def {name}(self, field: {field_type}) -> None:
+        self.value = (self.value & {cleared}) | ({stored})"#,
+            docstring(&field.description, 2)
+        )
+    }
+
+    fn declare_struct(name: &str, structure: &yaml_parser::InstFeedback) -> String {
+        format!(
+            r#"
+
+@dataclass
+class {name}:
+{}"#,
+            Self::dataclass_body(name, structure, None, 1)
+        )
+    }
+
+    /// Namespace class holding a dataclass per frame
+    fn declare_frames(
+        frame_type: FrameType,
+        frames: &[(u32, String, yaml_parser::InstFeedback)],
+    ) -> String {
+        let classes = frames
+            .iter()
+            .map(|(id, name, frame)| {
+                format!(
+                    r#"
+    @dataclass
+    class {name}(_Frame):
+{}"#,
+                    Self::dataclass_body(
+                        &format!("{}.{name}", frame_type.struct_name()),
+                        frame,
+                        Some(*id),
+                        2
+                    )
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("");
+
+        format!(
+            r#"
+
+class {}:
+    {}
+{}"#,
+            frame_type.struct_name(),
+            docstring(
+                &format!("Dataclasses of the {} frames", frame_type.long()),
+                1
+            ),
+            classes
+        )
+    }
+
+    /// Fields and encoding methods of a structure or frame dataclass
+    fn dataclass_body(
+        name: &str,
+        structure: &yaml_parser::InstFeedback,
+        code: Option<u32>,
+        level: usize,
+    ) -> String {
+        let indent = "    ".repeat(level);
+        let code = code
+            .map(|code| format!("\n{indent}CODE: ClassVar[int] = {code:#04x}"))
+            .unwrap_or_default();
+        let fields = structure
+            .parameters
+            .iter()
+            .map(|p| {
+                format!(
+                    "\n{indent}{}: {}  # {}",
+                    p.name,
+                    p.to_python_type_string(),
+                    p.description.trim_end().replace('\n', " ")
+                )
+            })
+            .collect::<String>();
+        let writes = if structure.parameters.is_empty() {
+            "b\"\"".to_string()
+        } else {
+            format!(
+                "b\"\".join(\n{indent}        [\n{}\n{indent}        ]\n{indent}    )",
+                structure
+                    .parameters
+                    .iter()
+                    .map(|p| format!(
+                        "{indent}            {},",
+                        p.to_python_writing_call(&format!("self.{}", p.name))
+                    ))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            )
+        };
+        let reads = structure
+            .parameters
+            .iter()
+            .map(|p| {
+                format!(
+                    "\n{indent}        {}={},",
+                    p.name,
+                    p.to_python_parsing_call()
+                )
+            })
+            .collect::<String>();
+        let reads = if reads.is_empty() {
+            reads
+        } else {
+            format!("{reads}\n{indent}    ")
+        };
+
+        format!(
+            r#"{indent}{}
+{code}{fields}
+
+{indent}def _write(self) -> bytes:
+{indent}    return {writes}
+
+{indent}@classmethod
+{indent}def _read(cls, reader: _Reader) -> {name}:
+{indent}    return cls({reads})
+"#,
+            docstring(&structure.description, level)
+        )
+    }
+
+    /// Frame classes by code, and the function parsing a frame into the class of its code
+    fn implement_dispatch(
+        frame_type: FrameType,
+        frames: &[(u32, String, yaml_parser::InstFeedback)],
+    ) -> String {
+        let by_code = format!("{}_BY_CODE", frame_type.struct_name().to_uppercase());
+        format!(
+            r#"
+
+{by_code}: Dict[int, Type[_Frame]] = {{{}
+}}
+
+
+def parse_{}(frame: bytes) -> _Frame:
+    """Parses a received {} frame into the dataclass of its code"""
+    return _parse_frame({by_code}, frame)
+"#,
+            frames
+                .iter()
+                .map(|(id, name, _)| format!(
+                    "\n    {id:#04x}: {}.{name},",
+                    frame_type.struct_name()
+                ))
+                .collect::<String>(),
+            frame_type.long(),
+            frame_type.long()
+        )
+    }
+}
+
+/// Turns a possibly multi-line description into a docstring indented by `level`
+fn docstring(description: &str, level: usize) -> String {
+    format!(
+        "\"\"\"{}\"\"\"",
+        description
+            .trim_end()
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', &format!("\n{}", "    ".repeat(level)))
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::yaml_parser::test::integration_codes_file;
+    use crate::yaml_parser::{Checksum, CodeWidth, Endianness};
+
+    #[test]
+    fn settings_follow_the_wire_format() {
+        let mut codes = integration_codes_file();
+        let model = PythonLanguageModel {};
+
+        assert_eq!(
+            "BYTE_ORDER = \"<\"\nCODE_FORMAT = \"B\"\nCHECKSUM: Optional[str] = None\n",
+            model.declare_settings(&codes)
+        );
+
+        codes.endianness = Endianness::Big;
+        codes.code_width = CodeWidth::Two;
+        codes.checksum = Some(Checksum::Crc16);
+        assert_eq!(
+            "BYTE_ORDER = \">\"\nCODE_FORMAT = \"H\"\nCHECKSUM: Optional[str] = \"crc16\"\n",
+            model.declare_settings(&codes)
+        );
+    }
+
+    #[test]
+    fn descriptions_are_escaped_in_docstrings() {
+        assert_eq!(
+            "\"\"\"Quoted \\\"value\\\"\n    on two lines\"\"\"",
+            docstring("Quoted \"value\"\non two lines\n", 1)
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::file_generator::LanguageModel;
+use crate::file_generator::{FrameType, LanguageModel};
 use crate::yaml_parser::{self, ParameterType};
 
 impl yaml_parser::ParameterType {
@@ -12,8 +12,13 @@ impl yaml_parser::ParameterType {
             ParameterType::Uint32 => "U32",
             ParameterType::Int64 => "I64",
             ParameterType::Uint64 => "U64",
+            ParameterType::Float32 => "F32",
+            ParameterType::Float64 => "F64",
             ParameterType::Bool => "Bool",
             ParameterType::String => "Str",
+            ParameterType::Array { .. } => "Array",
+            ParameterType::FixedArray { .. } => "FixedArray",
+            ParameterType::Custom(name) => name.as_str(),
         }
     }
 
@@ -27,8 +32,174 @@ impl yaml_parser::ParameterType {
             ParameterType::Uint32 => "u32",
             ParameterType::Int64 => "i64",
             ParameterType::Uint64 => "u64",
+            ParameterType::Float32 => "f32",
+            ParameterType::Float64 => "f64",
             ParameterType::Bool => "bool",
             ParameterType::String => "string",
+            ParameterType::Array { .. } => "array",
+            ParameterType::FixedArray { .. } => "fixed_array",
+            ParameterType::Custom(name) => name.as_str(),
+        }
+    }
+
+    /// Whether the Rust type of this parameter implements `Eq`
+    pub(super) fn implements_eq(&self, codes: &crate::CodesFile) -> bool {
+        match self {
+            ParameterType::Float32 | ParameterType::Float64 => false,
+            ParameterType::Array { item, .. } | ParameterType::FixedArray { item, .. } => {
+                item.implements_eq(codes)
+            }
+            ParameterType::Custom(name) => codes.types.get(name).is_none_or(|structure| {
+                structure
+                    .parameters
+                    .iter()
+                    .all(|p| p.data_type.implements_eq(codes))
+            }),
+            _ => true,
+        }
+    }
+
+    /// Whether the Rust type of this parameter implements `Copy`
+    fn implements_copy(&self, codes: &crate::CodesFile) -> bool {
+        match self {
+            ParameterType::String | ParameterType::Array { .. } => false,
+            ParameterType::FixedArray { item, .. } => item.implements_copy(codes),
+            ParameterType::Custom(name) => !codes.types.contains_key(name),
+            _ => true,
+        }
+    }
+
+    /// Field attribute needed to derive `Serialize`, serde only implements arrays up to 32 items
+    fn to_rust_serde_attribute(&self) -> &str {
+        match self {
+            ParameterType::FixedArray { count, .. } if *count > 32 => {
+                "#[serde(serialize_with = \"TypesEnum::serialize_fixed_array\")] "
+            }
+            _ => "",
+        }
+    }
+
+    /// Expression parsing a value of this type from the `buffer` slice
+    fn to_rust_parsing_call(&self, buffer: &str) -> String {
+        match self {
+            ParameterType::Array { item, length } => format!(
+                "TypesEnum::array_from_buffer({buffer}, {}, |{buffer}| {})",
+                length.size(),
+                item.to_rust_parsing_call(buffer)
+            ),
+            ParameterType::FixedArray { item, .. } => format!(
+                "TypesEnum::fixed_array_from_buffer({buffer}, |{buffer}| {})",
+                item.to_rust_parsing_call(buffer)
+            ),
+            ParameterType::Custom(name) => format!("{name}::from_buffer({buffer})"),
+            _ => format!(
+                "TypesEnum::{}_from_buffer({buffer})",
+                self.to_typesenum_parsing_funtion_type()
+            ),
+        }
+    }
+
+    /// Whether encoding this type can fail, arrays failing when their items count doesn't
+    /// fit its length type
+    fn writing_can_fail(&self) -> bool {
+        match self {
+            ParameterType::Array { .. } | ParameterType::Custom(_) => true,
+            ParameterType::FixedArray { item, .. } => item.writing_can_fail(),
+            _ => false,
+        }
+    }
+
+    /// Expression encoding the owned `value` of this type into a `Vec<u8>`, returning the
+    /// encoding error from the enclosing function
+    fn to_rust_writing_call(&self, value: &str) -> String {
+        match self {
+            _ if self.writing_can_fail() => format!("{}?", self.to_rust_try_writing_call(value)),
+            ParameterType::FixedArray { item, .. } => format!(
+                "TypesEnum::fixed_array_to_buffer({value}.into_iter().map(|item| {}).collect())",
+                item.to_rust_writing_call("item")
+            ),
+            _ => format!(
+                "TypesEnum::{}({value}).write_to_buffer()",
+                self.to_typesenum_name()
+            ),
+        }
+    }
+
+    /// Expression encoding the owned `value` of this type into a
+    /// `Result<Vec<u8>, TypesEnumError>`
+    fn to_rust_try_writing_call(&self, value: &str) -> String {
+        let try_items = |item: &ParameterType| {
+            format!(
+                "{value}.into_iter().map(|item| {}).collect::<Result<_, _>>()",
+                item.to_rust_try_writing_call("item")
+            )
+        };
+        match self {
+            ParameterType::Array { item, length } if item.writing_can_fail() => format!(
+                "{}.and_then(|items| TypesEnum::array_to_buffer({}, items))",
+                try_items(item),
+                length.size()
+            ),
+            ParameterType::Array { item, length } => format!(
+                "TypesEnum::array_to_buffer({}, {value}.into_iter().map(|item| {}).collect())",
+                length.size(),
+                item.to_rust_writing_call("item")
+            ),
+            ParameterType::FixedArray { item, .. } if item.writing_can_fail() => {
+                format!("{}.map(TypesEnum::fixed_array_to_buffer)", try_items(item))
+            }
+            ParameterType::Custom(_) => format!("{value}.try_write_to_buffer()"),
+            _ => format!("Ok({})", self.to_rust_writing_call(value)),
+        }
+    }
+}
+
+impl yaml_parser::InstFeedbackParameter {
+    fn to_rust_type_string(&self) -> String {
+        if self.optional {
+            format!("Option<{}>", self.data_type.to_rust_type_string())
+        } else {
+            self.data_type.to_rust_type_string()
+        }
+    }
+
+    fn to_rust_serde_attribute(&self) -> &str {
+        match self.data_type.to_rust_serde_attribute() {
+            "" => "",
+            _ if self.optional => {
+                "#[serde(serialize_with = \"TypesEnum::serialize_optional_fixed_array\")] "
+            }
+            attribute => attribute,
+        }
+    }
+
+    /// Expression parsing this parameter, preceded by its presence flag when optional
+    fn to_rust_parsing_call(&self, buffer: &str) -> String {
+        if self.optional {
+            format!(
+                "TypesEnum::optional_from_buffer({buffer}, |{buffer}| {})",
+                self.data_type.to_rust_parsing_call(buffer)
+            )
+        } else {
+            self.data_type.to_rust_parsing_call(buffer)
+        }
+    }
+
+    /// Expression encoding the owned `value` of this parameter into a `Vec<u8>`, returning
+    /// the encoding error from the enclosing function
+    fn to_rust_writing_call(&self, value: &str) -> String {
+        if self.optional && self.data_type.writing_can_fail() {
+            format!(
+                "TypesEnum::optional_to_buffer({value}.map(|value| {}).transpose()?)",
+                self.data_type.to_rust_try_writing_call("value")
+            )
+        } else if self.optional {
+            format!(
+                "TypesEnum::optional_to_buffer({value}.map(|value| {}))",
+                self.data_type.to_rust_writing_call("value")
+            )
+        } else {
+            self.data_type.to_rust_writing_call(value)
         }
     }
 }
@@ -40,14 +211,67 @@ impl LanguageModel for RustLanguageModel {
         String::new()
     }
 
-    fn custom_footer(&self) -> String {
+    fn custom_footer(&self, _codes: &crate::CodesFile) -> String {
         String::new()
     }
 
+    fn declare_settings(&self, codes: &crate::CodesFile) -> String {
+        format!(
+            "const BIG_ENDIAN: bool = {};\nconst CHECKSUM: Option<Checksum> = {};\n",
+            codes.endianness == yaml_parser::Endianness::Big,
+            match codes.checksum {
+                Some(checksum) => format!("Some(Checksum::{checksum:?})"),
+                None => "None".to_string(),
+            }
+        )
+    }
+
+    fn declare_types(&self, codes: &crate::CodesFile) -> String {
+        codes
+            .enums
+            .iter()
+            .map(|(name, enumeration)| Self::declare_enum(name, enumeration))
+            .chain(
+                codes
+                    .bitfields
+                    .iter()
+                    .map(|(name, bitfield)| Self::declare_bitfield(name, bitfield)),
+            )
+            .chain(
+                codes
+                    .types
+                    .iter()
+                    .map(|(name, structure)| Self::declare_struct(name, structure, codes)),
+            )
+            .chain([Self::declare_ref_types(codes)])
+            .collect::<String>()
+    }
+
+    fn implement_types(&self, codes: &crate::CodesFile) -> String {
+        codes
+            .enums
+            .iter()
+            .map(|(name, enumeration)| Self::implement_enum(name, enumeration))
+            .chain(
+                codes
+                    .bitfields
+                    .iter()
+                    .map(|(name, bitfield)| Self::implement_bitfield(name, bitfield)),
+            )
+            .chain(
+                codes
+                    .types
+                    .iter()
+                    .map(|(name, structure)| Self::implement_struct(name, structure, codes)),
+            )
+            .chain([Self::implement_ref_types(codes)])
+            .collect::<String>()
+    }
+
     fn declare_instructions(&self, codes: &crate::CodesFile) -> String {
         [
+            Self::derive_attribute(&codes.get_instructions(), codes),
             r#"
-#[derive(PartialEq, Eq, Clone, Serialize, Debug)]
 pub enum Instructions {
     "#
             .to_string(),
@@ -72,14 +296,16 @@ pub enum Instructions {
 
 "#
             .to_string(),
+            Self::declare_ref_frames(FrameType::Instruction, &codes.get_instructions(), codes),
+            Self::declare_handler(codes),
         ]
         .join("")
     }
 
     fn declare_feedbacks(&self, codes: &crate::CodesFile) -> String {
         [
+            Self::derive_attribute(&codes.get_feedbacks(), codes),
             r#"
-#[derive(PartialEq, Eq, Clone, Serialize, Debug)]
 pub enum Feedbacks {
     "#
             .to_string(),
@@ -105,16 +331,28 @@ pub enum Feedbacks {
 
 "#
             .to_string(),
+            Self::declare_ref_frames(FrameType::Feedback, &codes.get_feedbacks(), codes),
         ]
         .join("")
     }
 
     fn implement_feedbacks(&self, codes: &crate::CodesFile) -> String {
         [
+            Self::implement_stream("Feedbacks", "feedbacks", codes),
             r#"
 impl Feedbacks {
+    /// Encodes the frame.
+    ///
+    /// Panics when an array holds more items than its count can tell, which
+    /// `try_to_bytes` reports instead.
     pub fn to_bytes(self) -> Vec<u8> {
-        match self {
+        self.try_to_bytes()
+            .expect("Array items count doesn't fit its length")
+    }
+
+    /// Encodes the frame, failing when an array holds more items than its count can tell
+    pub fn try_to_bytes(self) -> Result<Vec<u8>, TypesEnumError> {
+        let frame = match self {
             "#
             .to_string(),
             codes
@@ -123,16 +361,22 @@ impl Feedbacks {
                 .filter_map(|(&id, code)| {
                     code.feedback
                         .clone()
-                        .map(|code_fb| build_frame_from_fields(id, code, &code_fb))
+                        .map(|code_fb| build_frame_from_fields(id, code, &code_fb, codes))
                 })
                 .collect::<Vec<String>>()
                 .join(",\n\t\t\t"),
             r#"
-        }
+        };
+        Ok(TypesEnum::append_checksum(frame))
     }
 
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, TypesEnumError> {
-        match bytes[0] {
+        let bytes = verify_checksum(bytes)?;
+        let (code, bytes) = "#
+                .to_string(),
+            codes.code_width.data_type().to_rust_parsing_call("bytes"),
+            r#"?;
+        match code {
             "#
             .to_string(),
             codes
@@ -141,7 +385,7 @@ impl Feedbacks {
                 .filter_map(|(&id, code)| {
                     code.feedback
                         .clone()
-                        .map(|code_fb| parse_frame_to_fields(id, code, &code_fb))
+                        .map(|code_fb| parse_frame_to_fields(id, code, &code_fb, codes))
                 })
                 .collect::<Vec<String>>()
                 .join(",\n\t\t\t"),
@@ -152,17 +396,29 @@ impl Feedbacks {
 }
 "#
             .to_string(),
+            Self::implement_ref_frames(FrameType::Feedback, &codes.get_feedbacks(), codes),
         ]
         .join("")
     }
 
     fn implement_instructions(&self, codes: &crate::CodesFile) -> String {
         [
+            Self::implement_stream("Instructions", "instructions", codes),
             r#"
 
 impl Instructions {
+    /// Encodes the frame.
+    ///
+    /// Panics when an array holds more items than its count can tell, which
+    /// `try_to_bytes` reports instead.
     pub fn to_bytes(self) -> Vec<u8> {
-        match self {
+        self.try_to_bytes()
+            .expect("Array items count doesn't fit its length")
+    }
+
+    /// Encodes the frame, failing when an array holds more items than its count can tell
+    pub fn try_to_bytes(self) -> Result<Vec<u8>, TypesEnumError> {
+        let frame = match self {
     "#
             .to_string(),
             codes
@@ -171,16 +427,22 @@ impl Instructions {
                 .filter_map(|(&id, code)| {
                     code.instruction
                         .clone()
-                        .map(|code_fb| build_frame_from_fields(id, code, &code_fb))
+                        .map(|code_fb| build_frame_from_fields(id, code, &code_fb, codes))
                 })
                 .collect::<Vec<String>>()
                 .join(",\n\t\t\t"),
             r#"
-        }
+        };
+        Ok(TypesEnum::append_checksum(frame))
     }
 
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, TypesEnumError> {
-        match bytes[0] {
+        let bytes = verify_checksum(bytes)?;
+        let (code, bytes) = "#
+                .to_string(),
+            codes.code_width.data_type().to_rust_parsing_call("bytes"),
+            r#"?;
+        match code {
             "#
             .to_string(),
             codes
@@ -189,7 +451,7 @@ impl Instructions {
                 .filter_map(|(&id, code)| {
                     code.instruction
                         .clone()
-                        .map(|code_fb| parse_frame_to_fields(id, code, &code_fb))
+                        .map(|code_fb| parse_frame_to_fields(id, code, &code_fb, codes))
                 })
                 .collect::<Vec<String>>()
                 .join(",\n\t\t\t"),
@@ -200,34 +462,440 @@ impl Instructions {
 }
 "#
             .to_string(),
+            Self::implement_ref_frames(FrameType::Instruction, &codes.get_instructions(), codes),
+            Self::implement_dispatch(codes),
         ]
         .join("")
     }
 }
 
 impl RustLanguageModel {
+    /// Feedbacks answering an instruction of the same code
+    fn paired_feedbacks(codes: &crate::CodesFile) -> Vec<(u32, String, yaml_parser::InstFeedback)> {
+        codes
+            .get_feedbacks()
+            .into_iter()
+            .filter(|(id, _, _)| codes.codes[id].instruction.is_some())
+            .collect()
+    }
+
+    /// Trait with a method per instruction, returning the feedback of the same code if any
+    /// as its `<Name>Feedback` structure
+    fn declare_handler(codes: &crate::CodesFile) -> String {
+        let feedbacks = Self::paired_feedbacks(codes)
+            .iter()
+            .map(|(_, name, feedback)| {
+                Self::declare_struct(&format!("{name}Feedback"), feedback, codes)
+            })
+            .collect::<String>();
+        format!(
+            r#"{feedbacks}
+/// Handlers of the received instructions, called by `dispatch`. The handlers of the codes
+/// with a feedback answer with its `<Name>Feedback` structure.
+pub trait Handler {{{}
+}}
+"#,
+            codes
+                .get_instructions()
+                .iter()
+                .map(|(id, name, instruction)| {
+                    format!(
+                        "\n{}\n    fn on_{}(&mut self{}){};",
+                        instruction
+                            .description
+                            .trim_end()
+                            .lines()
+                            .map(|line| format!("    /// {line}"))
+                            .collect::<Vec<String>>()
+                            .join("\n"),
+                        snake_case(name),
+                        instruction
+                            .parameters
+                            .iter()
+                            .map(|p| format!(", {}: {}", p.name, p.to_rust_type_string()))
+                            .collect::<String>(),
+                        if codes.has_feedback(*id) {
+                            format!(" -> {name}Feedback")
+                        } else {
+                            String::new()
+                        }
+                    )
+                })
+                .collect::<String>()
+        )
+    }
+
+    /// Decoding of a received instruction passed to its handler, and encoding of the
+    /// feedback it answers with
+    fn implement_dispatch(codes: &crate::CodesFile) -> String {
+        let conversions = Self::paired_feedbacks(codes)
+            .iter()
+            .map(|(_, name, feedback)| {
+                let fields = feedback
+                    .parameters
+                    .iter()
+                    .map(|p| p.name.clone())
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                format!(
+                    r#"
+impl From<{name}Feedback> for Feedbacks {{
+    fn from(feedback: {name}Feedback) -> Self {{
+        let {} = feedback;
+        {}
+    }}
+}}
+"#,
+                    struct_pattern(&format!("{name}Feedback"), &fields),
+                    struct_pattern(&format!("Self::{name}"), &fields)
+                )
+            })
+            .collect::<String>();
+        format!(
+            r#"{conversions}
+/// Decodes a received instruction, passes it to its `handler` method, and returns the
+/// encoded feedback it answers with, which is empty for the codes without feedback
+pub fn dispatch(handler: &mut impl Handler, frame: &[u8]) -> Result<Vec<u8>, TypesEnumError> {{
+    let feedback = match Instructions::from_bytes(frame)? {{{}
+    }};
+    Ok(feedback)
+}}
+"#,
+            codes
+                .get_instructions()
+                .iter()
+                .map(|(id, name, instruction)| {
+                    let fields = instruction
+                        .parameters
+                        .iter()
+                        .map(|p| p.name.clone())
+                        .collect::<Vec<String>>()
+                        .join(", ");
+                    let call = format!("handler.on_{}({fields})", snake_case(name));
+                    let pattern = struct_pattern(&format!("Instructions::{name}"), &fields);
+                    if codes.has_feedback(*id) {
+                        format!("\n        {pattern} => Feedbacks::from({call}).try_to_bytes()?,")
+                    } else {
+                        format!(
+                            "\n        {pattern} => {{\n            {call};\n            Vec::new()\n        }}"
+                        )
+                    }
+                })
+                .collect::<String>()
+        )
+    }
+
+    /// Stream encoding and decoding of the frames, when a framing is configured
+    fn implement_stream(type_name: &str, lowercase_name: &str, codes: &crate::CodesFile) -> String {
+        let Some(framing) = codes.framing else {
+            return String::new();
+        };
+
+        format!(
+            r#"
+impl {type_name} {{
+    /// Encodes the frame delimited for a byte stream
+    pub fn to_stream_bytes(self) -> Result<Vec<u8>, TypesEnumError> {{
+        Framing::{framing:?}.encode(&self.try_to_bytes()?)
+    }}
+}}
+
+impl StreamDecoder {{
+    /// Feeds the received `chunk`, and returns the {lowercase_name} it completes
+    pub fn decode_{lowercase_name}(&mut self, chunk: &[u8]) -> Vec<Result<{type_name}, TypesEnumError>> {{
+        self.push(chunk)
+            .into_iter()
+            .map(|frame| frame.and_then(|frame| {type_name}::from_bytes(&frame)))
+            .collect()
+    }}
+}}
+"#
+        )
+    }
+
+    fn declare_enum(name: &str, enumeration: &yaml_parser::EnumType) -> String {
+        format!(
+            r#"
+{}
+#[derive(PartialEq, Eq, Clone, Copy, Serialize, Debug)]
+#[repr({})]
+pub enum {name} {{
+    {}
+}}
+"#,
+            comment(&enumeration.description),
+            enumeration.data_type.to_rust_type_string(),
+            enumeration
+                .values
+                .iter()
+                .map(|v| format!("{} = {},    // {}", v.name, v.value, v.description))
+                .collect::<Vec<String>>()
+                .join("\n\t")
+        )
+    }
+
+    fn implement_enum(name: &str, enumeration: &yaml_parser::EnumType) -> String {
+        let repr = enumeration.data_type.to_rust_type_string();
+        format!(
+            r#"
+impl TryFrom<{repr}> for {name} {{
+    type Error = TypesEnumError;
+
+    fn try_from(value: {repr}) -> Result<Self, Self::Error> {{
+        match value {{
+            {}
+            _ => Err(TypesEnumError::UnknownEnumValue),
+        }}
+    }}
+}}
+
+impl {name} {{
+    pub fn from_buffer(buffer: &[u8]) -> Result<(Self, &[u8]), TypesEnumError> {{
+        let (value, buffer) = {}?;
+        Ok((Self::try_from(value)?, buffer))
+    }}
+}}
+
+impl WriteToBuffer for {name} {{
+    fn write_to_buffer(&self) -> Vec<u8> {{
+        {}
+    }}
+}}
+"#,
+            enumeration
+                .values
+                .iter()
+                .map(|v| format!("{} => Ok(Self::{}),", v.value, v.name))
+                .collect::<Vec<String>>()
+                .join("\n\t\t\t"),
+            enumeration.data_type.to_rust_parsing_call("buffer"),
+            enumeration
+                .data_type
+                .to_rust_writing_call(&format!("*self as {repr}"))
+        )
+    }
+
+    fn declare_bitfield(name: &str, bitfield: &yaml_parser::BitfieldType) -> String {
+        format!(
+            r#"
+{}
+#[derive(PartialEq, Eq, Clone, Copy, Default, Serialize, Debug)]
+pub struct {name}(pub {});
+"#,
+            comment(&bitfield.description),
+            bitfield.data_type.to_rust_type_string()
+        )
+    }
+
+    fn implement_bitfield(name: &str, bitfield: &yaml_parser::BitfieldType) -> String {
+        let repr = bitfield.data_type.to_rust_type_string();
+        format!(
+            r#"
+impl {name} {{
+    pub fn from_buffer(buffer: &[u8]) -> Result<(Self, &[u8]), TypesEnumError> {{
+        let (value, buffer) = {}?;
+        Ok((Self(value), buffer))
+    }}
+
+    {}
+}}
+
+impl WriteToBuffer for {name} {{
+    fn write_to_buffer(&self) -> Vec<u8> {{
+        {}
+    }}
+}}
+"#,
+            bitfield.data_type.to_rust_parsing_call("buffer"),
+            bitfield
+                .fields_with_offsets()
+                .into_iter()
+                .map(|(field, offset)| Self::bitfield_accessors(field, offset, &repr))
+                .collect::<Vec<String>>()
+                .join("\n\n\t"),
+            bitfield.data_type.to_rust_writing_call("self.0")
+        )
+    }
+
+    /// Getter and setter of a bitfield field, single bits are accessed as `bool`
+    pub(super) fn bitfield_accessors(
+        field: &yaml_parser::BitfieldField,
+        offset: u32,
+        repr: &str,
+    ) -> String {
+        let name = &field.name;
+        let mask = format!("{:#x}", field.mask());
+        let shifted = if offset == 0 {
+            "self.0".to_string()
+        } else {
+            format!("(self.0 >> {offset})")
+        };
+        let (field_type, getter, stored) = if field.bits == 1 {
+            (
+                "bool",
+                format!("{shifted} & 1 != 0"),
+                format!("value as {repr}"),
+            )
+        } else {
+            (
+                repr,
+                format!("{shifted} & {mask}"),
+                format!("value & {mask}"),
+            )
+        };
+        let (cleared, stored) = if offset == 0 {
+            (format!("!{mask}"), stored)
+        } else {
+            (
+                format!("!({mask} << {offset})"),
+                format!("({stored}) << {offset}"),
+            )
+        };
+
+        format!(
+            r#"// {}
+    pub fn {name}(&self) -> {field_type} {{
+        {getter}
+    }}
+
+    pub fn set_{name}(&mut self, value: {field_type}) {{
+        self.0 = (self.0 & {cleared}) | {stored};
+    }}"#,
+            field.description
+        )
+    }
+
+    fn declare_struct(
+        name: &str,
+        structure: &yaml_parser::InstFeedback,
+        codes: &crate::CodesFile,
+    ) -> String {
+        format!(
+            r#"
+{}{}
+pub struct {name} {{
+    {}
+}}
+"#,
+            comment(&structure.description),
+            Self::derive_attribute(&[(0, name.to_string(), structure.clone())], codes),
+            structure
+                .parameters
+                .iter()
+                .map(|p| format!(
+                    "{}pub {}: {},    // {}",
+                    p.to_rust_serde_attribute(),
+                    p.name,
+                    p.to_rust_type_string(),
+                    p.description
+                ))
+                .collect::<Vec<String>>()
+                .join("\n\t")
+        )
+    }
+
+    fn implement_struct(
+        name: &str,
+        structure: &yaml_parser::InstFeedback,
+        codes: &crate::CodesFile,
+    ) -> String {
+        format!(
+            r#"
+impl {name} {{
+    pub fn from_buffer(buffer: &[u8]) -> Result<(Self, &[u8]), TypesEnumError> {{
+        {}
+        Ok((Self {{ {} }}, buffer))
+    }}
+}}
+
+impl TryWriteToBuffer for {name} {{
+    fn try_write_to_buffer(&self) -> Result<Vec<u8>, TypesEnumError> {{
+        Ok([{}].concat())
+    }}
+}}
+"#,
+            structure
+                .parameters
+                .iter()
+                .map(|p| format!(
+                    "let ({}, buffer) = {}?;",
+                    p.name,
+                    p.to_rust_parsing_call("buffer")
+                ))
+                .collect::<Vec<String>>()
+                .join("\n\t\t"),
+            structure
+                .parameters
+                .iter()
+                .map(|p| p.name.clone())
+                .collect::<Vec<String>>()
+                .join(", "),
+            structure
+                .parameters
+                .iter()
+                .map(|p| {
+                    let field = if p.data_type.implements_copy(codes) {
+                        format!("self.{}", p.name)
+                    } else {
+                        format!("self.{}.clone()", p.name)
+                    };
+                    p.to_rust_writing_call(&field)
+                })
+                .collect::<Vec<String>>()
+                .join(",\n\t\t\t")
+        )
+    }
+
+    /// Floats don't implement `Eq`, only derive it when no parameter holds one
+    fn derive_attribute(
+        frames: &[(u32, String, yaml_parser::InstFeedback)],
+        codes: &crate::CodesFile,
+    ) -> String {
+        let implements_eq = frames
+            .iter()
+            .flat_map(|(_, _, frame)| &frame.parameters)
+            .all(|p| p.data_type.implements_eq(codes));
+
+        if implements_eq {
+            "\n#[derive(PartialEq, Eq, Clone, Serialize, Debug)]"
+        } else {
+            "\n#[derive(PartialEq, Clone, Serialize, Debug)]"
+        }
+        .to_string()
+    }
+
     fn map_instfeedback_list_and_type(inst: &yaml_parser::InstFeedback) -> String {
         format!(
             "{{{}}}",
             inst.parameters
                 .iter()
-                .map(|v| format!("{}: {}", v.name, v.data_type.to_rust_type_string()))
+                .map(|v| format!(
+                    "{}{}: {}",
+                    v.to_rust_serde_attribute(),
+                    v.name,
+                    v.to_rust_type_string()
+                ))
                 .collect::<Vec<String>>()
                 .join(",")
         )
     }
 }
 
-fn parse_frame_to_fields(id: u32, code: &crate::Codes, code_fb: &crate::InstFeedback) -> String {
+fn parse_frame_to_fields(
+    id: u32,
+    code: &crate::Codes,
+    code_fb: &crate::InstFeedback,
+    codes: &crate::CodesFile,
+) -> String {
     let params_parsing = &code_fb
         .parameters
         .iter()
         .map(|param| {
             format!(
-                r#"let ({}, bytes) = TypesEnum::{}_from_buffer(bytes)?;
+                r#"let ({}, bytes) = {}?;
 "#,
                 param.name,
-                param.data_type.to_typesenum_parsing_funtion_type(),
+                param.to_rust_parsing_call("bytes"),
             )
             .as_bytes()
             .to_vec()
@@ -236,13 +904,13 @@ fn parse_frame_to_fields(id: u32, code: &crate::Codes, code_fb: &crate::InstFeed
         .join("\t\t\t\t".as_bytes());
 
     format!(
-        r#"{}u8 => {{
-                let bytes = &bytes[1..];
+        r#"{}{} => {{
                 {}
                 Ok(Self::{}{{{}}})
             }}
                 "#,
         id,
+        codes.code_width.data_type().to_rust_type_string(),
         String::from_utf8(params_parsing.clone()).unwrap(),
         &code.name,
         code_fb
@@ -254,23 +922,22 @@ fn parse_frame_to_fields(id: u32, code: &crate::Codes, code_fb: &crate::InstFeed
     )
 }
 
-fn build_frame_from_fields(id: u32, code: &crate::Codes, code_fb: &crate::InstFeedback) -> String {
+fn build_frame_from_fields(
+    id: u32,
+    code: &crate::Codes,
+    code_fb: &crate::InstFeedback,
+    codes: &crate::CodesFile,
+) -> String {
     let params = code_fb
         .parameters
         .iter()
-        .map(|param| {
-            format!(
-                "TypesEnum::{}({}).write_to_buffer().as_slice()",
-                param.data_type.to_typesenum_name(),
-                param.name
-            )
-        })
+        .map(|param| format!("{}.as_slice()", param.to_rust_writing_call(&param.name)))
         .collect::<Vec<String>>()
         .join(",");
 
     [
         format!(
-            "Self::{}{{{}}} => vec![&[{}u8] as &[u8],",
+            "Self::{}{{{}}} => vec![{}.as_slice(),",
             &code.name,
             code_fb
                 .parameters
@@ -278,10 +945,50 @@ fn build_frame_from_fields(id: u32, code: &crate::Codes, code_fb: &crate::InstFe
                 .map(|p| p.name.clone())
                 .collect::<Vec<String>>()
                 .join(","),
-            id
+            codes.code_width.data_type().to_rust_writing_call(&format!(
+                "{id}{}",
+                codes.code_width.data_type().to_rust_type_string()
+            ))
         ),
         params,
         "].concat()".to_string(),
     ]
     .concat()
 }
+
+/// Pattern or expression of the `name` structure or variant with the given `fields`
+fn struct_pattern(name: &str, fields: &str) -> String {
+    if fields.is_empty() {
+        format!("{name} {{}}")
+    } else {
+        format!("{name} {{ {fields} }}")
+    }
+}
+
+/// Turns a `CamelCase` frame name into a `snake_case` method name
+fn snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<char>>();
+    let mut snake = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 && chars[i - 1] != '_' {
+            let starts_word = chars
+                .get(i + 1)
+                .is_some_and(|next| next.is_ascii_lowercase());
+            if !chars[i - 1].is_ascii_uppercase() || starts_word {
+                snake.push('_');
+            }
+        }
+        snake.push(c.to_ascii_lowercase());
+    }
+    snake
+}
+
+/// Turns a possibly multi-line description into line comments
+pub(super) fn comment(description: &str) -> String {
+    description
+        .trim_end()
+        .lines()
+        .map(|line| format!("// {line}"))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
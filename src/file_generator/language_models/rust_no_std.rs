@@ -0,0 +1,491 @@
+use super::rust::comment;
+use crate::file_generator::{FrameType, LanguageModel, language_models::RustLanguageModel};
+use crate::yaml_parser::{self, ParameterType};
+
+impl yaml_parser::ParameterType {
+    /// Whether the value borrows the received frame, needing the `'a` lifetime
+    pub(super) fn borrows(&self, codes: &yaml_parser::CodesFile) -> bool {
+        match self {
+            ParameterType::String | ParameterType::Array { .. } => true,
+            ParameterType::FixedArray { item, .. } => item.borrows(codes),
+            ParameterType::Custom(name) => codes
+                .types
+                .get(name)
+                .is_some_and(|structure| parameters_borrow(&structure.parameters, codes)),
+            _ => false,
+        }
+    }
+
+    fn to_rust_no_std_type_string(&self, codes: &yaml_parser::CodesFile) -> String {
+        match self {
+            ParameterType::String => "&'a str".to_string(),
+            ParameterType::Array { item, length } => format!(
+                "Array<'a, {}, {}>",
+                item.to_rust_no_std_type_string(codes),
+                length.size()
+            ),
+            ParameterType::FixedArray { item, count } => {
+                format!("[{}; {count}]", item.to_rust_no_std_type_string(codes))
+            }
+            ParameterType::Custom(name) if self.borrows(codes) => format!("{name}<'a>"),
+            _ => self.to_rust_type_string(),
+        }
+    }
+}
+
+impl yaml_parser::InstFeedbackParameter {
+    fn to_rust_no_std_type_string(&self, codes: &yaml_parser::CodesFile) -> String {
+        if self.optional {
+            format!(
+                "Option<{}>",
+                self.data_type.to_rust_no_std_type_string(codes)
+            )
+        } else {
+            self.data_type.to_rust_no_std_type_string(codes)
+        }
+    }
+}
+
+/// Whether any of the parameters borrows the received frame
+pub(super) fn parameters_borrow(
+    parameters: &[yaml_parser::InstFeedbackParameter],
+    codes: &yaml_parser::CodesFile,
+) -> bool {
+    parameters.iter().any(|p| p.data_type.borrows(codes))
+}
+
+/// Generic parameters of a type holding the `parameters`
+pub(super) fn generics(
+    parameters: &[yaml_parser::InstFeedbackParameter],
+    codes: &yaml_parser::CodesFile,
+) -> &'static str {
+    if parameters_borrow(parameters, codes) {
+        "<'a>"
+    } else {
+        ""
+    }
+}
+
+/// Derived traits of a type holding the `parameters`, floats not implementing `Eq`. The
+/// borrowing types of the no_std output are also `Copy`.
+pub(super) fn derive_attribute(
+    parameters: &[yaml_parser::InstFeedbackParameter],
+    codes: &yaml_parser::CodesFile,
+    copy: bool,
+) -> String {
+    format!(
+        "#[derive(PartialEq, {}Clone, {}Debug)]",
+        if parameters.iter().all(|p| p.data_type.implements_eq(codes)) {
+            "Eq, "
+        } else {
+            ""
+        },
+        if copy { "Copy, " } else { "" }
+    )
+}
+
+/// Fields of a struct expression, decoded in order from the `reader`, one per line
+pub(super) fn decode_fields(
+    parameters: &[yaml_parser::InstFeedbackParameter],
+    reader: &str,
+    indent: &str,
+) -> String {
+    parameters
+        .iter()
+        .map(|p| format!("\n{indent}{}: Decode::decode({reader})?,", p.name))
+        .collect::<String>()
+}
+
+/// Allocation-free Rust output, for `#![no_std]` crates
+pub struct RustNoStdLanguageModel {}
+
+impl LanguageModel for RustNoStdLanguageModel {
+    fn custom_includes(&self) -> String {
+        String::new()
+    }
+
+    fn custom_footer(&self, _codes: &crate::CodesFile) -> String {
+        String::new()
+    }
+
+    fn declare_settings(&self, codes: &crate::CodesFile) -> String {
+        RustLanguageModel {}.declare_settings(codes)
+    }
+
+    fn declare_types(&self, codes: &crate::CodesFile) -> String {
+        codes
+            .enums
+            .iter()
+            .map(|(name, enumeration)| Self::declare_enum(name, enumeration))
+            .chain(
+                codes
+                    .bitfields
+                    .iter()
+                    .map(|(name, bitfield)| Self::declare_bitfield(name, bitfield)),
+            )
+            .chain(
+                codes
+                    .types
+                    .iter()
+                    .map(|(name, structure)| Self::declare_struct(name, structure, codes)),
+            )
+            .collect::<String>()
+    }
+
+    fn implement_types(&self, codes: &crate::CodesFile) -> String {
+        codes
+            .enums
+            .iter()
+            .map(|(name, enumeration)| Self::implement_enum(name, enumeration))
+            .chain(
+                codes
+                    .bitfields
+                    .iter()
+                    .map(|(name, bitfield)| Self::implement_bitfield(name, bitfield)),
+            )
+            .chain(
+                codes
+                    .types
+                    .iter()
+                    .map(|(name, structure)| Self::implement_struct(name, structure, codes)),
+            )
+            .collect::<String>()
+    }
+
+    fn declare_feedbacks(&self, codes: &crate::CodesFile) -> String {
+        Self::declare_frames(FrameType::Feedback, &codes.get_feedbacks(), codes)
+    }
+
+    fn declare_instructions(&self, codes: &crate::CodesFile) -> String {
+        Self::declare_frames(FrameType::Instruction, &codes.get_instructions(), codes)
+    }
+
+    fn implement_feedbacks(&self, codes: &crate::CodesFile) -> String {
+        Self::implement_frames(FrameType::Feedback, &codes.get_feedbacks(), codes)
+    }
+
+    fn implement_instructions(&self, codes: &crate::CodesFile) -> String {
+        Self::implement_frames(FrameType::Instruction, &codes.get_instructions(), codes)
+    }
+}
+
+impl RustNoStdLanguageModel {
+    fn declare_enum(name: &str, enumeration: &yaml_parser::EnumType) -> String {
+        format!(
+            r#"
+{}
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[repr({})]
+pub enum {name} {{
+    {}
+}}
+"#,
+            comment(&enumeration.description),
+            enumeration.data_type.to_rust_type_string(),
+            enumeration
+                .values
+                .iter()
+                .map(|v| format!("{} = {},    // {}", v.name, v.value, v.description))
+                .collect::<Vec<String>>()
+                .join("\n\t")
+        )
+    }
+
+    fn implement_enum(name: &str, enumeration: &yaml_parser::EnumType) -> String {
+        let repr = enumeration.data_type.to_rust_type_string();
+        format!(
+            r#"
+impl TryFrom<{repr}> for {name} {{
+    type Error = TypesEnumError;
+
+    fn try_from(value: {repr}) -> Result<Self, Self::Error> {{
+        match value {{
+            {}
+            _ => Err(TypesEnumError::UnknownEnumValue),
+        }}
+    }}
+}}
+
+impl<'a> Decode<'a> for {name} {{
+    fn decode(reader: &mut Reader<'a>) -> Result<Self, TypesEnumError> {{
+        Self::try_from({repr}::decode(reader)?)
+    }}
+}}
+
+impl Encode for {name} {{
+    fn encode(&self, writer: &mut Writer) -> Result<(), TypesEnumError> {{
+        (*self as {repr}).encode(writer)
+    }}
+}}
+"#,
+            enumeration
+                .values
+                .iter()
+                .map(|v| format!("{} => Ok(Self::{}),", v.value, v.name))
+                .collect::<Vec<String>>()
+                .join("\n\t\t\t")
+        )
+    }
+
+    fn declare_bitfield(name: &str, bitfield: &yaml_parser::BitfieldType) -> String {
+        format!(
+            r#"
+{}
+#[derive(PartialEq, Eq, Clone, Copy, Default, Debug)]
+pub struct {name}(pub {});
+"#,
+            comment(&bitfield.description),
+            bitfield.data_type.to_rust_type_string()
+        )
+    }
+
+    fn implement_bitfield(name: &str, bitfield: &yaml_parser::BitfieldType) -> String {
+        let repr = bitfield.data_type.to_rust_type_string();
+        format!(
+            r#"
+impl {name} {{
+    {}
+}}
+
+impl<'a> Decode<'a> for {name} {{
+    fn decode(reader: &mut Reader<'a>) -> Result<Self, TypesEnumError> {{
+        Ok(Self({repr}::decode(reader)?))
+    }}
+}}
+
+impl Encode for {name} {{
+    fn encode(&self, writer: &mut Writer) -> Result<(), TypesEnumError> {{
+        self.0.encode(writer)
+    }}
+}}
+"#,
+            bitfield
+                .fields_with_offsets()
+                .into_iter()
+                .map(|(field, offset)| RustLanguageModel::bitfield_accessors(field, offset, &repr))
+                .collect::<Vec<String>>()
+                .join("\n\n\t")
+        )
+    }
+
+    fn declare_struct(
+        name: &str,
+        structure: &yaml_parser::InstFeedback,
+        codes: &crate::CodesFile,
+    ) -> String {
+        format!(
+            r#"
+{}
+{}
+pub struct {name}{} {{
+    {}
+}}
+"#,
+            comment(&structure.description),
+            derive_attribute(&structure.parameters, codes, true),
+            generics(&structure.parameters, codes),
+            structure
+                .parameters
+                .iter()
+                .map(|p| format!(
+                    "pub {}: {},    // {}",
+                    p.name,
+                    p.to_rust_no_std_type_string(codes),
+                    p.description
+                ))
+                .collect::<Vec<String>>()
+                .join("\n\t")
+        )
+    }
+
+    fn implement_struct(
+        name: &str,
+        structure: &yaml_parser::InstFeedback,
+        codes: &crate::CodesFile,
+    ) -> String {
+        let generics = generics(&structure.parameters, codes);
+        format!(
+            r#"
+impl<'a> Decode<'a> for {name}{generics} {{
+    fn decode(reader: &mut Reader<'a>) -> Result<Self, TypesEnumError> {{
+        Ok(Self {{{}
+        }})
+    }}
+}}
+
+impl{generics} Encode for {name}{generics} {{
+    fn encode(&self, writer: &mut Writer) -> Result<(), TypesEnumError> {{
+        {}
+        Ok(())
+    }}
+}}
+"#,
+            decode_fields(&structure.parameters, "reader", "\t\t\t"),
+            structure
+                .parameters
+                .iter()
+                .map(|p| format!("self.{}.encode(writer)?;", p.name))
+                .collect::<Vec<String>>()
+                .join("\n\t\t")
+        )
+    }
+
+    /// Generic parameters of the frames enumeration, borrowing when any frame borrows
+    fn frames_generics(
+        frames: &[(u32, String, yaml_parser::InstFeedback)],
+        codes: &crate::CodesFile,
+    ) -> &'static str {
+        if frames
+            .iter()
+            .any(|(_, _, frame)| parameters_borrow(&frame.parameters, codes))
+        {
+            "<'a>"
+        } else {
+            ""
+        }
+    }
+
+    fn declare_frames(
+        frame_type: FrameType,
+        frames: &[(u32, String, yaml_parser::InstFeedback)],
+        codes: &crate::CodesFile,
+    ) -> String {
+        let parameters = frames
+            .iter()
+            .flat_map(|(_, _, frame)| frame.parameters.clone())
+            .collect::<Vec<yaml_parser::InstFeedbackParameter>>();
+        format!(
+            r#"
+{}
+pub enum {}{} {{
+    {}
+}}
+"#,
+            derive_attribute(&parameters, codes, true),
+            frame_type.struct_name(),
+            Self::frames_generics(frames, codes),
+            frames
+                .iter()
+                .map(|(_, name, frame)| format!(
+                    "{name} {{{}}},    // {}",
+                    frame
+                        .parameters
+                        .iter()
+                        .map(|p| format!("{}: {}", p.name, p.to_rust_no_std_type_string(codes)))
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                    frame.description
+                ))
+                .collect::<Vec<String>>()
+                .join("\n\t")
+        )
+    }
+
+    fn implement_frames(
+        frame_type: FrameType,
+        frames: &[(u32, String, yaml_parser::InstFeedback)],
+        codes: &crate::CodesFile,
+    ) -> String {
+        let type_name = frame_type.struct_name();
+        let generics = Self::frames_generics(frames, codes);
+        let lifetime = if generics.is_empty() { "" } else { "'a " };
+        let code_type = codes.code_width.data_type().to_rust_type_string();
+
+        format!(
+            r#"
+impl{generics} {type_name}{generics} {{
+    /// Decodes a received frame, borrowing its strings and arrays
+    pub fn from_bytes(bytes: &{lifetime}[u8]) -> Result<Self, TypesEnumError> {{
+        let mut reader = Reader::new(verify_checksum(bytes)?);
+        let frame = match {code_type}::decode(&mut reader)? {{
+            {}
+            _ => return Err(TypesEnumError::UnknownCode),
+        }};
+        Ok(frame)
+    }}
+
+    /// Encodes the frame into `buffer`, and returns the number of bytes written
+    pub fn encode(&self, buffer: &mut [u8]) -> Result<usize, TypesEnumError> {{
+        let mut writer = Writer::new(buffer);
+        match self {{
+            {}
+        }}
+        writer.append_checksum()?;
+        Ok(writer.len())
+    }}
+}}
+{}"#,
+            frames
+                .iter()
+                .map(|(id, name, frame)| format!(
+                    "{id}{code_type} => Self::{name} {{{}\n\t\t\t}},",
+                    decode_fields(&frame.parameters, "&mut reader", "\t\t\t\t")
+                ))
+                .collect::<Vec<String>>()
+                .join("\n\t\t\t"),
+            frames
+                .iter()
+                .map(|(id, name, frame)| format!(
+                    "Self::{name} {{ {} }} => {{\n\t\t\t\t{id}{code_type}.encode(&mut writer)?;{}\n\t\t\t}}",
+                    frame
+                        .parameters
+                        .iter()
+                        .map(|p| p.name.clone())
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                    frame
+                        .parameters
+                        .iter()
+                        .map(|p| format!("\n\t\t\t\t{}.encode(&mut writer)?;", p.name))
+                        .collect::<String>()
+                ))
+                .collect::<Vec<String>>()
+                .join("\n\t\t\t"),
+            Self::implement_stream(frame_type, generics, codes)
+        )
+    }
+
+    /// Stream encoding and decoding of the frames, when a framing is configured
+    fn implement_stream(frame_type: FrameType, generics: &str, codes: &crate::CodesFile) -> String {
+        let Some(framing) = codes.framing else {
+            return String::new();
+        };
+        let type_name = frame_type.struct_name();
+        let lowercase_name = type_name.to_lowercase();
+        let frame_lifetime = if generics.is_empty() { "" } else { "<'_>" };
+
+        let default_decoder = if let FrameType::Instruction = frame_type {
+            format!(
+                r#"
+impl<const N: usize> Default for StreamDecoder<N> {{
+    fn default() -> Self {{
+        Self::new(Framing::{framing:?})
+    }}
+}}
+"#
+            )
+        } else {
+            String::new()
+        };
+
+        format!(
+            r#"{default_decoder}
+impl{generics} {type_name}{generics} {{
+    /// Encodes the frame into `buffer`, then delimits it into `stream` for a byte stream.
+    /// Returns the number of bytes written in `stream`.
+    pub fn encode_stream(&self, buffer: &mut [u8], stream: &mut [u8]) -> Result<usize, TypesEnumError> {{
+        let len = self.encode(buffer)?;
+        Framing::{framing:?}.encode(&buffer[..len], stream)
+    }}
+}}
+
+impl<const N: usize> StreamDecoder<N> {{
+    /// Feeds a received byte, and returns the {lowercase_name} frame it completes
+    pub fn decode_{lowercase_name}(&mut self, byte: u8) -> Option<Result<{type_name}{frame_lifetime}, TypesEnumError>> {{
+        self.push(byte).map(|frame| frame.and_then({type_name}::from_bytes))
+    }}
+}}
+"#
+        )
+    }
+}
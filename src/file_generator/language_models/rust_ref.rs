@@ -0,0 +1,321 @@
+use super::rust::comment;
+use super::rust_no_std::{decode_fields, derive_attribute, generics, parameters_borrow};
+use crate::file_generator::{FrameType, language_models::RustLanguageModel};
+use crate::yaml_parser::{self, ParameterType};
+
+impl yaml_parser::ParameterType {
+    /// Type of the value borrowed from a received frame
+    fn to_rust_ref_type_string(&self, codes: &yaml_parser::CodesFile) -> String {
+        match self {
+            ParameterType::String => "&'a str".to_string(),
+            ParameterType::Array { item, length } => format!(
+                "Array<'a, {}, {}>",
+                item.to_rust_ref_type_string(codes),
+                length.size()
+            ),
+            ParameterType::FixedArray { item, count } => {
+                format!("[{}; {count}]", item.to_rust_ref_type_string(codes))
+            }
+            ParameterType::Custom(name) if self.borrows(codes) => format!("{name}Ref<'a>"),
+            _ => self.to_rust_type_string(),
+        }
+    }
+}
+
+impl yaml_parser::InstFeedbackParameter {
+    fn to_rust_ref_type_string(&self, codes: &yaml_parser::CodesFile) -> String {
+        if self.optional {
+            format!("Option<{}>", self.data_type.to_rust_ref_type_string(codes))
+        } else {
+            self.data_type.to_rust_ref_type_string(codes)
+        }
+    }
+
+    /// Statement encoding the owned `value` of this parameter into `writer`, the vectors
+    /// being viewed as arrays to know the size of their count
+    fn to_rust_encoding_call(&self, value: &str, writer: &str) -> String {
+        match &self.data_type {
+            ParameterType::Array { length, .. } if self.optional => format!(
+                "{value}.as_deref().map(Array::<_, {}>::from).encode({writer})?;",
+                length.size()
+            ),
+            ParameterType::Array { length, .. } => format!(
+                "Array::<_, {}>::from({value}.as_slice()).encode({writer})?;",
+                length.size()
+            ),
+            _ => format!("{value}.encode({writer})?;"),
+        }
+    }
+}
+
+/// Parameters of all the frames, borrowing when any of them borrows
+fn frames_parameters(
+    frames: &[(u32, String, yaml_parser::InstFeedback)],
+) -> Vec<yaml_parser::InstFeedbackParameter> {
+    frames
+        .iter()
+        .flat_map(|(_, _, frame)| frame.parameters.clone())
+        .collect()
+}
+
+/// Zero-copy API: the `Ref` types borrow the strings and arrays of a received frame, and
+/// every type is encoded into an `io::Write` without intermediate buffers
+impl RustLanguageModel {
+    pub(super) fn declare_ref_types(codes: &crate::CodesFile) -> String {
+        codes
+            .types
+            .iter()
+            .filter(|(_, structure)| parameters_borrow(&structure.parameters, codes))
+            .map(|(name, structure)| {
+                format!(
+                    r#"
+{}
+{}
+pub struct {name}Ref<'a> {{
+    {}
+}}
+"#,
+                    comment(&structure.description),
+                    derive_attribute(&structure.parameters, codes, false),
+                    structure
+                        .parameters
+                        .iter()
+                        .map(|p| format!(
+                            "pub {}: {},    // {}",
+                            p.name,
+                            p.to_rust_ref_type_string(codes),
+                            p.description
+                        ))
+                        .collect::<Vec<String>>()
+                        .join("\n\t")
+                )
+            })
+            .collect::<String>()
+    }
+
+    pub(super) fn implement_ref_types(codes: &crate::CodesFile) -> String {
+        let enums = codes.enums.iter().map(|(name, enumeration)| {
+            let repr = enumeration.data_type.to_rust_type_string();
+            format!(
+                r#"
+impl<'a> Decode<'a> for {name} {{
+    fn decode(reader: &mut Reader<'a>) -> Result<Self, TypesEnumError> {{
+        Self::try_from({repr}::decode(reader)?)
+    }}
+}}
+
+impl Encode for {name} {{
+    fn encode<W: io::Write>(&self, writer: &mut Writer<W>) -> io::Result<()> {{
+        (*self as {repr}).encode(writer)
+    }}
+}}
+"#
+            )
+        });
+        let bitfields = codes.bitfields.iter().map(|(name, bitfield)| {
+            format!(
+                r#"
+impl<'a> Decode<'a> for {name} {{
+    fn decode(reader: &mut Reader<'a>) -> Result<Self, TypesEnumError> {{
+        Ok(Self({}::decode(reader)?))
+    }}
+}}
+
+impl Encode for {name} {{
+    fn encode<W: io::Write>(&self, writer: &mut Writer<W>) -> io::Result<()> {{
+        self.0.encode(writer)
+    }}
+}}
+"#,
+                bitfield.data_type.to_rust_type_string()
+            )
+        });
+        let structs = codes
+            .types
+            .iter()
+            .map(|(name, structure)| Self::implement_ref_struct(name, structure, codes));
+
+        enums.chain(bitfields).chain(structs).collect::<String>()
+    }
+
+    /// Encoding of a structure, and decoding of the structure borrowing the received frame,
+    /// which is the structure itself when it holds neither strings nor arrays
+    fn implement_ref_struct(
+        name: &str,
+        structure: &yaml_parser::InstFeedback,
+        codes: &crate::CodesFile,
+    ) -> String {
+        let borrows = parameters_borrow(&structure.parameters, codes);
+        let ref_name = if borrows {
+            format!("{name}Ref<'a>")
+        } else {
+            name.to_string()
+        };
+
+        let owned_encoding = format!(
+            r#"
+impl Encode for {name} {{
+    fn encode<W: io::Write>(&self, writer: &mut Writer<W>) -> io::Result<()> {{
+        {}
+        Ok(())
+    }}
+}}
+"#,
+            structure
+                .parameters
+                .iter()
+                .map(|p| p.to_rust_encoding_call(&format!("self.{}", p.name), "writer"))
+                .collect::<Vec<String>>()
+                .join("\n\t\t")
+        );
+        let ref_encoding = if borrows {
+            format!(
+                r#"
+impl<'a> Encode for {ref_name} {{
+    fn encode<W: io::Write>(&self, writer: &mut Writer<W>) -> io::Result<()> {{
+        {}
+        Ok(())
+    }}
+}}
+"#,
+                structure
+                    .parameters
+                    .iter()
+                    .map(|p| format!("self.{}.encode(writer)?;", p.name))
+                    .collect::<Vec<String>>()
+                    .join("\n\t\t")
+            )
+        } else {
+            String::new()
+        };
+
+        format!(
+            r#"{owned_encoding}{ref_encoding}
+impl<'a> Decode<'a> for {ref_name} {{
+    fn decode(reader: &mut Reader<'a>) -> Result<Self, TypesEnumError> {{
+        Ok(Self {{{}
+        }})
+    }}
+}}
+"#,
+            decode_fields(&structure.parameters, "reader", "\t\t\t")
+        )
+    }
+
+    pub(super) fn declare_ref_frames(
+        frame_type: FrameType,
+        frames: &[(u32, String, yaml_parser::InstFeedback)],
+        codes: &crate::CodesFile,
+    ) -> String {
+        let parameters = frames_parameters(frames);
+        format!(
+            r#"
+/// {} borrowing the strings and arrays of the received frame
+{}
+pub enum {}Ref{} {{
+    {}
+}}
+"#,
+            frame_type.struct_name(),
+            derive_attribute(&parameters, codes, false),
+            frame_type.struct_name(),
+            generics(&parameters, codes),
+            frames
+                .iter()
+                .map(|(_, name, frame)| format!(
+                    "{name} {{{}}},    // {}",
+                    frame
+                        .parameters
+                        .iter()
+                        .map(|p| format!("{}: {}", p.name, p.to_rust_ref_type_string(codes)))
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                    frame.description
+                ))
+                .collect::<Vec<String>>()
+                .join("\n\t")
+        )
+    }
+
+    pub(super) fn implement_ref_frames(
+        frame_type: FrameType,
+        frames: &[(u32, String, yaml_parser::InstFeedback)],
+        codes: &crate::CodesFile,
+    ) -> String {
+        let type_name = frame_type.struct_name();
+        let generics = generics(&frames_parameters(frames), codes);
+        let lifetime = if generics.is_empty() { "" } else { "'a " };
+        let code_type = codes.code_width.data_type().to_rust_type_string();
+
+        let write_to = |encode_fields: &dyn Fn(&yaml_parser::InstFeedbackParameter) -> String| {
+            format!(
+                r#"
+    /// Encodes the frame into `output`, and returns the number of bytes written. Arrays
+    /// holding more items than their count can tell fail with an `InvalidInput` error.
+    pub fn write_to(&self, output: impl io::Write) -> io::Result<usize> {{
+        let mut writer = Writer::new(output);
+        match self {{
+            {}
+        }}
+        writer.finish()
+    }}
+
+    /// Encodes the frame into `buffer`, and returns the number of bytes written
+    pub fn encode(&self, buffer: &mut [u8]) -> Result<usize, TypesEnumError> {{
+        self.write_to(buffer).map_err(|error| match error.kind() {{
+            io::ErrorKind::InvalidInput => TypesEnumError::ArrayTooLong,
+            _ => TypesEnumError::BufferTooSmall,
+        }})
+    }}"#,
+                frames
+                    .iter()
+                    .map(|(id, name, frame)| format!(
+                        "Self::{name} {{ {} }} => {{\n\t\t\t\t{id}{code_type}.encode(&mut writer)?;{}\n\t\t\t}}",
+                        frame
+                            .parameters
+                            .iter()
+                            .map(|p| p.name.clone())
+                            .collect::<Vec<String>>()
+                            .join(", "),
+                        frame
+                            .parameters
+                            .iter()
+                            .map(|p| format!("\n\t\t\t\t{}", encode_fields(p)))
+                            .collect::<String>()
+                    ))
+                    .collect::<Vec<String>>()
+                    .join("\n\t\t\t")
+            )
+        };
+
+        format!(
+            r#"
+impl {type_name} {{{}
+}}
+
+impl{generics} {type_name}Ref{generics} {{
+    /// Decodes a received frame, borrowing its strings and arrays
+    pub fn from_bytes(bytes: &{lifetime}[u8]) -> Result<Self, TypesEnumError> {{
+        let mut reader = Reader::new(verify_checksum(bytes)?);
+        let frame = match {code_type}::decode(&mut reader)? {{
+            {}
+            _ => return Err(TypesEnumError::UnknownCode),
+        }};
+        Ok(frame)
+    }}
+{}
+}}
+"#,
+            write_to(&|p| p.to_rust_encoding_call(&p.name, "&mut writer")),
+            frames
+                .iter()
+                .map(|(id, name, frame)| format!(
+                    "{id}{code_type} => Self::{name} {{{}\n\t\t\t}},",
+                    decode_fields(&frame.parameters, "&mut reader", "\t\t\t\t")
+                ))
+                .collect::<Vec<String>>()
+                .join("\n\t\t\t"),
+            write_to(&|p| format!("{}.encode(&mut writer)?;", p.name))
+        )
+    }
+}
@@ -0,0 +1,545 @@
+use crate::file_generator::{FrameType, LanguageModel};
+use crate::yaml_parser::{self, ParameterType};
+
+impl yaml_parser::ParameterType {
+    fn to_typescript_type_string(&self) -> String {
+        match self {
+            ParameterType::Array { item, .. } | ParameterType::FixedArray { item, .. } => {
+                format!("{}[]", item.to_typescript_type_string())
+            }
+            ParameterType::Custom(name) => name.clone(),
+            ParameterType::String => "string".to_string(),
+            ParameterType::Bool => "boolean".to_string(),
+            ParameterType::Uint64 | ParameterType::Int64 => "bigint".to_string(),
+            _ => "number".to_string(),
+        }
+    }
+
+    /// `FrameWriter` and `FrameReader` method handling the scalar types
+    fn to_typescript_method(&self) -> &str {
+        match self {
+            ParameterType::Uint8 => "u8",
+            ParameterType::Int8 => "i8",
+            ParameterType::Uint16 => "u16",
+            ParameterType::Int16 => "i16",
+            ParameterType::Uint32 => "u32",
+            ParameterType::Int32 => "i32",
+            ParameterType::Uint64 => "u64",
+            ParameterType::Int64 => "i64",
+            ParameterType::Float32 => "f32",
+            ParameterType::Float64 => "f64",
+            ParameterType::Bool => "bool",
+            ParameterType::String => "string",
+            _ => unreachable!("{self} isn't a scalar type"),
+        }
+    }
+
+    /// Expression decoding a value of this type from the `reader`
+    fn to_typescript_parsing_call(&self) -> String {
+        match self {
+            ParameterType::Array { item, length } => format!(
+                "reader.array({}, () => {})",
+                length.size(),
+                item.to_typescript_parsing_call()
+            ),
+            ParameterType::FixedArray { item, count } => format!(
+                "reader.fixedArray({count}, () => {})",
+                item.to_typescript_parsing_call()
+            ),
+            ParameterType::Custom(name) => format!("read{name}(reader)"),
+            _ => format!("reader.{}()", self.to_typescript_method()),
+        }
+    }
+
+    /// Statement encoding the `value` of this type with the `writer`
+    fn to_typescript_writing_call(&self, value: &str) -> String {
+        match self {
+            ParameterType::Array { item, length } => format!(
+                "writer.array({}, {value}, (item) => {})",
+                length.size(),
+                item.to_typescript_writing_call("item")
+            ),
+            ParameterType::FixedArray { item, count } => format!(
+                "writer.fixedArray({count}, {value}, (item) => {})",
+                item.to_typescript_writing_call("item")
+            ),
+            ParameterType::Custom(name) => format!("write{name}(writer, {value})"),
+            _ => format!("writer.{}({value})", self.to_typescript_method()),
+        }
+    }
+}
+
+impl yaml_parser::InstFeedbackParameter {
+    fn to_typescript_field(&self) -> String {
+        format!(
+            "{}{}: {}",
+            self.name,
+            if self.optional { "?" } else { "" },
+            self.data_type.to_typescript_type_string()
+        )
+    }
+
+    /// Expression decoding this parameter, preceded by its presence flag when optional
+    fn to_typescript_parsing_call(&self) -> String {
+        if self.optional {
+            format!(
+                "reader.optional(() => {})",
+                self.data_type.to_typescript_parsing_call()
+            )
+        } else {
+            self.data_type.to_typescript_parsing_call()
+        }
+    }
+
+    /// Statement encoding the `value` of this parameter with the `writer`
+    fn to_typescript_writing_call(&self, value: &str) -> String {
+        if self.optional {
+            format!(
+                "writer.optional({value}, (value) => {})",
+                self.data_type.to_typescript_writing_call("value")
+            )
+        } else {
+            self.data_type.to_typescript_writing_call(value)
+        }
+    }
+}
+
+pub struct TypeScriptLanguageModel {}
+
+impl LanguageModel for TypeScriptLanguageModel {
+    fn custom_includes(&self) -> String {
+        String::new()
+    }
+
+    fn custom_footer(&self, _codes: &crate::CodesFile) -> String {
+        String::new()
+    }
+
+    fn declare_settings(&self, codes: &crate::CodesFile) -> String {
+        format!(
+            "const LITTLE_ENDIAN: boolean = {};\nconst CHECKSUM: Checksum | null = {};\n",
+            codes.endianness == yaml_parser::Endianness::Little,
+            match codes.checksum {
+                Some(checksum) => format!("\"{}\"", format!("{checksum:?}").to_lowercase()),
+                None => "null".to_string(),
+            }
+        )
+    }
+
+    fn declare_types(&self, codes: &crate::CodesFile) -> String {
+        codes
+            .enums
+            .iter()
+            .map(|(name, enumeration)| Self::declare_enum(name, enumeration))
+            .chain(
+                codes
+                    .bitfields
+                    .iter()
+                    .map(|(name, bitfield)| Self::declare_bitfield(name, bitfield)),
+            )
+            .chain(
+                codes
+                    .types
+                    .iter()
+                    .map(|(name, structure)| Self::declare_struct(name, structure)),
+            )
+            .collect::<String>()
+    }
+
+    fn implement_types(&self, codes: &crate::CodesFile) -> String {
+        codes
+            .enums
+            .iter()
+            .map(|(name, enumeration)| Self::implement_enum(name, enumeration))
+            .chain(
+                codes
+                    .bitfields
+                    .iter()
+                    .map(|(name, bitfield)| Self::implement_bitfield(name, bitfield)),
+            )
+            .chain(
+                codes
+                    .types
+                    .iter()
+                    .map(|(name, structure)| Self::implement_struct(name, structure)),
+            )
+            .collect::<String>()
+    }
+
+    fn declare_feedbacks(&self, codes: &crate::CodesFile) -> String {
+        Self::declare_frames(FrameType::Feedback, &codes.get_feedbacks())
+    }
+
+    fn declare_instructions(&self, codes: &crate::CodesFile) -> String {
+        Self::declare_frames(FrameType::Instruction, &codes.get_instructions())
+    }
+
+    fn implement_feedbacks(&self, codes: &crate::CodesFile) -> String {
+        Self::implement_frames(FrameType::Feedback, &codes.get_feedbacks(), codes)
+    }
+
+    fn implement_instructions(&self, codes: &crate::CodesFile) -> String {
+        Self::implement_frames(FrameType::Instruction, &codes.get_instructions(), codes)
+    }
+}
+
+impl TypeScriptLanguageModel {
+    fn declare_enum(name: &str, enumeration: &yaml_parser::EnumType) -> String {
+        format!(
+            r#"
+{}
+export enum {name} {{
+  {}
+}}
+"#,
+            doc_comment(&enumeration.description, 0),
+            enumeration
+                .values
+                .iter()
+                .map(|v| format!("{} = {}, // {}", v.name, v.value, v.description))
+                .collect::<Vec<String>>()
+                .join("\n  ")
+        )
+    }
+
+    fn implement_enum(name: &str, enumeration: &yaml_parser::EnumType) -> String {
+        let method = enumeration.data_type.to_typescript_method();
+        format!(
+            r#"
+function write{name}(writer: FrameWriter, value: {name}): void {{
+  writer.{method}(value);
+}}
+
+function read{name}(reader: FrameReader): {name} {{
+  const value = reader.{method}();
+  if ({name}[value] === undefined) {{
+    throw new FrameError("UnknownEnumValue");
+  }}
+  return value as {name};
+}}
+"#
+        )
+    }
+
+    fn declare_bitfield(name: &str, bitfield: &yaml_parser::BitfieldType) -> String {
+        let repr = bitfield.data_type.to_typescript_type_string();
+        let zero = if repr == "bigint" { "0n" } else { "0" };
+        format!(
+            r#"
+{}
+export class {name} {{
+  value: {repr};
+
+  constructor(value: {repr} = {zero}) {{
+    this.value = value;
+  }}
+
+  {}
+}}
+"#,
+            doc_comment(&bitfield.description, 0),
+            bitfield
+                .fields_with_offsets()
+                .into_iter()
+                .map(|(field, offset)| Self::bitfield_accessors(field, offset, repr == "bigint"))
+                .collect::<Vec<String>>()
+                .join("\n\n  ")
+        )
+    }
+
+    /// Getter and setter of a bitfield field, single bits are accessed as `boolean`
+    fn bitfield_accessors(field: &yaml_parser::BitfieldField, offset: u32, bigint: bool) -> String {
+        let name = &field.name;
+        // Numbers go through signed 32 bits integers in bitwise operations, `>>> 0` restores them
+        let (suffix, shift, unsigned, repr) = if bigint {
+            ("n", ">>", "", "bigint")
+        } else {
+            ("", ">>>", " >>> 0", "number")
+        };
+        let mask = format!("{:#x}{suffix}", field.mask());
+        let (shifted, cleared, shifted_offset) = if offset == 0 {
+            ("this.value".to_string(), format!("~{mask}"), String::new())
+        } else {
+            (
+                format!("(this.value {shift} {offset}{suffix})"),
+                format!("~({mask} << {offset}{suffix})"),
+                format!(" << {offset}{suffix}"),
+            )
+        };
+        let (field_type, getter, stored) = if field.bits == 1 {
+            (
+                "boolean",
+                format!("({shifted} & 1{suffix}) !== 0{suffix}"),
+                format!("(field ? 1{suffix} : 0{suffix}){shifted_offset}"),
+            )
+        } else {
+            (
+                repr,
+                if field.bits == 32 && !bigint {
+                    format!("({shifted} & {mask}){unsigned}")
+                } else {
+                    format!("{shifted} & {mask}")
+                },
+                format!("(field & {mask}){shifted_offset}"),
+            )
+        };
+
+        let assigned = if bigint {
+            format!("(this.value & {cleared}) | {stored}")
+        } else {
+            format!("((this.value & {cleared}) | {stored}){unsigned}")
+        };
+
+        format!(
+            r#"{}
+  get {name}(): {field_type} {{
+    return {getter};
+  }}
+
+  set {name}(field: {field_type}) {{
+    this.value = {assigned};
+  }}"#,
+            doc_comment(&field.description, 1)
+        )
+    }
+
+    fn implement_bitfield(name: &str, bitfield: &yaml_parser::BitfieldType) -> String {
+        let method = bitfield.data_type.to_typescript_method();
+        format!(
+            r#"
+function write{name}(writer: FrameWriter, value: {name}): void {{
+  writer.{method}(value.value);
+}}
+
+function read{name}(reader: FrameReader): {name} {{
+  return new {name}(reader.{method}());
+}}
+"#
+        )
+    }
+
+    fn declare_struct(name: &str, structure: &yaml_parser::InstFeedback) -> String {
+        format!(
+            r#"
+{}
+export interface {name} {{{}
+}}
+"#,
+            doc_comment(&structure.description, 0),
+            Self::declare_fields(&structure.parameters, 1)
+        )
+    }
+
+    fn implement_struct(name: &str, structure: &yaml_parser::InstFeedback) -> String {
+        format!(
+            r#"
+function write{name}(writer: FrameWriter, value: {name}): void {{{}
+}}
+
+function read{name}(reader: FrameReader): {name} {{
+  return {{{}
+  }};
+}}
+"#,
+            structure
+                .parameters
+                .iter()
+                .map(|p| format!(
+                    "\n  {};",
+                    p.to_typescript_writing_call(&format!("value.{}", p.name))
+                ))
+                .collect::<String>(),
+            Self::read_fields(&structure.parameters, 2)
+        )
+    }
+
+    /// Discriminated union of the frames, by name
+    fn declare_frames(
+        frame_type: FrameType,
+        frames: &[(u32, String, yaml_parser::InstFeedback)],
+    ) -> String {
+        let members = frames
+            .iter()
+            .map(|(_, name, frame)| {
+                format!(
+                    "\n  {}\n  | {{\n      kind: \"{name}\";{}\n    }}",
+                    comment(&frame.description, 1),
+                    Self::declare_fields(&frame.parameters, 3)
+                )
+            })
+            .collect::<String>();
+
+        format!(
+            "\nexport type {} ={};\n",
+            frame_type.struct_name(),
+            if members.is_empty() {
+                " never".to_string()
+            } else {
+                members
+            }
+        )
+    }
+
+    /// Encoding and decoding functions of the frames
+    fn implement_frames(
+        frame_type: FrameType,
+        frames: &[(u32, String, yaml_parser::InstFeedback)],
+        codes: &crate::CodesFile,
+    ) -> String {
+        let code_type = codes.code_width.data_type();
+        let type_name = frame_type.struct_name();
+        let function_name = capitalize(frame_type.long());
+        let cases = frames
+            .iter()
+            .map(|(id, name, frame)| {
+                format!(
+                    "\n    case \"{name}\":\n      {};{}\n      break;",
+                    code_type.to_typescript_writing_call(&format!("{id:#04x}")),
+                    frame
+                        .parameters
+                        .iter()
+                        .map(|p| format!(
+                            "\n      {};",
+                            p.to_typescript_writing_call(&format!("frame.{}", p.name))
+                        ))
+                        .collect::<String>()
+                )
+            })
+            .collect::<String>();
+        // The `never` type has no `kind` to switch on
+        let encoding = if frames.is_empty() {
+            String::new()
+        } else {
+            format!("\n  switch (frame.kind) {{{cases}\n  }}")
+        };
+
+        format!(
+            r#"
+/** Encodes a {long} frame */
+export function encode{function_name}(frame: {type_name}): Uint8Array {{
+  const writer = new FrameWriter();{encoding}
+  return appendChecksum(writer);
+}}
+
+/** Decodes a received {long} frame */
+export function decode{function_name}(bytes: Uint8Array): {type_name} {{
+  const frame = verifyChecksum(bytes);
+  const reader = new FrameReader(frame, 0);
+  switch ({}) {{{}
+    default:
+      throw new FrameError("UnknownCode");
+  }}
+}}
+"#,
+            code_type.to_typescript_parsing_call(),
+            frames
+                .iter()
+                .map(|(id, name, frame)| {
+                    format!(
+                        "\n    case {id:#04x}:\n      return {{\n        kind: \"{name}\",{}\n      }};",
+                        Self::read_fields(&frame.parameters, 4)
+                    )
+                })
+                .collect::<String>(),
+            long = frame_type.long(),
+        )
+    }
+
+    /// Documented fields of an interface or object type, indented by `level`
+    fn declare_fields(parameters: &[yaml_parser::InstFeedbackParameter], level: usize) -> String {
+        let indent = "  ".repeat(level);
+        parameters
+            .iter()
+            .map(|p| {
+                format!(
+                    "\n{indent}{}\n{indent}{};",
+                    doc_comment(&p.description, level),
+                    p.to_typescript_field()
+                )
+            })
+            .collect::<String>()
+    }
+
+    /// Object literal fields decoded in order, indented by `level`
+    fn read_fields(parameters: &[yaml_parser::InstFeedbackParameter], level: usize) -> String {
+        let indent = "  ".repeat(level);
+        parameters
+            .iter()
+            .map(|p| format!("\n{indent}{}: {},", p.name, p.to_typescript_parsing_call()))
+            .collect::<String>()
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// Turns a possibly multi-line description into line comments indented by `level`
+fn comment(description: &str, level: usize) -> String {
+    description
+        .trim_end()
+        .lines()
+        .map(|line| format!("// {line}"))
+        .collect::<Vec<String>>()
+        .join(&format!("\n{}", "  ".repeat(level)))
+}
+
+/// Turns a possibly multi-line description into a documentation comment indented by `level`
+fn doc_comment(description: &str, level: usize) -> String {
+    let indent = "  ".repeat(level);
+    let lines = description
+        .trim_end()
+        .replace("*/", "*\\/")
+        .lines()
+        .map(str::to_string)
+        .collect::<Vec<String>>();
+    match lines.as_slice() {
+        [line] => format!("/** {line} */"),
+        lines => format!(
+            "/**\n{}\n{indent} */",
+            lines
+                .iter()
+                .map(|line| format!("{indent} * {line}"))
+                .collect::<Vec<String>>()
+                .join("\n")
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::yaml_parser::test::integration_codes_file;
+    use crate::yaml_parser::{Checksum, Endianness};
+
+    #[test]
+    fn settings_follow_the_wire_format() {
+        let mut codes = integration_codes_file();
+        let model = TypeScriptLanguageModel {};
+
+        assert_eq!(
+            "const LITTLE_ENDIAN: boolean = true;\nconst CHECKSUM: Checksum | null = null;\n",
+            model.declare_settings(&codes)
+        );
+
+        codes.endianness = Endianness::Big;
+        codes.checksum = Some(Checksum::Crc32);
+        assert_eq!(
+            "const LITTLE_ENDIAN: boolean = false;\nconst CHECKSUM: Checksum | null = \"crc32\";\n",
+            model.declare_settings(&codes)
+        );
+    }
+
+    #[test]
+    fn descriptions_are_escaped_in_doc_comments() {
+        assert_eq!("/** Ends with *\\/ */", doc_comment("Ends with */\n", 0));
+        assert_eq!(
+            "/**\n   * First line\n   * Second line\n   */",
+            doc_comment("First line\nSecond line\n", 1)
+        );
+    }
+}
@@ -0,0 +1,667 @@
+use crate::file_generator::{FrameType, LanguageModel};
+use crate::yaml_parser::{self, ParameterType};
+
+impl yaml_parser::ParameterType {
+    /// `ProtoField` constructor of the scalar types
+    fn to_lua_field_kind(&self) -> &str {
+        match self {
+            ParameterType::Uint8 => "uint8",
+            ParameterType::Int8 => "int8",
+            ParameterType::Uint16 => "uint16",
+            ParameterType::Int16 => "int16",
+            ParameterType::Uint32 => "uint32",
+            ParameterType::Int32 => "int32",
+            ParameterType::Uint64 => "uint64",
+            ParameterType::Int64 => "int64",
+            ParameterType::Float32 => "float",
+            ParameterType::Float64 => "double",
+            ParameterType::Bool => "bool",
+            _ => unreachable!("{self} isn't a scalar type"),
+        }
+    }
+
+    /// Declarations of the fields displaying a value of this type under `key`
+    fn to_lua_fields(
+        &self,
+        codes: &yaml_parser::CodesFile,
+        key: &str,
+        name: &str,
+        description: &str,
+    ) -> Vec<String> {
+        let field = |kind: &str, arguments: String| {
+            format!("add_field(\"{key}\", \"{kind}\", \"{name}\", {arguments})")
+        };
+        let count_field = |kind: &str, arguments: String| {
+            format!("add_field(\"{key}\", \"{kind}\", \"{name} count\", {arguments})")
+        };
+        let quoted = lua_string(description);
+        match self {
+            ParameterType::Array { item, length } => {
+                let mut fields = vec![count_field(
+                    length.to_lua_field_kind(),
+                    format!("base.DEC, nil, nil, {quoted}"),
+                )];
+                fields.extend(item.to_lua_fields(codes, &format!("{key}.item"), name, description));
+                fields
+            }
+            ParameterType::FixedArray { item, .. } => {
+                let mut fields = vec![field("none", quoted)];
+                fields.extend(item.to_lua_fields(codes, &format!("{key}.item"), name, description));
+                fields
+            }
+            ParameterType::Custom(type_name) => {
+                if let Some(enumeration) = codes.enums.get(type_name) {
+                    vec![field(
+                        enumeration.data_type.to_lua_field_kind(),
+                        format!("base.DEC, ENUMS.{type_name}, nil, {quoted}"),
+                    )]
+                } else if let Some(bitfield) = codes.bitfields.get(type_name) {
+                    vec![field(
+                        bitfield.data_type.to_lua_field_kind(),
+                        format!("base.HEX, nil, nil, {quoted}"),
+                    )]
+                } else {
+                    vec![field("none", quoted)]
+                }
+            }
+            ParameterType::String => vec![field("stringz", format!("nil, {quoted}"))],
+            ParameterType::Float32 | ParameterType::Float64 => {
+                vec![field(self.to_lua_field_kind(), format!("nil, {quoted}"))]
+            }
+            ParameterType::Bool => vec![field("bool", format!("base.NONE, nil, nil, {quoted}"))],
+            _ => vec![field(
+                self.to_lua_field_kind(),
+                format!("base.DEC, nil, nil, {quoted}"),
+            )],
+        }
+    }
+
+    /// Dissector of a value of this type, displayed by the fields under `key`
+    fn to_lua_dissector(&self, codes: &yaml_parser::CodesFile, key: &str) -> String {
+        match self {
+            ParameterType::Array { item, length } => format!(
+                "D.array(F[\"{key}\"], {}, {})",
+                length.size(),
+                item.to_lua_dissector(codes, &format!("{key}.item"))
+            ),
+            ParameterType::FixedArray { item, count } => format!(
+                "D.fixed_array(F[\"{key}\"], {count}, {})",
+                item.to_lua_dissector(codes, &format!("{key}.item"))
+            ),
+            ParameterType::Custom(type_name) => {
+                if let Some(enumeration) = codes.enums.get(type_name) {
+                    format!(
+                        "D.enum(F[\"{key}\"], {}, ENUMS.{type_name})",
+                        enumeration.data_type.size()
+                    )
+                } else if let Some(bitfield) = codes.bitfields.get(type_name) {
+                    format!(
+                        "D.bitfield(F[\"{key}\"], {}, BITFIELDS.{type_name})",
+                        bitfield.data_type.size()
+                    )
+                } else {
+                    format!("D.struct(F[\"{key}\"], \"{type_name}\")")
+                }
+            }
+            ParameterType::String => format!("D.stringz(F[\"{key}\"])"),
+            _ => format!("D.value(F[\"{key}\"], {})", self.size()),
+        }
+    }
+}
+
+impl yaml_parser::InstFeedbackParameter {
+    /// Declarations of the fields of this parameter, preceded by its presence flag when optional
+    fn to_lua_fields(&self, codes: &yaml_parser::CodesFile, prefix: &str) -> Vec<String> {
+        let key = format!("{prefix}.{}", self.name.to_lowercase());
+        let mut fields = Vec::new();
+        if self.optional {
+            fields.push(format!(
+                "add_field(\"{key}.present\", \"bool\", \"{} present\", base.NONE)",
+                self.name
+            ));
+        }
+        fields.extend(
+            self.data_type
+                .to_lua_fields(codes, &key, &self.name, &self.description),
+        );
+        fields
+    }
+
+    /// Dissector of this parameter, preceded by its presence flag when optional
+    fn to_lua_dissector(&self, codes: &yaml_parser::CodesFile, prefix: &str) -> String {
+        let key = format!("{prefix}.{}", self.name.to_lowercase());
+        let dissector = self.data_type.to_lua_dissector(codes, &key);
+        if self.optional {
+            format!("D.optional(F[\"{key}.present\"], {dissector})")
+        } else {
+            dissector
+        }
+    }
+}
+
+pub struct WiresharkLanguageModel {
+    /// Wireshark protocol name, also used as the display filters prefix
+    pub protocol_name: String,
+}
+
+impl LanguageModel for WiresharkLanguageModel {
+    fn custom_includes(&self) -> String {
+        String::new()
+    }
+
+    fn custom_footer(&self, _codes: &crate::CodesFile) -> String {
+        String::new()
+    }
+
+    fn declare_settings(&self, codes: &crate::CodesFile) -> String {
+        format!(
+            r#"local PROTOCOL_NAME = "{}"
+local PROTOCOL_DESCRIPTION = "{} protocol"
+local LITTLE_ENDIAN = {}
+local CODE_SIZE = {}
+local CHECKSUM = {}
+local CHECKSUM_SIZE = {}
+local FRAMING = {}
+
+local CODE_NAMES = {{{}
+}}
+"#,
+            self.protocol_name,
+            capitalize(&self.protocol_name),
+            codes.endianness == yaml_parser::Endianness::Little,
+            codes.code_width.size(),
+            match codes.checksum {
+                Some(checksum) => format!("\"{}\"", format!("{checksum:?}").to_lowercase()),
+                None => "nil".to_string(),
+            },
+            codes.checksum.map(|checksum| checksum.size()).unwrap_or(0),
+            match codes.framing {
+                Some(yaml_parser::Framing::Cobs) => "\"cobs\"",
+                Some(yaml_parser::Framing::Slip) => "\"slip\"",
+                Some(yaml_parser::Framing::SyncLength) => "\"sync\"",
+                None => "nil",
+            },
+            codes
+                .codes
+                .iter()
+                .map(|(id, code)| format!("\n    [{id:#04x}] = {},", lua_string(&code.name)))
+                .collect::<String>()
+        )
+    }
+
+    /// Value names of the enumerations, and masked fields of the bitfields and structures
+    fn declare_types(&self, codes: &crate::CodesFile) -> String {
+        let enums = codes
+            .enums
+            .iter()
+            .map(|(name, enumeration)| {
+                format!(
+                    "ENUMS.{name} = {{{}\n}}\n",
+                    enumeration
+                        .values
+                        .iter()
+                        .map(|v| format!("\n    [{}] = {},", v.value, lua_string(&v.name)))
+                        .collect::<String>()
+                )
+            })
+            .collect::<String>();
+        let bitfields = codes
+            .bitfields
+            .iter()
+            .map(|(name, bitfield)| Self::declare_bitfield(name, bitfield))
+            .collect::<String>();
+        let structures = codes
+            .types
+            .iter()
+            .flat_map(|(name, structure)| {
+                structure.parameters.iter().flat_map(move |p| {
+                    p.to_lua_fields(codes, &format!("type.{}", name.to_lowercase()))
+                })
+            })
+            .map(|field| format!("{field}\n"))
+            .collect::<String>();
+
+        format!("local ENUMS = {{}}\n{enums}\nlocal BITFIELDS = {{}}\n{bitfields}\n{structures}")
+    }
+
+    fn implement_types(&self, codes: &crate::CodesFile) -> String {
+        codes
+            .types
+            .iter()
+            .map(|(name, structure)| {
+                format!(
+                    "TYPES.{name} = {}\n",
+                    Self::sequence(codes, structure, &format!("type.{}", name.to_lowercase()))
+                )
+            })
+            .collect::<String>()
+    }
+
+    fn declare_feedbacks(&self, codes: &crate::CodesFile) -> String {
+        Self::declare_frames(FrameType::Feedback, codes, &codes.get_feedbacks())
+    }
+
+    fn declare_instructions(&self, codes: &crate::CodesFile) -> String {
+        Self::declare_frames(FrameType::Instruction, codes, &codes.get_instructions())
+    }
+
+    fn implement_feedbacks(&self, codes: &crate::CodesFile) -> String {
+        Self::implement_frames(FrameType::Feedback, codes, &codes.get_feedbacks())
+    }
+
+    fn implement_instructions(&self, codes: &crate::CodesFile) -> String {
+        Self::implement_frames(FrameType::Instruction, codes, &codes.get_instructions())
+    }
+}
+
+impl WiresharkLanguageModel {
+    /// Fields of a bitfield, masked from the value of the parameter they're added under
+    fn declare_bitfield(name: &str, bitfield: &yaml_parser::BitfieldType) -> String {
+        let bits = bitfield.data_type.size() * 8;
+        let key = format!("type.{}", name.to_lowercase());
+        let fields = bitfield
+            .fields_with_offsets()
+            .into_iter()
+            .map(|(field, offset)| {
+                let mask = field.mask() << offset;
+                let mask = if bits == 64 {
+                    format!("UInt64.fromhex(\"{mask:016x}\")")
+                } else {
+                    format!("{mask:#x}")
+                };
+                let (kind, display) = if field.bits == 1 {
+                    ("bool", bits.to_string())
+                } else {
+                    (
+                        bitfield.data_type.to_lua_field_kind(),
+                        "base.DEC".to_string(),
+                    )
+                };
+                format!(
+                    "add_field(\"{key}.{}\", \"{kind}\", \"{}\", {display}, nil, {mask}, {})\n",
+                    field.name.to_lowercase(),
+                    field.name,
+                    lua_string(&field.description)
+                )
+            })
+            .collect::<String>();
+
+        format!(
+            "{fields}BITFIELDS.{name} = {{{}\n}}\n",
+            bitfield
+                .fields
+                .iter()
+                .map(|field| format!("\n    F[\"{key}.{}\"],", field.name.to_lowercase()))
+                .collect::<String>()
+        )
+    }
+
+    fn declare_frames(
+        frame_type: FrameType,
+        codes: &yaml_parser::CodesFile,
+        frames: &[(u32, String, yaml_parser::InstFeedback)],
+    ) -> String {
+        frames
+            .iter()
+            .flat_map(|(_, name, frame)| {
+                let prefix = format!("{}.{}", frame_type.short(), name.to_lowercase());
+                frame
+                    .parameters
+                    .iter()
+                    .flat_map(move |p| p.to_lua_fields(codes, &prefix))
+            })
+            .map(|field| format!("{field}\n"))
+            .collect::<String>()
+    }
+
+    /// Dissectors of the frames, by code
+    fn implement_frames(
+        frame_type: FrameType,
+        codes: &yaml_parser::CodesFile,
+        frames: &[(u32, String, yaml_parser::InstFeedback)],
+    ) -> String {
+        frames
+            .iter()
+            .map(|(id, name, frame)| {
+                format!(
+                    "{}[{id:#04x}] = {}\n",
+                    frame_type.struct_name().to_uppercase(),
+                    Self::sequence(
+                        codes,
+                        frame,
+                        &format!("{}.{}", frame_type.short(), name.to_lowercase())
+                    )
+                )
+            })
+            .collect::<String>()
+    }
+
+    /// Dissector of the parameters of a frame or structure, in order
+    fn sequence(
+        codes: &yaml_parser::CodesFile,
+        structure: &yaml_parser::InstFeedback,
+        prefix: &str,
+    ) -> String {
+        if structure.parameters.is_empty() {
+            return "D.sequence({})".to_string();
+        }
+        format!(
+            "D.sequence({{{}\n}})",
+            structure
+                .parameters
+                .iter()
+                .map(|p| format!("\n    {},", p.to_lua_dissector(codes, prefix)))
+                .collect::<String>()
+        )
+    }
+}
+
+/// Quotes a description as a single-line Lua string literal
+fn lua_string(text: &str) -> String {
+    format!(
+        "\"{}\"",
+        text.trim()
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace(['\r', '\n', '\t'], " ")
+    )
+}
+
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::yaml_parser::test::integration_codes_file;
+    use crate::yaml_parser::{Checksum, CodeWidth, Endianness, Framing};
+
+    /// Lines of the generated Lua mentioning `text`
+    fn lines_with<'a>(lua: &'a str, text: &str) -> Vec<&'a str> {
+        lua.lines().filter(|line| line.contains(text)).collect()
+    }
+
+    /// Codes of the frames registered in the `table` of dissectors
+    fn registered_codes<'a>(lua: &'a str, table: &str) -> Vec<&'a str> {
+        lua.lines()
+            .filter_map(|line| {
+                line.strip_prefix(table)?
+                    .strip_prefix('[')?
+                    .split(']')
+                    .next()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn every_type_has_fields_and_a_dissector() {
+        let codes = integration_codes_file();
+        let value = |kind: &str, size: usize| {
+            (
+                vec![format!(
+                    "add_field(\"k\", \"{kind}\", \"n\", base.DEC, nil, nil, \"d\")"
+                )],
+                format!("D.value(F[\"k\"], {size})"),
+            )
+        };
+        let float = |kind: &str, size: usize| {
+            (
+                vec![format!("add_field(\"k\", \"{kind}\", \"n\", nil, \"d\")")],
+                format!("D.value(F[\"k\"], {size})"),
+            )
+        };
+        let expectations = [
+            ("u8", value("uint8", 1)),
+            ("i8", value("int8", 1)),
+            ("u16", value("uint16", 2)),
+            ("i16", value("int16", 2)),
+            ("u32", value("uint32", 4)),
+            ("i32", value("int32", 4)),
+            ("u64", value("uint64", 8)),
+            ("i64", value("int64", 8)),
+            ("f32", float("float", 4)),
+            ("f64", float("double", 8)),
+            (
+                "bool",
+                (
+                    vec!["add_field(\"k\", \"bool\", \"n\", base.NONE, nil, nil, \"d\")".into()],
+                    "D.value(F[\"k\"], 1)".into(),
+                ),
+            ),
+            (
+                "string",
+                (
+                    vec!["add_field(\"k\", \"stringz\", \"n\", nil, \"d\")".into()],
+                    "D.stringz(F[\"k\"])".into(),
+                ),
+            ),
+            (
+                "u16[u32]",
+                (
+                    vec![
+                        "add_field(\"k\", \"uint32\", \"n count\", base.DEC, nil, nil, \"d\")"
+                            .into(),
+                        "add_field(\"k.item\", \"uint16\", \"n\", base.DEC, nil, nil, \"d\")"
+                            .into(),
+                    ],
+                    "D.array(F[\"k\"], 4, D.value(F[\"k.item\"], 2))".into(),
+                ),
+            ),
+            (
+                "string[2]",
+                (
+                    vec![
+                        "add_field(\"k\", \"none\", \"n\", \"d\")".into(),
+                        "add_field(\"k.item\", \"stringz\", \"n\", nil, \"d\")".into(),
+                    ],
+                    "D.fixed_array(F[\"k\"], 2, D.stringz(F[\"k.item\"]))".into(),
+                ),
+            ),
+            (
+                "Offset",
+                (
+                    vec![
+                        "add_field(\"k\", \"int16\", \"n\", base.DEC, ENUMS.Offset, nil, \"d\")"
+                            .into(),
+                    ],
+                    "D.enum(F[\"k\"], 2, ENUMS.Offset)".into(),
+                ),
+            ),
+            (
+                "Status",
+                (
+                    vec!["add_field(\"k\", \"uint8\", \"n\", base.HEX, nil, nil, \"d\")".into()],
+                    "D.bitfield(F[\"k\"], 1, BITFIELDS.Status)".into(),
+                ),
+            ),
+            (
+                "RtcDate",
+                (
+                    vec!["add_field(\"k\", \"none\", \"n\", \"d\")".into()],
+                    "D.struct(F[\"k\"], \"RtcDate\")".into(),
+                ),
+            ),
+        ];
+
+        for (input, (fields, dissector)) in expectations {
+            let data_type = ParameterType::try_from(input.to_string()).unwrap();
+            assert_eq!(
+                fields,
+                data_type.to_lua_fields(&codes, "k", "n", "d"),
+                "{input} fields"
+            );
+            assert_eq!(
+                dissector,
+                data_type.to_lua_dissector(&codes, "k"),
+                "{input} dissector"
+            );
+        }
+    }
+
+    #[test]
+    fn optional_parameters_have_a_presence_field() {
+        let codes = integration_codes_file();
+        let model = WiresharkLanguageModel {
+            protocol_name: "codes".to_string(),
+        };
+
+        assert_eq!(
+            vec![
+                "add_field(\"inst.optionaltest.level.present\", \"bool\", \"level present\", base.NONE)",
+                "add_field(\"inst.optionaltest.level\", \"uint8\", \"level\", base.DEC, nil, nil, \"Requested level\")",
+                "add_field(\"inst.optionaltest.label.present\", \"bool\", \"label present\", base.NONE)",
+                "add_field(\"inst.optionaltest.label\", \"stringz\", \"label\", nil, \"Optional label\")",
+                "add_field(\"inst.optionaltest.date.present\", \"bool\", \"date present\", base.NONE)",
+                "add_field(\"inst.optionaltest.date\", \"none\", \"date\", \"Optional date\")",
+            ],
+            lines_with(&model.declare_instructions(&codes), "\"inst.optionaltest.")
+        );
+        assert_eq!(
+            vec![
+                "    D.optional(F[\"inst.optionaltest.level.present\"], D.value(F[\"inst.optionaltest.level\"], 1)),",
+                "    D.optional(F[\"inst.optionaltest.label.present\"], D.stringz(F[\"inst.optionaltest.label\"])),",
+                "    D.optional(F[\"inst.optionaltest.date.present\"], D.struct(F[\"inst.optionaltest.date\"], \"RtcDate\")),",
+            ],
+            lines_with(
+                &model.implement_instructions(&codes),
+                "\"inst.optionaltest."
+            )
+        );
+    }
+
+    #[test]
+    fn settings_follow_the_wire_format() {
+        let mut codes = integration_codes_file();
+        let model = WiresharkLanguageModel {
+            protocol_name: "morpheus".to_string(),
+        };
+
+        let settings = model.declare_settings(&codes);
+        assert_eq!(
+            vec![
+                "local PROTOCOL_NAME = \"morpheus\"",
+                "local PROTOCOL_DESCRIPTION = \"Morpheus protocol\"",
+                "local LITTLE_ENDIAN = true",
+                "local CODE_SIZE = 1",
+                "local CHECKSUM = nil",
+                "local CHECKSUM_SIZE = 0",
+                "local FRAMING = nil",
+                "local CODE_NAMES = {",
+            ],
+            lines_with(&settings, "local ")
+        );
+        assert_eq!(
+            vec!["    [0x0b] = \"OptionalTest\","],
+            lines_with(&settings, "[0x0b]")
+        );
+
+        codes.endianness = Endianness::Big;
+        codes.code_width = CodeWidth::Two;
+        codes.checksum = Some(Checksum::Crc16);
+        codes.framing = Some(Framing::SyncLength);
+        assert_eq!(
+            vec![
+                "local PROTOCOL_NAME = \"morpheus\"",
+                "local PROTOCOL_DESCRIPTION = \"Morpheus protocol\"",
+                "local LITTLE_ENDIAN = false",
+                "local CODE_SIZE = 2",
+                "local CHECKSUM = \"crc16\"",
+                "local CHECKSUM_SIZE = 2",
+                "local FRAMING = \"sync\"",
+                "local CODE_NAMES = {",
+            ],
+            lines_with(&model.declare_settings(&codes), "local ")
+        );
+    }
+
+    #[test]
+    fn types_are_declared_for_their_dissectors() {
+        let codes = integration_codes_file();
+        let model = WiresharkLanguageModel {
+            protocol_name: "codes".to_string(),
+        };
+        let types = model.declare_types(&codes);
+
+        assert_eq!(
+            vec![
+                "    [-300] = \"Negative\",",
+                "    [300] = \"Positive\",",
+                "    [0] = \"Light\",",
+                "    [1] = \"Deep\",",
+                "    [7] = \"Hibernate\",",
+            ],
+            lines_with(&types, "] = \"")
+        );
+        assert_eq!(
+            vec![
+                "add_field(\"type.status.charging\", \"bool\", \"charging\", 8, nil, 0x1, \"Battery is charging\")",
+                "add_field(\"type.status.level\", \"uint8\", \"level\", base.DEC, nil, 0xe, \"Battery level from 0 to 7\")",
+                "add_field(\"type.status.error\", \"bool\", \"error\", 8, nil, 0x10, \"An error occurred\")",
+                "    F[\"type.status.charging\"],",
+                "    F[\"type.status.level\"],",
+                "    F[\"type.status.error\"],",
+            ],
+            lines_with(&types, "\"type.status.")
+        );
+        assert_eq!(
+            vec![
+                "    D.struct(F[\"type.event.date\"], \"RtcDate\"),",
+                "    D.stringz(F[\"type.event.label\"]),",
+                "    D.enum(F[\"type.event.mode\"], 1, ENUMS.SleepMode),",
+            ],
+            lines_with(&model.implement_types(&codes), "\"type.event.")
+        );
+    }
+
+    /// The direction heuristic tries the dissectors of both frame types, registered by code
+    #[test]
+    fn frames_are_registered_by_direction() {
+        let mut codes = integration_codes_file();
+        let model = WiresharkLanguageModel {
+            protocol_name: "codes".to_string(),
+        };
+        let all_codes = vec![
+            "0x00", "0x01", "0x03", "0x04", "0x05", "0x06", "0x07", "0x08", "0x09", "0x0a", "0x0b",
+        ];
+
+        let instructions = model.implement_instructions(&codes);
+        let feedbacks = model.implement_feedbacks(&codes);
+        assert_eq!(all_codes, registered_codes(&instructions, "INSTRUCTIONS"));
+        assert_eq!(all_codes, registered_codes(&feedbacks, "FEEDBACKS"));
+        assert!(registered_codes(&instructions, "FEEDBACKS").is_empty());
+        assert!(registered_codes(&feedbacks, "INSTRUCTIONS").is_empty());
+        assert_eq!(
+            vec![
+                "    D.array(F[\"inst.arraytest.readings\"], 1, D.value(F[\"inst.arraytest.readings.item\"], 2)),",
+                "    D.array(F[\"inst.arraytest.labels\"], 2, D.stringz(F[\"inst.arraytest.labels.item\"])),",
+            ],
+            lines_with(&instructions, "\"inst.arraytest.")
+        );
+
+        codes.codes.get_mut(&0x05).unwrap().feedback = None;
+        assert_eq!(
+            all_codes,
+            registered_codes(&model.implement_instructions(&codes), "INSTRUCTIONS")
+        );
+        assert_eq!(
+            vec![
+                "0x00", "0x01", "0x03", "0x04", "0x06", "0x07", "0x08", "0x09", "0x0a", "0x0b"
+            ],
+            registered_codes(&model.implement_feedbacks(&codes), "FEEDBACKS")
+        );
+        assert!(lines_with(&model.declare_feedbacks(&codes), "\"fb.arraytest.").is_empty());
+    }
+
+    #[test]
+    fn descriptions_are_quoted_on_one_line() {
+        assert_eq!(
+            "\"Quoted \\\"value\\\" on two lines \\\\\"",
+            lua_string("Quoted \"value\"\non two lines \\\n")
+        );
+    }
+}
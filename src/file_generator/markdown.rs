@@ -0,0 +1,582 @@
+use crate::file_generator::{FileGenerator, FrameType};
+use crate::yaml_parser::{self, ParameterType};
+use std::fs::File;
+use std::io::{self, Write};
+
+const MARKDOWN_TEMPLATE: &str = include_str!("./templates/markdown_template.md");
+
+/// Protocol documentation, rendered from the descriptions of the input file
+pub struct MarkdownFileGenerator {
+    writer: Box<dyn Write>,
+}
+
+impl FileGenerator for MarkdownFileGenerator {
+    fn build_file(&mut self, codes: &yaml_parser::CodesFile) -> Result<(), io::Error> {
+        self.writer.write_all(
+            MARKDOWN_TEMPLATE
+                .replace("##WIRE_FORMAT##", &wire_format(codes))
+                .replace("##TYPES##", &types(codes))
+                .replace("##CODES##", &code_sections(codes))
+                .as_bytes(),
+        )
+    }
+}
+
+impl MarkdownFileGenerator {
+    pub fn new(file_name: String) -> Result<Self, io::Error> {
+        let file = Box::new(File::create(file_name)?);
+        Ok(Self { writer: file })
+    }
+}
+
+impl yaml_parser::ParameterType {
+    /// Size on the wire, `None` when it depends on the value
+    fn wire_size(&self, codes: &yaml_parser::CodesFile) -> Option<usize> {
+        match self {
+            ParameterType::String | ParameterType::Array { .. } => None,
+            ParameterType::FixedArray { item, count } => Some(item.wire_size(codes)? * count),
+            ParameterType::Custom(name) => {
+                if let Some(enumeration) = codes.enums.get(name) {
+                    Some(enumeration.data_type.size())
+                } else if let Some(bitfield) = codes.bitfields.get(name) {
+                    Some(bitfield.data_type.size())
+                } else {
+                    codes.types.get(name).and_then(|structure| {
+                        structure
+                            .parameters
+                            .iter()
+                            .map(|p| p.wire_size(codes))
+                            .sum()
+                    })
+                }
+            }
+            scalar => Some(scalar.size()),
+        }
+    }
+
+    /// Smallest size on the wire, empty strings and arrays included
+    fn min_wire_size(&self, codes: &yaml_parser::CodesFile) -> usize {
+        match self {
+            ParameterType::String => 1,
+            ParameterType::Array { length, .. } => length.size(),
+            ParameterType::FixedArray { item, count } => item.min_wire_size(codes) * count,
+            ParameterType::Custom(name) => match codes.types.get(name) {
+                Some(structure) => structure
+                    .parameters
+                    .iter()
+                    .map(|p| p.min_wire_size(codes))
+                    .sum(),
+                None => self.wire_size(codes).unwrap_or_default(),
+            },
+            scalar => scalar.size(),
+        }
+    }
+}
+
+impl yaml_parser::InstFeedbackParameter {
+    fn wire_size(&self, codes: &yaml_parser::CodesFile) -> Option<usize> {
+        if self.optional {
+            None
+        } else {
+            self.data_type.wire_size(codes)
+        }
+    }
+
+    fn min_wire_size(&self, codes: &yaml_parser::CodesFile) -> usize {
+        if self.optional {
+            1
+        } else {
+            self.data_type.min_wire_size(codes)
+        }
+    }
+}
+
+/// A row of a layout table, offsets are unknown after a variable-size value
+struct LayoutRow {
+    offset: Option<usize>,
+    size: Option<usize>,
+    /// Smallest size, for the variable-size values
+    min_size: usize,
+    /// Size cell, only the presence byte of absent optional values is sent
+    size_text: String,
+    name: String,
+    data_type: String,
+    description: String,
+}
+
+impl LayoutRow {
+    fn fixed(size: usize, name: &str, data_type: &str, description: String) -> Self {
+        Self {
+            offset: None,
+            size: Some(size),
+            min_size: size,
+            size_text: size.to_string(),
+            name: name.to_string(),
+            data_type: format!("`{data_type}`"),
+            description,
+        }
+    }
+
+    fn parameter(p: &yaml_parser::InstFeedbackParameter, codes: &yaml_parser::CodesFile) -> Self {
+        let data_type = format!("`{}`", p.data_type.to_input_string());
+        let (data_type, size_text) = match p.data_type.wire_size(codes) {
+            Some(size) if p.optional => (
+                format!("{data_type} (optional)"),
+                format!("1 or {}", size + 1),
+            ),
+            None if p.optional => (format!("{data_type} (optional)"), "variable".to_string()),
+            Some(size) => (data_type, size.to_string()),
+            None => (data_type, "variable".to_string()),
+        };
+        Self {
+            offset: None,
+            size: p.wire_size(codes),
+            min_size: p.min_wire_size(codes),
+            size_text,
+            name: p.name.clone(),
+            data_type,
+            description: p.description.clone(),
+        }
+    }
+}
+
+fn wire_format(codes: &yaml_parser::CodesFile) -> String {
+    let mut rules = vec![
+        format!(
+            "Each frame starts with its code on {}, followed by its parameters in order.",
+            match codes.code_width.size() {
+                1 => "one byte".to_string(),
+                size => format!("{size} bytes"),
+            }
+        ),
+        format!(
+            "Multi-bytes values, including the arrays counts, are sent in {} endian order.",
+            match codes.endianness {
+                yaml_parser::Endianness::Little => "little",
+                yaml_parser::Endianness::Big => "big",
+            }
+        ),
+        "Strings are sent in UTF-8, terminated by a NUL byte.".to_string(),
+        "Variable-length arrays (`TYPE[]`) are prefixed by their items count, on one byte \
+         unless given as `TYPE[u16]` or `TYPE[u32]`. Fixed-length arrays (`TYPE[N]`) are sent \
+         without count."
+            .to_string(),
+        "Optional parameters are preceded by a presence byte, `1` when the value follows and \
+         `0` otherwise."
+            .to_string(),
+    ];
+
+    rules.push(match codes.checksum {
+        Some(checksum) => format!(
+            "Frames end with a {} of their code and parameters, on {} bytes.",
+            match checksum {
+                yaml_parser::Checksum::Crc8 => "CRC-8 (polynomial 0x07)",
+                yaml_parser::Checksum::Crc16 => "CRC-16/CCITT-FALSE",
+                yaml_parser::Checksum::Crc32 => "CRC-32",
+            },
+            checksum.size()
+        ),
+        None => "Frames don't carry a checksum.".to_string(),
+    });
+
+    if let Some(framing) = codes.framing {
+        rules.push(format!(
+            "On byte streams, {}.",
+            match framing {
+                yaml_parser::Framing::Cobs =>
+                    "frames are COBS encoded and terminated by a `0x00` byte",
+                yaml_parser::Framing::Slip =>
+                    "frames are SLIP escaped and terminated by a `0xC0` byte",
+                yaml_parser::Framing::SyncLength =>
+                    "frames are preceded by a `0xAA` sync byte and their length on two bytes",
+            }
+        ));
+    }
+
+    rules
+        .iter()
+        .map(|rule| format!("- {rule}"))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn types(codes: &yaml_parser::CodesFile) -> String {
+    let enums = codes.enums.iter().map(|(name, enumeration)| {
+        format!(
+            "### {name}\n\n{}\n\nEnumeration sent as `{}`.\n\n| Value | Name | Description |\n|---|---|---|\n{}\n",
+            paragraph(&enumeration.description),
+            enumeration.data_type.to_input_string(),
+            enumeration
+                .values
+                .iter()
+                .map(|v| format!("| {} | {} | {} |", v.value, v.name, cell(&v.description)))
+                .collect::<Vec<String>>()
+                .join("\n")
+        )
+    });
+
+    let bitfields = codes.bitfields.iter().map(|(name, bitfield)| {
+        format!(
+            "### {name}\n\n{}\n\nBitfield sent as `{}`, from the least significant bit.\n\n| Bits | Field | Description |\n|---|---|---|\n{}\n",
+            paragraph(&bitfield.description),
+            bitfield.data_type.to_input_string(),
+            bitfield
+                .fields_with_offsets()
+                .into_iter()
+                .map(|(field, offset)| {
+                    let bits = if field.bits == 1 {
+                        offset.to_string()
+                    } else {
+                        format!("{offset}-{}", offset + field.bits - 1)
+                    };
+                    format!("| {bits} | {} | {} |", field.name, cell(&field.description))
+                })
+                .collect::<Vec<String>>()
+                .join("\n")
+        )
+    });
+
+    let structures = codes.types.iter().map(|(name, structure)| {
+        format!(
+            "### {name}\n\n{}\n\n{}\n",
+            paragraph(&structure.description),
+            layout(Vec::new(), &structure.parameters, codes, None)
+        )
+    });
+
+    let sections = enums
+        .chain(bitfields)
+        .chain(structures)
+        .collect::<Vec<String>>();
+    if sections.is_empty() {
+        String::new()
+    } else {
+        format!("## Types\n\n{}", sections.join("\n"))
+    }
+}
+
+fn code_sections(codes: &yaml_parser::CodesFile) -> String {
+    let summary = codes
+        .codes
+        .iter()
+        .map(|(id, code)| {
+            let summary = |frame: &Option<yaml_parser::InstFeedback>| {
+                frame.as_ref().map_or("-".to_string(), |frame| {
+                    cell(frame.description.lines().next().unwrap_or_default())
+                })
+            };
+            format!(
+                "| `{id:#04x}` | [{}](#{id:#04x}-{}) | {} | {} |",
+                code.name,
+                code.name.to_lowercase(),
+                summary(&code.instruction),
+                summary(&code.feedback)
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let sections = codes
+        .codes
+        .iter()
+        .map(|(id, code)| {
+            format!(
+                "### {id:#04x} {}\n\n{}\n{}",
+                code.name,
+                frame_section(FrameType::Instruction, *id, &code.instruction, codes),
+                frame_section(FrameType::Feedback, *id, &code.feedback, codes)
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    format!("| Code | Name | Instruction | Feedback |\n|---|---|---|---|\n{summary}\n\n{sections}")
+}
+
+/// Description and layout of the instruction or the feedback of a code
+fn frame_section(
+    frame_type: FrameType,
+    id: u32,
+    frame: &Option<yaml_parser::InstFeedback>,
+    codes: &yaml_parser::CodesFile,
+) -> String {
+    let title = capitalize(frame_type.long());
+    let Some(frame) = frame else {
+        return format!("#### {title}\n\nThis code has no {}.\n", frame_type.long());
+    };
+
+    let code_type = codes.code_width.data_type();
+    let code = LayoutRow::fixed(
+        code_type.size(),
+        "code",
+        &code_type.to_input_string(),
+        format!("`{id:#04x}`"),
+    );
+    format!(
+        "#### {title}\n\n{}\n\n{}\n",
+        paragraph(&frame.description),
+        layout(vec![code], &frame.parameters, codes, codes.checksum)
+    )
+}
+
+/// Byte-offset table of the `leading` rows followed by the parameters, with their total size
+fn layout(
+    leading: Vec<LayoutRow>,
+    parameters: &[yaml_parser::InstFeedbackParameter],
+    codes: &yaml_parser::CodesFile,
+    checksum: Option<yaml_parser::Checksum>,
+) -> String {
+    let mut rows = leading
+        .into_iter()
+        .chain(parameters.iter().map(|p| LayoutRow::parameter(p, codes)))
+        .chain(checksum.map(|checksum| {
+            LayoutRow::fixed(
+                checksum.size(),
+                "checksum",
+                &format!("u{}", checksum.size() * 8),
+                "Checksum of the code and parameters".to_string(),
+            )
+        }))
+        .collect::<Vec<LayoutRow>>();
+
+    let mut offset = Some(0);
+    for row in rows.iter_mut() {
+        row.offset = offset;
+        offset = offset.zip(row.size).map(|(offset, size)| offset + size);
+    }
+
+    let size = match offset {
+        Some(size) => format!("Size: {size} {}.", plural(size, "byte")),
+        None => {
+            let min_size = rows.iter().map(|row| row.min_size).sum();
+            format!(
+                "Size: variable, at least {min_size} {}.",
+                plural(min_size, "byte")
+            )
+        }
+    };
+
+    format!(
+        "| Offset | Size | Name | Type | Description |\n|---|---|---|---|---|\n{}\n\n{size}",
+        rows.iter()
+            .map(|row| format!(
+                "| {} | {} | {} | {} | {} |",
+                row.offset.map_or("variable".to_string(), |o| o.to_string()),
+                row.size_text,
+                row.name,
+                row.data_type,
+                cell(&row.description)
+            ))
+            .collect::<Vec<String>>()
+            .join("\n")
+    )
+}
+
+fn plural(count: usize, word: &str) -> String {
+    if count == 1 {
+        word.to_string()
+    } else {
+        format!("{word}s")
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// A possibly multi-line description as a paragraph
+fn paragraph(description: &str) -> String {
+    description.trim().to_string()
+}
+
+/// A possibly multi-line description fitting in a table cell
+fn cell(description: &str) -> String {
+    description
+        .trim()
+        .lines()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::yaml_parser::test::integration_codes_file;
+    use crate::yaml_parser::{Checksum, CodeWidth, CodesFile};
+
+    /// Layout of the instruction or the feedback of the `id` code
+    fn frame_layout(frame_type: FrameType, id: u32, codes: &CodesFile) -> String {
+        let code = &codes.codes[&id];
+        let frame = match frame_type {
+            FrameType::Instruction => &code.instruction,
+            FrameType::Feedback => &code.feedback,
+        };
+        frame_section(frame_type, id, frame, codes)
+    }
+
+    /// Offset, size and name of the rows of the layout tables, followed by their size line
+    fn offset_rows(layout: &str) -> Vec<String> {
+        layout
+            .lines()
+            .filter_map(|line| {
+                if line.starts_with("Size: ") {
+                    return Some(line.to_string());
+                }
+                let cells: Vec<&str> = line.split(" | ").collect();
+                match cells.as_slice() {
+                    [offset, size, name, _, _] if !line.starts_with("| Offset") => {
+                        Some(format!("{offset} | {size} | {name}"))
+                    }
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fixed_size_parameters_have_offsets() {
+        assert_eq!(
+            "#### Instruction\n\nSends fixed size blobs\n\n\
+             | Offset | Size | Name | Type | Description |\n|---|---|---|---|---|\n\
+             | 0 | 1 | code | `u8` | `0x06` |\n\
+             | 1 | 16 | device_id | `u8[16]` | Device UUID |\n\
+             | 17 | 6 | calibration | `i16[3]` | Calibration coefficients |\n\
+             | 23 | 40 | key | `u8[40]` | A blob larger than serde arrays |\n\n\
+             Size: 63 bytes.\n",
+            frame_layout(FrameType::Instruction, 0x06, &integration_codes_file())
+        );
+    }
+
+    #[test]
+    fn strings_make_the_following_offsets_variable() {
+        let codes = integration_codes_file();
+
+        assert_eq!(
+            vec![
+                "| 0 | 1 | code",
+                "| 1 | variable | a_string",
+                "| variable | 8 | a_u64",
+                "| variable | 8 | a_i64",
+                "Size: variable, at least 18 bytes.",
+            ],
+            offset_rows(&frame_layout(FrameType::Instruction, 0x01, &codes))
+        );
+        assert_eq!(
+            vec![
+                "| 0 | 1 | code",
+                "| 1 | variable | labels",
+                "| variable | 6 | mac",
+                "Size: variable, at least 9 bytes.",
+            ],
+            offset_rows(&frame_layout(FrameType::Feedback, 0x06, &codes))
+        );
+    }
+
+    #[test]
+    fn arrays_count_their_length_in_the_smallest_size() {
+        let codes = integration_codes_file();
+
+        assert_eq!(
+            vec![
+                "| 0 | 1 | code",
+                "| 1 | variable | readings",
+                "| variable | variable | labels",
+                "Size: variable, at least 4 bytes.",
+            ],
+            offset_rows(&frame_layout(FrameType::Instruction, 0x05, &codes))
+        );
+        assert_eq!(
+            vec![
+                "| 0 | 1 | code",
+                "| 1 | variable | registers",
+                "| variable | 1 | status",
+                "Size: variable, at least 6 bytes.",
+            ],
+            offset_rows(&frame_layout(FrameType::Feedback, 0x05, &codes))
+        );
+    }
+
+    #[test]
+    fn optional_parameters_count_their_presence_byte() {
+        assert_eq!(
+            vec![
+                "| 0 | 1 | code",
+                "| 1 | 1 or 2 | level",
+                "| variable | variable | label",
+                "| variable | 1 or 5 | date",
+                "Size: variable, at least 4 bytes.",
+            ],
+            offset_rows(&frame_layout(
+                FrameType::Instruction,
+                0x0b,
+                &integration_codes_file()
+            ))
+        );
+    }
+
+    #[test]
+    fn custom_types_sizes_follow_their_declaration() {
+        assert_eq!(
+            vec![
+                "| 0 | 4 | date",
+                "| 4 | variable | label",
+                "| variable | 1 | mode",
+                "Size: variable, at least 6 bytes.",
+                "| 0 | 2 | year",
+                "| 2 | 1 | month",
+                "| 3 | 1 | day",
+                "Size: 4 bytes.",
+            ],
+            offset_rows(&types(&integration_codes_file()))
+        );
+    }
+
+    #[test]
+    fn code_and_checksum_are_laid_out_around_the_parameters() {
+        let mut codes = integration_codes_file();
+        codes.code_width = CodeWidth::Two;
+        codes.checksum = Some(Checksum::Crc16);
+
+        assert_eq!(
+            vec![
+                "| 0 | 2 | code",
+                "| 2 | 4 | setpoint",
+                "| 6 | 8 | gain",
+                "| 14 | 2 | checksum",
+                "Size: 16 bytes.",
+            ],
+            offset_rows(&frame_layout(FrameType::Instruction, 0x07, &codes))
+        );
+    }
+
+    #[test]
+    fn codes_pair_their_instruction_and_feedback() {
+        let mut codes = integration_codes_file();
+        let sections = code_sections(&codes);
+
+        assert!(sections.lines().any(|row| row
+            == "| `0x05` | [ArrayTest](#0x05-arraytest) | Sends lists of values | Returns a list of register values |"));
+        let section = &sections[sections.find("### 0x05 ArrayTest").unwrap()..];
+        let instruction = section
+            .find("#### Instruction\n\nSends lists of values")
+            .unwrap();
+        let feedback = section
+            .find("#### Feedback\n\nReturns a list of register values")
+            .unwrap();
+        assert!(instruction < feedback);
+        assert!(feedback < section.find("### 0x06").unwrap());
+
+        codes.codes.get_mut(&0x05).unwrap().feedback = None;
+        let sections = code_sections(&codes);
+        assert!(
+            sections.lines().any(|row| row
+                == "| `0x05` | [ArrayTest](#0x05-arraytest) | Sends lists of values | - |")
+        );
+        assert!(sections.contains("#### Feedback\n\nThis code has no feedback.\n\n### 0x06"));
+    }
+}
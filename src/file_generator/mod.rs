@@ -3,12 +3,21 @@ use std::io;
 mod cpp_header;
 mod cpp_source;
 pub mod language_models;
+mod markdown;
+mod python_template;
 mod rust_template;
+mod typescript_template;
+mod wireshark_template;
 
 use crate::yaml_parser;
 pub use cpp_header::CppHeaderGenerator;
 pub use cpp_source::CppFileGenerator;
+pub use language_models::CPlatform;
+pub use markdown::MarkdownFileGenerator;
+pub use python_template::PythonFileGenerator;
 pub use rust_template::RustFileGenerator;
+pub use typescript_template::TypeScriptFileGenerator;
+pub use wireshark_template::WiresharkFileGenerator;
 
 pub trait FileGenerator {
     fn build_file(&mut self, codes: &yaml_parser::CodesFile) -> Result<(), io::Error>;
@@ -20,6 +29,18 @@ pub trait FileGenerator {
         codes: &yaml_parser::CodesFile,
     ) -> String {
         template
+            .replace(
+                "##SETTINGS##",
+                format!("Settings\n{}", model.declare_settings(codes)).as_str(),
+            )
+            .replace(
+                "##TYPES_DECLARATIONS##",
+                format!("Types declarations\n{}", model.declare_types(codes)).as_str(),
+            )
+            .replace(
+                "##TYPES_IMPLEMENTATION##",
+                format!("Types implementation\n{}", model.implement_types(codes)).as_str(),
+            )
             .replace(
                 "##FEEDBACKS_DECLARATIONS##",
                 format!("Feedbacks declarations\n{}", model.declare_feedbacks(codes)).as_str(),
@@ -54,7 +75,7 @@ pub trait FileGenerator {
             )
             .replace(
                 "##CUSTOM_FOOTER##",
-                format!("Custom Footer\n{}", model.custom_footer()).as_str(),
+                format!("Custom Footer\n{}", model.custom_footer(codes)).as_str(),
             )
     }
 }
@@ -90,7 +111,10 @@ impl FrameType {
 
 pub trait LanguageModel {
     fn custom_includes(&self) -> String;
-    fn custom_footer(&self) -> String;
+    fn custom_footer(&self, codes: &yaml_parser::CodesFile) -> String;
+    fn declare_settings(&self, codes: &yaml_parser::CodesFile) -> String;
+    fn declare_types(&self, codes: &yaml_parser::CodesFile) -> String;
+    fn implement_types(&self, codes: &yaml_parser::CodesFile) -> String;
     fn declare_feedbacks(&self, codes: &yaml_parser::CodesFile) -> String;
     fn declare_instructions(&self, codes: &yaml_parser::CodesFile) -> String;
     fn implement_feedbacks(&self, codes: &yaml_parser::CodesFile) -> String;
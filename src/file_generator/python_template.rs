@@ -0,0 +1,30 @@
+use crate::file_generator::{FileGenerator, language_models};
+use crate::yaml_parser::{self};
+use std::fs::File;
+use std::io::{self, Write};
+
+const PYTHON_TEMPLATE: &str = include_str!("./templates/python_template.py");
+
+pub struct PythonFileGenerator {
+    writer: Box<dyn Write>,
+}
+
+impl FileGenerator for PythonFileGenerator {
+    fn build_file(&mut self, codes: &yaml_parser::CodesFile) -> Result<(), io::Error> {
+        self.writer.write_all(
+            self.process_template(
+                PYTHON_TEMPLATE,
+                &language_models::PythonLanguageModel {},
+                codes,
+            )
+            .as_bytes(),
+        )
+    }
+}
+
+impl PythonFileGenerator {
+    pub fn new(file_name: String) -> Result<Self, io::Error> {
+        let file = Box::new(File::create(file_name)?);
+        Ok(Self { writer: file })
+    }
+}
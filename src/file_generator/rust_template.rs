@@ -1,31 +1,57 @@
 use crate::file_generator::{FileGenerator, language_models};
 use crate::yaml_parser::{self};
 use std::fs::File;
-use std::io::{self, Write};
+use std::io::{Error, Write};
 
 const RUST_TEMPLATE: &str = include_str!("./templates/rust_template.rs");
+const RUST_NO_STD_TEMPLATE: &str = include_str!("./templates/rust_no_std_template.rs");
+/// Checksums, borrowed decoding and stream framing of both templates
+const RUST_COMMON_TEMPLATE: &str = include_str!("./templates/rust_common_template.rs");
 
 pub struct RustFileGenerator {
     writer: Box<dyn Write>,
+    /// Generates allocation-free code for `#![no_std]` crates
+    no_std: bool,
 }
 
 impl FileGenerator for RustFileGenerator {
-    fn build_file(&mut self, codes: &yaml_parser::CodesFile) -> Result<(), io::Error> {
+    fn build_file(&mut self, codes: &yaml_parser::CodesFile) -> Result<(), Error> {
         // Show some Rust code
-        self.writer.write_all(
-            self.process_template(RUST_TEMPLATE, &language_models::RustLanguageModel {}, codes)
-                .as_bytes(),
-        )
+        let common = format!("Common to the std and no_std outputs\n{RUST_COMMON_TEMPLATE}");
+        let output = if self.no_std {
+            self.process_template(
+                &RUST_NO_STD_TEMPLATE.replace("##RUST_COMMON##", &common),
+                &language_models::RustNoStdLanguageModel {},
+                codes,
+            )
+        } else {
+            self.process_template(
+                &RUST_TEMPLATE.replace("##RUST_COMMON##", &common),
+                &language_models::RustLanguageModel {},
+                codes,
+            )
+        };
+        self.writer.write_all(output.as_bytes())
     }
 }
 
 impl RustFileGenerator {
-    pub fn new(file_name: String) -> Result<Self, io::Error> {
+    pub fn new(file_name: String, no_std: bool) -> Result<Self, Error> {
         let file = Box::new(File::create(file_name)?);
-        Ok(Self { writer: file })
+        Ok(Self {
+            writer: file,
+            no_std,
+        })
     }
 }
 
+// Settings generated by `RustLanguageModel::declare_settings`
+#[cfg(test)]
+const BIG_ENDIAN: bool = false;
+#[cfg(test)]
+const CHECKSUM: Option<Checksum> = None;
+#[cfg(test)]
+include!("templates/rust_common_template.rs");
 #[cfg(test)]
 include!("templates/rust_template.rs");
 #[cfg(test)]
@@ -5,6 +5,21 @@
 #include <stdlib.h>
 #include <string.h>
 
+// Specific frame parsing errors, other parsing failures return -1
+#define FRAME_ERROR_UNKNOWN_ENUM_VALUE (-4)
+#define FRAME_ERROR_CHECKSUM_MISMATCH (-5)
+#define FRAME_ERROR_INVALID_FRAMING (-6)
+#define FRAME_ERROR_TOO_LONG (-7)
+#define FRAME_ERROR_NO_HANDLER (-8)
+
+// Returned by the stream decoders while the frame isn't complete
+#define FRAME_STREAM_INCOMPLETE 1
+
+// Returned by the instruction handlers not answering with their feedback
+#define FRAME_NO_FEEDBACK 2
+
+// ##TYPES_DECLARATIONS##
+
 // ##FEEDBACKS_DECLARATIONS##
 
 // ##INSTRUCTIONS_DECLARATIONS##
@@ -4,14 +4,21 @@
 #include <stdlib.h>
 #include <string.h>
 // ##CUSTOM_INCLUDES##
-#include <zephyr/zephyr.h>
+
+// ##SETTINGS##
 
 typedef struct {
   char *head;
   size_t len;
   bool valid;
+  int error; // Specific FRAME_ERROR_* code of an invalid slice, 0 otherwise
 } buffer_slice;
 
+inline void invalidate_buffer_slice(buffer_slice *slice, int error) {
+  slice->valid = false;
+  slice->error = error;
+}
+
 inline buffer_slice move_buffer_slice(buffer_slice in, size_t by) {
   if (in.valid) {
     in.valid = (in.len >= by);
@@ -33,6 +40,18 @@ inline int strnlen(char *input, size_t len) {
   return -1;
 }
 
+// Writes the `size` lower bytes of `value` in the wire byte order
+inline buffer_slice write_uint_to_buffer(buffer_slice in, uint64_t value,
+                                         size_t size) {
+  if (in.valid && in.len >= size) {
+    for (size_t i = 0; i < size; i++) {
+      size_t byte = WIRE_BIG_ENDIAN ? (size - 1 - i) : i;
+      in.head[i] = (char)((value >> (8 * byte)) & 0xff);
+    }
+  }
+  return move_buffer_slice(in, size);
+}
+
 inline buffer_slice write_bool_to_buffer(buffer_slice in, bool value) {
   if (in.valid && in.len >= sizeof(value)) {
     in.head[0] = value ? 1 : 0;
@@ -55,45 +74,39 @@ inline buffer_slice write_u8_to_buffer(buffer_slice in, uint8_t value) {
 }
 
 inline buffer_slice write_i16_to_buffer(buffer_slice in, int16_t value) {
-  if (in.valid && in.len >= sizeof(value)) {
-    memcpy(in.head, &value, sizeof(value));
-  }
-  return move_buffer_slice(in, sizeof(value));
+  return write_uint_to_buffer(in, (uint16_t)value, sizeof(value));
 }
 
 inline buffer_slice write_u16_to_buffer(buffer_slice in, uint16_t value) {
-  if (in.valid && in.len >= sizeof(value)) {
-    memcpy(in.head, &value, sizeof(value));
-  }
-  return move_buffer_slice(in, sizeof(value));
+  return write_uint_to_buffer(in, value, sizeof(value));
 }
 
 inline buffer_slice write_i32_to_buffer(buffer_slice in, int32_t value) {
-  if (in.valid && in.len >= sizeof(value)) {
-    memcpy(in.head, &value, sizeof(value));
-  }
-  return move_buffer_slice(in, sizeof(value));
+  return write_uint_to_buffer(in, (uint32_t)value, sizeof(value));
 }
 
 inline buffer_slice write_u32_to_buffer(buffer_slice in, uint32_t value) {
-  if (in.valid && in.len >= sizeof(value)) {
-    memcpy(in.head, &value, sizeof(value));
-  }
-  return move_buffer_slice(in, sizeof(value));
+  return write_uint_to_buffer(in, value, sizeof(value));
 }
 
 inline buffer_slice write_i64_to_buffer(buffer_slice in, int64_t value) {
-  if (in.valid && in.len >= sizeof(value)) {
-    memcpy(in.head, &value, sizeof(value));
-  }
-  return move_buffer_slice(in, sizeof(value));
+  return write_uint_to_buffer(in, (uint64_t)value, sizeof(value));
 }
 
 inline buffer_slice write_u64_to_buffer(buffer_slice in, uint64_t value) {
-  if (in.valid && in.len >= sizeof(value)) {
-    memcpy(in.head, &value, sizeof(value));
-  }
-  return move_buffer_slice(in, sizeof(value));
+  return write_uint_to_buffer(in, value, sizeof(value));
+}
+
+inline buffer_slice write_f32_to_buffer(buffer_slice in, float value) {
+  uint32_t bits;
+  memcpy(&bits, &value, sizeof(bits));
+  return write_u32_to_buffer(in, bits);
+}
+
+inline buffer_slice write_f64_to_buffer(buffer_slice in, double value) {
+  uint64_t bits;
+  memcpy(&bits, &value, sizeof(bits));
+  return write_u64_to_buffer(in, bits);
 }
 
 inline buffer_slice write_String_to_buffer(buffer_slice in, const char *value) {
@@ -107,6 +120,46 @@ inline buffer_slice write_String_to_buffer(buffer_slice in, const char *value) {
   return in;
 }
 
+// Writes a string stored in the `capacity` bytes of `value`, which must hold its
+// NUL terminator
+inline buffer_slice write_bounded_String_to_buffer(buffer_slice in,
+                                                   const char *value,
+                                                   size_t capacity) {
+  if (memchr(value, 0, capacity) == NULL) {
+    in.valid = false;
+    return in;
+  }
+  return write_String_to_buffer(in, value);
+}
+
+inline buffer_slice write_length_to_buffer(buffer_slice in, size_t length,
+                                           size_t length_size) {
+  if (length_size < sizeof(length) && (length >> (8 * length_size)) != 0) {
+    in.valid = false;
+    return in;
+  }
+  return write_uint_to_buffer(in, length, length_size);
+}
+
+// Reads a `size` bytes unsigned integer in the wire byte order
+inline uint64_t parse_uint_from_buffer(buffer_slice *slice, size_t size) {
+  uint64_t value = 0;
+  if (slice->valid && slice->len >= size) {
+    for (size_t i = 0; i < size; i++) {
+      size_t byte = WIRE_BIG_ENDIAN ? (size - 1 - i) : i;
+      value |= ((uint64_t)(uint8_t)slice->head[i]) << (8 * byte);
+    }
+  }
+  *slice = move_buffer_slice(*slice, size);
+  return value;
+}
+
+// Reads the code starting a frame of at least FRAME_CODE_SIZE bytes
+inline uint32_t parse_frame_code(char *buffer) {
+  buffer_slice slice = {.head = buffer, .len = FRAME_CODE_SIZE, .valid = true};
+  return (uint32_t)parse_uint_from_buffer(&slice, FRAME_CODE_SIZE);
+}
+
 inline bool parse_bool_from_buffer(buffer_slice *slice) {
   bool value;
   if (slice->valid && slice->len >= sizeof(value)) {
@@ -134,66 +187,72 @@ inline int8_t parse_i8_from_buffer(buffer_slice *slice) {
 }
 
 inline uint16_t parse_u16_from_buffer(buffer_slice *slice) {
-  uint16_t value;
-  if (slice->valid && slice->len >= sizeof(value)) {
-    memcpy(&value, slice->head, sizeof(value));
-  }
-  *slice = move_buffer_slice(*slice, sizeof(value));
-  return value;
+  return (uint16_t)parse_uint_from_buffer(slice, sizeof(uint16_t));
 }
 
 inline int16_t parse_i16_from_buffer(buffer_slice *slice) {
-  int16_t value;
-  if (slice->valid && slice->len >= sizeof(value)) {
-    memcpy(&value, slice->head, sizeof(value));
-  }
-  *slice = move_buffer_slice(*slice, sizeof(value));
-  return value;
+  return (int16_t)parse_uint_from_buffer(slice, sizeof(int16_t));
 }
 
 inline uint32_t parse_u32_from_buffer(buffer_slice *slice) {
-  uint32_t value;
-  if (slice->valid && slice->len >= sizeof(value)) {
-    memcpy(&value, slice->head, sizeof(value));
-  }
-  *slice = move_buffer_slice(*slice, sizeof(value));
-  return value;
+  return (uint32_t)parse_uint_from_buffer(slice, sizeof(uint32_t));
 }
 
 inline int32_t parse_i32_from_buffer(buffer_slice *slice) {
-  int32_t value;
-  if (slice->valid && slice->len >= sizeof(value)) {
-    memcpy(&value, slice->head, sizeof(value));
-  }
-  *slice = move_buffer_slice(*slice, sizeof(value));
-  return value;
+  return (int32_t)parse_uint_from_buffer(slice, sizeof(int32_t));
 }
 
 inline uint64_t parse_u64_from_buffer(buffer_slice *slice) {
-  uint64_t value;
-  if (slice->valid && slice->len >= sizeof(value)) {
-    memcpy(&value, slice->head, sizeof(value));
-  }
-  *slice = move_buffer_slice(*slice, sizeof(value));
-  return value;
+  return (uint64_t)parse_uint_from_buffer(slice, sizeof(uint64_t));
 }
 
 inline int64_t parse_i64_from_buffer(buffer_slice *slice) {
-  int64_t value;
-  if (slice->valid && slice->len >= sizeof(value)) {
-    memcpy(&value, slice->head, sizeof(value));
-  }
-  *slice = move_buffer_slice(*slice, sizeof(value));
+  return (int64_t)parse_uint_from_buffer(slice, sizeof(int64_t));
+}
+
+inline float parse_f32_from_buffer(buffer_slice *slice) {
+  uint32_t bits = parse_u32_from_buffer(slice);
+  float value;
+  memcpy(&value, &bits, sizeof(value));
   return value;
 }
 
+inline double parse_f64_from_buffer(buffer_slice *slice) {
+  uint64_t bits = parse_u64_from_buffer(slice);
+  double value;
+  memcpy(&value, &bits, sizeof(value));
+  return value;
+}
+
+// Copies a NUL-terminated string into the `capacity` bytes of `value`
+inline void parse_bounded_String_from_buffer(buffer_slice *slice, char *value,
+                                             size_t capacity) {
+  if (slice->valid) {
+    int len = strnlen(slice->head, slice->len);
+    if (len < 0) {
+      slice->valid = false;
+    } else if ((size_t)len >= capacity) {
+      invalidate_buffer_slice(slice, FRAME_ERROR_TOO_LONG);
+    } else {
+      memcpy(value, slice->head, len + 1);
+      *slice = move_buffer_slice(*slice, len + 1);
+    }
+  }
+}
+
+inline size_t parse_length_from_buffer(buffer_slice *slice,
+                                      size_t length_size) {
+  return (size_t)parse_uint_from_buffer(slice, length_size);
+}
+
+#if !FRAME_NO_ALLOC
 inline char *parse_String_from_buffer(buffer_slice *slice) {
   char *value = NULL;
   if (slice->valid) {
     int len = strnlen(slice->head, slice->len);
     if (len > 0) {
       len++;
-      value = (char *)k_malloc((size_t)len);
+      value = (char *)FRAME_MALLOC((size_t)len);
       memcpy(value, slice->head, len);
       *slice = move_buffer_slice(*slice, len);
     } else {
@@ -203,6 +262,290 @@ inline char *parse_String_from_buffer(buffer_slice *slice) {
   return value;
 }
 
+inline void *allocate_array_from_buffer(buffer_slice *slice, size_t count,
+                                        size_t item_wire_size,
+                                        size_t item_size) {
+  void *value = NULL;
+  if (slice->valid && count > 0) {
+    // Reject counts the remaining buffer can't hold before allocating
+    if (count > slice->len / item_wire_size) {
+      slice->valid = false;
+    } else {
+      value = FRAME_MALLOC(count * item_size);
+      if (value != NULL) {
+        memset(value, 0, count * item_size);
+      } else {
+        slice->valid = false;
+      }
+    }
+  }
+  return value;
+}
+#endif
+
+inline uint32_t compute_no_checksum(const char *buffer, size_t len) {
+  (void)buffer;
+  (void)len;
+  return 0;
+}
+
+// CRC-8 with polynomial 0x07
+inline uint32_t compute_crc8(const char *buffer, size_t len) {
+  uint8_t crc = 0;
+  for (size_t i = 0; i < len; i++) {
+    crc ^= (uint8_t)buffer[i];
+    for (int bit = 0; bit < 8; bit++) {
+      crc = (crc & 0x80) ? (uint8_t)((crc << 1) ^ 0x07) : (uint8_t)(crc << 1);
+    }
+  }
+  return crc;
+}
+
+// CRC-16/CCITT-FALSE
+inline uint32_t compute_crc16(const char *buffer, size_t len) {
+  uint16_t crc = 0xffff;
+  for (size_t i = 0; i < len; i++) {
+    crc ^= (uint16_t)((uint8_t)buffer[i] << 8);
+    for (int bit = 0; bit < 8; bit++) {
+      crc = (crc & 0x8000) ? (uint16_t)((crc << 1) ^ 0x1021)
+                           : (uint16_t)(crc << 1);
+    }
+  }
+  return crc;
+}
+
+// CRC-32 as used by Ethernet and zlib
+inline uint32_t compute_crc32(const char *buffer, size_t len) {
+  uint32_t crc = 0xffffffff;
+  for (size_t i = 0; i < len; i++) {
+    crc ^= (uint8_t)buffer[i];
+    for (int bit = 0; bit < 8; bit++) {
+      crc = (crc & 1) ? ((crc >> 1) ^ 0xedb88320) : (crc >> 1);
+    }
+  }
+  return ~crc;
+}
+
+// Appends the checksum of the frame written from `frame` up to the slice head
+inline buffer_slice write_checksum_to_buffer(buffer_slice slice,
+                                             const char *frame) {
+  if (FRAME_CHECKSUM_SIZE > 0 && slice.valid) {
+    uint32_t checksum = FRAME_CHECKSUM(frame, (size_t)(slice.head - frame));
+    slice = write_uint_to_buffer(slice, checksum, FRAME_CHECKSUM_SIZE);
+  }
+  return slice;
+}
+
+// Checks the checksum ending the frame, and removes it from `len`
+inline int verify_frame_checksum(char *buffer, int *len) {
+  if (*len <= FRAME_CHECKSUM_SIZE) {
+    return -1;
+  }
+  *len -= FRAME_CHECKSUM_SIZE;
+  if (FRAME_CHECKSUM_SIZE > 0) {
+    buffer_slice slice = {.head = buffer + *len,
+                          .len = FRAME_CHECKSUM_SIZE,
+                          .valid = true};
+    uint32_t received = (uint32_t)parse_uint_from_buffer(&slice, FRAME_CHECKSUM_SIZE);
+    if (received != FRAME_CHECKSUM(buffer, (size_t)*len)) {
+      return FRAME_ERROR_CHECKSUM_MISMATCH;
+    }
+  }
+  return 0;
+}
+
+#define STREAM_FRAMING_NONE 0
+#define STREAM_FRAMING_COBS 1
+#define STREAM_FRAMING_SLIP 2
+#define STREAM_FRAMING_SYNC_LENGTH 3
+
+#if STREAM_FRAMING != STREAM_FRAMING_NONE
+#define SLIP_END 0xc0
+#define SLIP_ESC 0xdb
+#define SLIP_ESC_END 0xdc
+#define SLIP_ESC_ESC 0xdd
+#define SYNC_BYTE 0xaa
+#define SYNC_HEADER_SIZE 3
+
+void stream_decoder_init(stream_decoder *decoder, char *buffer,
+                         size_t capacity) {
+  memset(decoder, 0, sizeof(*decoder));
+  decoder->buffer = buffer;
+  decoder->capacity = capacity;
+}
+
+inline void stream_decoder_reset(stream_decoder *decoder) {
+  decoder->len = 0;
+  decoder->expected = 0;
+  decoder->header_len = 0;
+  decoder->escaped = false;
+  decoder->invalid = false;
+}
+
+inline void stream_decoder_store(stream_decoder *decoder, char byte) {
+  if (decoder->len < decoder->capacity) {
+    decoder->buffer[decoder->len++] = byte;
+  } else {
+    decoder->invalid = true;
+  }
+}
+
+// Decodes in place the COBS frame received in the decoder buffer, and returns
+// its length
+inline int stream_decoder_cobs_decode(stream_decoder *decoder) {
+  size_t read = 0;
+  size_t write = 0;
+  while (read < decoder->len) {
+    uint8_t code = (uint8_t)decoder->buffer[read++];
+    if (code == 0 || read + code - 1 > decoder->len) {
+      return FRAME_ERROR_INVALID_FRAMING;
+    }
+    memmove(decoder->buffer + write, decoder->buffer + read, code - 1);
+    write += code - 1;
+    read += code - 1;
+    if (code != 0xff && read < decoder->len) {
+      decoder->buffer[write++] = 0;
+    }
+  }
+  return (int)write;
+}
+
+// Feeds one received byte. Returns the length of the frame it completes, now at
+// the start of the decoder buffer, 0 while incomplete or a FRAME_ERROR_* code
+int stream_decoder_push(stream_decoder *decoder, char byte) {
+  int result = 0;
+  switch (STREAM_FRAMING) {
+  case STREAM_FRAMING_COBS:
+    if (byte != 0) {
+      stream_decoder_store(decoder, byte);
+      return 0;
+    }
+    // The zero byte ends the frame
+    if (decoder->invalid) {
+      result = FRAME_ERROR_INVALID_FRAMING;
+    } else if (decoder->len > 0) {
+      result = stream_decoder_cobs_decode(decoder);
+    }
+    stream_decoder_reset(decoder);
+    return result;
+
+  case STREAM_FRAMING_SLIP:
+    if (decoder->escaped) {
+      decoder->escaped = false;
+      if ((uint8_t)byte == SLIP_ESC_END) {
+        stream_decoder_store(decoder, (char)SLIP_END);
+      } else if ((uint8_t)byte == SLIP_ESC_ESC) {
+        stream_decoder_store(decoder, (char)SLIP_ESC);
+      } else {
+        decoder->invalid = true;
+      }
+    } else if ((uint8_t)byte == SLIP_ESC) {
+      decoder->escaped = true;
+    } else if ((uint8_t)byte == SLIP_END) {
+      result = decoder->invalid ? FRAME_ERROR_INVALID_FRAMING : (int)decoder->len;
+      stream_decoder_reset(decoder);
+    } else {
+      stream_decoder_store(decoder, byte);
+    }
+    return result;
+
+  default:
+    if (decoder->header_len == 0) {
+      decoder->header_len = ((uint8_t)byte == SYNC_BYTE) ? 1 : 0;
+    } else if (decoder->header_len < SYNC_HEADER_SIZE) {
+      decoder->header[decoder->header_len - 1] = byte;
+      if (++decoder->header_len == SYNC_HEADER_SIZE) {
+        buffer_slice slice = {.head = decoder->header,
+                              .len = sizeof(decoder->header),
+                              .valid = true};
+        decoder->expected = (size_t)parse_uint_from_buffer(&slice, 2);
+        if (decoder->expected == 0 ||
+            decoder->expected > decoder->capacity) {
+          stream_decoder_reset(decoder);
+          result = FRAME_ERROR_INVALID_FRAMING;
+        }
+      }
+    } else {
+      stream_decoder_store(decoder, byte);
+      if (decoder->len == decoder->expected) {
+        result = (int)decoder->len;
+        stream_decoder_reset(decoder);
+      }
+    }
+    return result;
+  }
+}
+
+int encode_stream_frame(const char *frame, int len, char *stream,
+                        int *stream_len) {
+  if ((frame == NULL) || (stream == NULL) || (stream_len == NULL) || len < 0)
+    return -1;
+
+  buffer_slice slice = {.head = stream, .len = (size_t)*stream_len, .valid = true};
+  switch (STREAM_FRAMING) {
+  case STREAM_FRAMING_COBS: {
+    // Each block starts with the offset of the next zero byte
+    char *code = slice.head;
+    uint8_t block_len = 0;
+    slice = move_buffer_slice(slice, 1);
+    for (int i = 0; i < len && slice.valid; i++) {
+      if (frame[i] != 0) {
+        slice = write_u8_to_buffer(slice, (uint8_t)frame[i]);
+        block_len++;
+      }
+      if (frame[i] == 0 || (block_len == 254 && i + 1 < len)) {
+        if (slice.valid) {
+          *code = (char)(block_len + 1);
+          code = slice.head;
+        }
+        block_len = 0;
+        slice = move_buffer_slice(slice, 1);
+      }
+    }
+    if (slice.valid) {
+      *code = (char)(block_len + 1);
+    }
+    slice = write_u8_to_buffer(slice, 0);
+    break;
+  }
+
+  case STREAM_FRAMING_SLIP:
+    slice = write_u8_to_buffer(slice, SLIP_END);
+    for (int i = 0; i < len; i++) {
+      if ((uint8_t)frame[i] == SLIP_END) {
+        slice = write_u8_to_buffer(slice, SLIP_ESC);
+        slice = write_u8_to_buffer(slice, SLIP_ESC_END);
+      } else if ((uint8_t)frame[i] == SLIP_ESC) {
+        slice = write_u8_to_buffer(slice, SLIP_ESC);
+        slice = write_u8_to_buffer(slice, SLIP_ESC_ESC);
+      } else {
+        slice = write_u8_to_buffer(slice, (uint8_t)frame[i]);
+      }
+    }
+    slice = write_u8_to_buffer(slice, SLIP_END);
+    break;
+
+  default:
+    if (len > UINT16_MAX)
+      return -1;
+    slice = write_u8_to_buffer(slice, SYNC_BYTE);
+    slice = write_u16_to_buffer(slice, (uint16_t)len);
+    if (slice.valid && slice.len >= (size_t)len) {
+      memcpy(slice.head, frame, len);
+    }
+    slice = move_buffer_slice(slice, len);
+    break;
+  }
+
+  if (!slice.valid)
+    return -1;
+  *stream_len = (int)(slice.head - stream);
+  return 0;
+}
+#endif
+
+// ##TYPES_IMPLEMENTATION##
+
 // ##INSTRUCTIONS_IMPLEMENTATION##
 
 // ##FEEDBACKS_IMPLEMENTATION##
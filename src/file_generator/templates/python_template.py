@@ -0,0 +1,192 @@
+# This file is autogenerated, please do not edit manually
+from __future__ import annotations
+
+import struct
+from dataclasses import dataclass
+from enum import IntEnum
+from typing import Callable, ClassVar, Dict, List, Optional, Type, TypeVar
+
+# ##CUSTOM_INCLUDES##
+
+# ##SETTINGS##
+
+T = TypeVar("T")
+
+
+class FrameError(ValueError):
+    """Raised when a received frame can't be parsed"""
+
+
+def _crc8(frame: bytes) -> int:
+    crc = 0
+    for byte in frame:
+        crc ^= byte
+        for _ in range(8):
+            crc = ((crc << 1) ^ 0x07 if crc & 0x80 else crc << 1) & 0xFF
+    return crc
+
+
+def _crc16(frame: bytes) -> int:
+    crc = 0xFFFF
+    for byte in frame:
+        crc ^= byte << 8
+        for _ in range(8):
+            crc = ((crc << 1) ^ 0x1021 if crc & 0x8000 else crc << 1) & 0xFFFF
+    return crc
+
+
+def _crc32(frame: bytes) -> int:
+    crc = 0xFFFFFFFF
+    for byte in frame:
+        crc ^= byte
+        for _ in range(8):
+            crc = (crc >> 1) ^ 0xEDB88320 if crc & 1 else crc >> 1
+    return crc ^ 0xFFFFFFFF
+
+
+# Checksum name: (struct format, computation)
+_CHECKSUMS: Dict[str, tuple] = {
+    "crc8": ("B", _crc8),
+    "crc16": ("H", _crc16),
+    "crc32": ("I", _crc32),
+}
+
+
+def _pack(fmt: str, value) -> bytes:
+    return struct.pack(BYTE_ORDER + fmt, value)
+
+
+def _pack_string(value: str) -> bytes:
+    return value.encode("utf-8") + b"\0"
+
+
+def _pack_array(length_fmt: str, items: List[bytes]) -> bytes:
+    """Writes the items count followed by the already encoded items"""
+    return _pack(length_fmt, len(items)) + b"".join(items)
+
+
+def _pack_fixed_array(count: int, items: List[bytes]) -> bytes:
+    if len(items) != count:
+        raise ValueError(f"Expected {count} items, got {len(items)}")
+    return b"".join(items)
+
+
+def _pack_optional(value: Optional[T], write: Callable[[T], bytes]) -> bytes:
+    """Writes the presence flag of an optional value, followed by the value when present"""
+    if value is None:
+        return _pack("?", False)
+    return _pack("?", True) + write(value)
+
+
+def _append_checksum(frame: bytes) -> bytes:
+    """Appends the configured checksum to an encoded frame"""
+    if CHECKSUM is None:
+        return frame
+    fmt, compute = _CHECKSUMS[CHECKSUM]
+    return frame + _pack(fmt, compute(frame))
+
+
+def _verify_checksum(frame: bytes) -> bytes:
+    """Checks the configured checksum of a received frame, and returns the frame without it"""
+    if CHECKSUM is None:
+        if len(frame) == 0:
+            raise FrameError("Frame too short")
+        return frame
+
+    fmt, compute = _CHECKSUMS[CHECKSUM]
+    size = struct.calcsize(BYTE_ORDER + fmt)
+    if len(frame) <= size:
+        raise FrameError("Frame too short")
+    frame, received = frame[:-size], frame[-size:]
+    if _pack(fmt, compute(frame)) != received:
+        raise FrameError("Checksum mismatch")
+    return frame
+
+
+class _Reader:
+    """Parses the values of a received frame in order"""
+
+    def __init__(self, buffer: bytes, offset: int = 0):
+        self.buffer = buffer
+        self.offset = offset
+
+    def unpack(self, fmt: str):
+        size = struct.calcsize(BYTE_ORDER + fmt)
+        if self.offset + size > len(self.buffer):
+            raise FrameError("Frame too short")
+        (value,) = struct.unpack_from(BYTE_ORDER + fmt, self.buffer, self.offset)
+        self.offset += size
+        return value
+
+    def string(self) -> str:
+        end = self.buffer.find(b"\0", self.offset)
+        if end < 0:
+            raise FrameError("No string termination found")
+        try:
+            value = self.buffer[self.offset : end].decode("utf-8")
+        except UnicodeDecodeError as error:
+            raise FrameError("Failed to decode string") from error
+        self.offset = end + 1
+        return value
+
+    def enum(self, enum_type: Type[IntEnum], fmt: str):
+        value = self.unpack(fmt)
+        try:
+            return enum_type(value)
+        except ValueError as error:
+            raise FrameError(f"Unknown {enum_type.__name__} value {value}") from error
+
+    def array(self, length_fmt: str, read: Callable[[], T]) -> List[T]:
+        return self.fixed_array(self.unpack(length_fmt), read)
+
+    def fixed_array(self, count: int, read: Callable[[], T]) -> List[T]:
+        return [read() for _ in range(count)]
+
+    def optional(self, read: Callable[[], T]) -> Optional[T]:
+        """Parses the presence flag of an optional value, followed by the value when present"""
+        return read() if self.unpack("?") else None
+
+
+class _Frame:
+    """Instruction or feedback frame, identified by its code"""
+
+    CODE: ClassVar[int]
+
+    def to_bytes(self) -> bytes:
+        return _append_checksum(_pack(CODE_FORMAT, self.CODE) + self._write())
+
+    @classmethod
+    def from_bytes(cls, frame: bytes):
+        reader = _Reader(_verify_checksum(frame))
+        code = reader.unpack(CODE_FORMAT)
+        if code != cls.CODE:
+            raise FrameError(f"Unexpected code {code}")
+        return cls._read(reader)
+
+    def _write(self) -> bytes:
+        raise NotImplementedError
+
+    @classmethod
+    def _read(cls, reader: _Reader):
+        raise NotImplementedError
+
+
+def _parse_frame(frames: Dict[int, Type[_Frame]], frame: bytes) -> _Frame:
+    reader = _Reader(_verify_checksum(frame))
+    code = reader.unpack(CODE_FORMAT)
+    if code not in frames:
+        raise FrameError(f"Unknown code {code}")
+    return frames[code]._read(reader)
+
+
+# ##TYPES_DECLARATIONS##
+
+# ##TYPES_IMPLEMENTATION##
+
+# ##FEEDBACKS_DECLARATIONS##
+
+# ##INSTRUCTIONS_DECLARATIONS##
+
+# ##FEEDBACKS_IMPLEMENTATION##
+
+# ##INSTRUCTIONS_IMPLEMENTATION##
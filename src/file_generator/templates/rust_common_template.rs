@@ -0,0 +1,398 @@
+#[derive(PartialEq, Debug, Clone, Copy)]
+enum Checksum {
+    Crc8,
+    Crc16,
+    Crc32,
+}
+
+impl Checksum {
+    /// Computes the checksum of `bytes`
+    fn compute(&self, bytes: &[u8]) -> u32 {
+        self.finish(self.update(self.initial(), bytes))
+    }
+
+    fn initial(&self) -> u32 {
+        match self {
+            Self::Crc8 => 0,
+            Self::Crc16 => 0xffff,
+            Self::Crc32 => 0xffffffff,
+        }
+    }
+
+    /// Updates a checksum being computed with the following `bytes`
+    fn update(&self, crc: u32, bytes: &[u8]) -> u32 {
+        match self {
+            Self::Crc8 => bytes.iter().fold(crc as u8, |crc, byte| {
+                (0..8).fold(crc ^ byte, |crc, _| {
+                    if crc & 0x80 != 0 {
+                        (crc << 1) ^ 0x07
+                    } else {
+                        crc << 1
+                    }
+                })
+            }) as u32,
+            Self::Crc16 => bytes.iter().fold(crc as u16, |crc, byte| {
+                (0..8).fold(crc ^ ((*byte as u16) << 8), |crc, _| {
+                    if crc & 0x8000 != 0 {
+                        (crc << 1) ^ 0x1021
+                    } else {
+                        crc << 1
+                    }
+                })
+            }) as u32,
+            Self::Crc32 => bytes.iter().fold(crc, |crc, byte| {
+                (0..8).fold(crc ^ *byte as u32, |crc, _| {
+                    if crc & 1 != 0 {
+                        (crc >> 1) ^ 0xedb88320
+                    } else {
+                        crc >> 1
+                    }
+                })
+            }),
+        }
+    }
+
+    fn finish(&self, crc: u32) -> u32 {
+        match self {
+            Self::Crc32 => !crc,
+            _ => crc,
+        }
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            Self::Crc8 => 1,
+            Self::Crc16 => 2,
+            Self::Crc32 => 4,
+        }
+    }
+}
+
+/// Checks the configured checksum of a received frame, and returns the frame without it
+fn verify_checksum(frame: &[u8]) -> Result<&[u8], TypesEnumError> {
+    let checksum_size = CHECKSUM.map_or(0, |checksum| checksum.size());
+    if frame.len() <= checksum_size {
+        return Err(TypesEnumError::TooShort);
+    }
+
+    let (frame, received) = frame.split_at(frame.len() - checksum_size);
+    match CHECKSUM {
+        Some(checksum)
+            if Reader::new(received).uint(checksum_size)? != checksum.compute(frame) as u64 =>
+        {
+            Err(TypesEnumError::ChecksumMismatch)
+        }
+        _ => Ok(frame),
+    }
+}
+
+/// Zero-copy reading of a received frame, the decoded values borrowing its bytes
+#[derive(Clone)]
+pub struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    /// Bytes not read yet
+    pub fn remaining(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Reads the next `size` bytes
+    pub fn take(&mut self, size: usize) -> Result<&'a [u8], TypesEnumError> {
+        if self.bytes.len() < size {
+            return Err(TypesEnumError::TooShort);
+        }
+        let (bytes, rest) = self.bytes.split_at(size);
+        self.bytes = rest;
+        Ok(bytes)
+    }
+
+    /// Reads an unsigned integer sent on `size` bytes
+    fn uint(&mut self, size: usize) -> Result<u64, TypesEnumError> {
+        let mut bytes = [0u8; 8];
+        bytes[..size].copy_from_slice(self.take(size)?);
+        if BIG_ENDIAN {
+            bytes[..size].reverse();
+        }
+        Ok(u64::from_le_bytes(bytes))
+    }
+}
+
+/// Value decoded from a frame borrowed for `'a`
+pub trait Decode<'a>: Sized {
+    fn decode(reader: &mut Reader<'a>) -> Result<Self, TypesEnumError>;
+}
+
+macro_rules! impl_decode_for_numbers {
+    ($($type:ty),*) => {
+        $(
+            impl<'a> Decode<'a> for $type {
+                fn decode(reader: &mut Reader<'a>) -> Result<Self, TypesEnumError> {
+                    let mut bytes = [0u8; core::mem::size_of::<$type>()];
+                    bytes.copy_from_slice(reader.take(core::mem::size_of::<$type>())?);
+                    if BIG_ENDIAN {
+                        bytes.reverse();
+                    }
+                    Ok(<$type>::from_le_bytes(bytes))
+                }
+            }
+        )*
+    };
+}
+
+impl_decode_for_numbers!(u8, i8, u16, i16, u32, i32, u64, i64, f32, f64);
+
+impl<'a> Decode<'a> for bool {
+    fn decode(reader: &mut Reader<'a>) -> Result<Self, TypesEnumError> {
+        Ok(reader.take(1)?[0] != 0)
+    }
+}
+
+/// NUL-terminated UTF-8 string
+impl<'a> Decode<'a> for &'a str {
+    fn decode(reader: &mut Reader<'a>) -> Result<Self, TypesEnumError> {
+        let bytes = reader.remaining();
+        let index = bytes
+            .iter()
+            .position(|byte| *byte == 0)
+            .ok_or(TypesEnumError::NoStringTerminationFound)?;
+        let string = core::str::from_utf8(&bytes[..index])
+            .map_err(|_| TypesEnumError::FailedToDecodeString)?;
+        reader.take(index + 1)?;
+        Ok(string)
+    }
+}
+
+/// Fixed-length array, sent without length
+impl<'a, T: Decode<'a>, const N: usize> Decode<'a> for [T; N] {
+    fn decode(reader: &mut Reader<'a>) -> Result<Self, TypesEnumError> {
+        let mut error = None;
+        let items: [Option<T>; N] = core::array::from_fn(|_| match error {
+            None => T::decode(reader).map_err(|e| error = Some(e)).ok(),
+            Some(_) => None,
+        });
+        match error {
+            Some(error) => Err(error),
+            None => Ok(items.map(Option::unwrap)),
+        }
+    }
+}
+
+/// Optional value, preceded by its presence flag
+impl<'a, T: Decode<'a>> Decode<'a> for Option<T> {
+    fn decode(reader: &mut Reader<'a>) -> Result<Self, TypesEnumError> {
+        if bool::decode(reader)? {
+            Ok(Some(T::decode(reader)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Variable-length array, prefixed on the wire by its items count on `COUNT_SIZE` bytes.
+///
+/// The items to send are borrowed from a slice, while the received items are checked when
+/// the frame is decoded, then decoded again while iterating.
+pub enum Array<'a, T, const COUNT_SIZE: usize> {
+    Items(&'a [T]),
+    Encoded { bytes: &'a [u8], count: usize },
+}
+
+impl<'a, T, const COUNT_SIZE: usize> Array<'a, T, COUNT_SIZE> {
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Items(items) => items.len(),
+            Self::Encoded { count, .. } => *count,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Items count, failing when it doesn't fit in `COUNT_SIZE` bytes
+    fn count(&self) -> Result<u64, TypesEnumError> {
+        let count = self.len() as u64;
+        if COUNT_SIZE < 8 && count >> (8 * COUNT_SIZE) != 0 {
+            return Err(TypesEnumError::ArrayTooLong);
+        }
+        Ok(count)
+    }
+}
+
+impl<'a, T: Decode<'a> + Clone, const COUNT_SIZE: usize> Array<'a, T, COUNT_SIZE> {
+    pub fn iter(&self) -> ArrayIter<'a, T> {
+        match *self {
+            Self::Items(items) => ArrayIter::Items(items.iter()),
+            Self::Encoded { bytes, count } => ArrayIter::Encoded {
+                reader: Reader::new(bytes),
+                count,
+            },
+        }
+    }
+}
+
+impl<'a, T, const COUNT_SIZE: usize> From<&'a [T]> for Array<'a, T, COUNT_SIZE> {
+    fn from(items: &'a [T]) -> Self {
+        Self::Items(items)
+    }
+}
+
+impl<T, const COUNT_SIZE: usize> Clone for Array<'_, T, COUNT_SIZE> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, const COUNT_SIZE: usize> Copy for Array<'_, T, COUNT_SIZE> {}
+
+impl<'a, T: Decode<'a> + Clone + PartialEq, const COUNT_SIZE: usize> PartialEq
+    for Array<'a, T, COUNT_SIZE>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<'a, T: Decode<'a> + Clone + Eq, const COUNT_SIZE: usize> Eq for Array<'a, T, COUNT_SIZE> {}
+
+impl<'a, T: Decode<'a> + Clone + fmt::Debug, const COUNT_SIZE: usize> fmt::Debug
+    for Array<'a, T, COUNT_SIZE>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T: Decode<'a>, const COUNT_SIZE: usize> Decode<'a> for Array<'a, T, COUNT_SIZE> {
+    fn decode(reader: &mut Reader<'a>) -> Result<Self, TypesEnumError> {
+        let count = reader.uint(COUNT_SIZE)? as usize;
+        let items = reader.remaining();
+        for _ in 0..count {
+            T::decode(reader)?;
+        }
+        Ok(Self::Encoded {
+            bytes: &items[..items.len() - reader.remaining().len()],
+            count,
+        })
+    }
+}
+
+/// Items of an `Array`
+pub enum ArrayIter<'a, T> {
+    Items(core::slice::Iter<'a, T>),
+    Encoded { reader: Reader<'a>, count: usize },
+}
+
+impl<'a, T: Decode<'a> + Clone> Iterator for ArrayIter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        match self {
+            Self::Items(items) => items.next().cloned(),
+            Self::Encoded { reader, count } => {
+                if *count == 0 {
+                    return None;
+                }
+                *count -= 1;
+                // The items were checked when the frame was decoded
+                T::decode(reader).ok()
+            }
+        }
+    }
+}
+
+/// Delimitation of the frames sent on a byte stream
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Framing {
+    /// Consistent Overhead Byte Stuffing, frames end with a zero byte
+    Cobs,
+    /// Serial Line Internet Protocol, frames are enclosed by 0xC0 bytes
+    Slip,
+    /// Sync byte 0xAA followed by the frame length on two bytes
+    SyncLength,
+}
+
+const SLIP_END: u8 = 0xc0;
+const SLIP_ESC: u8 = 0xdb;
+const SLIP_ESC_END: u8 = 0xdc;
+const SLIP_ESC_ESC: u8 = 0xdd;
+const SYNC_BYTE: u8 = 0xaa;
+const SYNC_HEADER_SIZE: usize = 3;
+
+impl Framing {
+    /// Wraps an encoded frame to send it on a byte stream, passing the delimited bytes to
+    /// `put` in order
+    fn write_stream(
+        &self,
+        frame: &[u8],
+        mut put: impl FnMut(&[u8]) -> Result<(), TypesEnumError>,
+    ) -> Result<(), TypesEnumError> {
+        match self {
+            Self::Cobs => {
+                let mut blocks = frame.split(|byte| *byte == 0).peekable();
+                while let Some(block) = blocks.next() {
+                    let mut chunks = block.chunks(254).peekable();
+                    if chunks.peek().is_none() {
+                        put(&[1])?;
+                    }
+                    while let Some(chunk) = chunks.next() {
+                        put(&[chunk.len() as u8 + 1])?;
+                        put(chunk)?;
+                        // A full chunk doesn't imply a zero, an empty block carries it
+                        if chunk.len() == 254 && chunks.peek().is_none() && blocks.peek().is_some() {
+                            put(&[1])?;
+                        }
+                    }
+                }
+                put(&[0])
+            }
+            Self::Slip => {
+                put(&[SLIP_END])?;
+                for byte in frame {
+                    match *byte {
+                        SLIP_END => put(&[SLIP_ESC, SLIP_ESC_END])?,
+                        SLIP_ESC => put(&[SLIP_ESC, SLIP_ESC_ESC])?,
+                        byte => put(&[byte])?,
+                    }
+                }
+                put(&[SLIP_END])
+            }
+            Self::SyncLength => {
+                let mut length = u16::try_from(frame.len())
+                    .map_err(|_| TypesEnumError::FrameTooLong)?
+                    .to_le_bytes();
+                if BIG_ENDIAN {
+                    length.reverse();
+                }
+                put(&[SYNC_BYTE])?;
+                put(&length)?;
+                put(frame)
+            }
+        }
+    }
+
+    /// Decodes in place a COBS frame without its ending zero byte, and returns its length
+    fn cobs_decode(frame: &mut [u8]) -> Result<usize, TypesEnumError> {
+        let (mut read, mut write) = (0, 0);
+        while read < frame.len() {
+            let code = frame[read] as usize;
+            if code == 0 || read + code > frame.len() {
+                return Err(TypesEnumError::InvalidFraming);
+            }
+            frame.copy_within(read + 1..read + code, write);
+            write += code - 1;
+            read += code;
+            if code != 0xff && read < frame.len() {
+                frame[write] = 0;
+                write += 1;
+            }
+        }
+        Ok(write)
+    }
+}
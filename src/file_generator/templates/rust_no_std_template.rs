@@ -0,0 +1,270 @@
+// This file is autogenerated, please do not edit manually
+// Only depends on `core` and the `heapless` crate, to be used by `#![no_std]` crates
+use core::{convert::TryFrom, fmt};
+
+// ##SETTINGS##
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum TypesEnumError {
+    TooShort,
+    NoStringTerminationFound,
+    FailedToDecodeString,
+    UnknownCode,
+    UnknownEnumValue,
+    ChecksumMismatch,
+    InvalidFraming,
+    /// The caller-provided buffer can't hold the encoded frame
+    BufferTooSmall,
+    /// The items count of an array doesn't fit its length type
+    ArrayTooLong,
+    /// The frame doesn't fit the length field of the stream framing
+    FrameTooLong,
+}
+
+// ##RUST_COMMON##
+
+/// Encodes the values of a frame in order into a caller-provided buffer
+pub struct Writer<'b> {
+    buffer: &'b mut [u8],
+    len: usize,
+}
+
+impl<'b> Writer<'b> {
+    pub fn new(buffer: &'b mut [u8]) -> Self {
+        Self { buffer, len: 0 }
+    }
+
+    /// Number of bytes written
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn put(&mut self, bytes: &[u8]) -> Result<(), TypesEnumError> {
+        let end = self.len + bytes.len();
+        if end > self.buffer.len() {
+            return Err(TypesEnumError::BufferTooSmall);
+        }
+        self.buffer[self.len..end].copy_from_slice(bytes);
+        self.len = end;
+        Ok(())
+    }
+
+    /// Encodes the `size` lower bytes of `value` in the wire byte order
+    fn uint(&mut self, value: u64, size: usize) -> Result<(), TypesEnumError> {
+        let mut bytes = value.to_le_bytes();
+        if BIG_ENDIAN {
+            bytes[..size].reverse();
+        }
+        self.put(&bytes[..size])
+    }
+
+    /// Appends the configured checksum of the written bytes
+    fn append_checksum(&mut self) -> Result<(), TypesEnumError> {
+        if let Some(checksum) = CHECKSUM {
+            let value = checksum.compute(&self.buffer[..self.len]);
+            self.uint(value as u64, checksum.size())?;
+        }
+        Ok(())
+    }
+}
+
+/// Value written on the wire
+pub trait Encode {
+    fn encode(&self, writer: &mut Writer) -> Result<(), TypesEnumError>;
+}
+
+macro_rules! impl_encode_for_numbers {
+    ($($type:ty),*) => {
+        $(
+            impl Encode for $type {
+                fn encode(&self, writer: &mut Writer) -> Result<(), TypesEnumError> {
+                    let mut bytes = self.to_le_bytes();
+                    if BIG_ENDIAN {
+                        bytes.reverse();
+                    }
+                    writer.put(&bytes)
+                }
+            }
+        )*
+    };
+}
+
+impl_encode_for_numbers!(u8, i8, u16, i16, u32, i32, u64, i64, f32, f64);
+
+impl Encode for bool {
+    fn encode(&self, writer: &mut Writer) -> Result<(), TypesEnumError> {
+        writer.put(&[*self as u8])
+    }
+}
+
+/// NUL-terminated UTF-8 string
+impl Encode for &str {
+    fn encode(&self, writer: &mut Writer) -> Result<(), TypesEnumError> {
+        writer.put(self.as_bytes())?;
+        writer.put(&[0])
+    }
+}
+
+/// Fixed-length array, sent without length
+impl<T: Encode, const N: usize> Encode for [T; N] {
+    fn encode(&self, writer: &mut Writer) -> Result<(), TypesEnumError> {
+        self.iter().try_for_each(|item| item.encode(writer))
+    }
+}
+
+/// Optional value, preceded by its presence flag
+impl<T: Encode> Encode for Option<T> {
+    fn encode(&self, writer: &mut Writer) -> Result<(), TypesEnumError> {
+        match self {
+            Some(value) => {
+                true.encode(writer)?;
+                value.encode(writer)
+            }
+            None => false.encode(writer),
+        }
+    }
+}
+
+/// Fails with `ArrayTooLong` if the items count doesn't fit in `COUNT_SIZE` bytes
+impl<T: Encode, const COUNT_SIZE: usize> Encode for Array<'_, T, COUNT_SIZE> {
+    fn encode(&self, writer: &mut Writer) -> Result<(), TypesEnumError> {
+        writer.uint(self.count()?, COUNT_SIZE)?;
+        match self {
+            Self::Items(items) => items.iter().try_for_each(|item| item.encode(writer)),
+            Self::Encoded { bytes, .. } => writer.put(bytes),
+        }
+    }
+}
+
+impl Framing {
+    /// Wraps an encoded frame into `stream` to send it on a byte stream, and returns the
+    /// number of bytes written
+    pub fn encode(&self, frame: &[u8], stream: &mut [u8]) -> Result<usize, TypesEnumError> {
+        let mut writer = Writer::new(stream);
+        self.write_stream(frame, |bytes| writer.put(bytes))?;
+        Ok(writer.len())
+    }
+}
+
+/// Incremental decoder extracting the frames received on a byte stream, storing up to
+/// `N` bytes of the frame being received. Longer frames are reported as invalid.
+pub struct StreamDecoder<const N: usize> {
+    framing: Framing,
+    buffer: heapless::Vec<u8, N>,
+    escaped: bool,
+    invalid: bool,
+    /// The buffer holds the last returned frame
+    complete: bool,
+}
+
+impl<const N: usize> StreamDecoder<N> {
+    pub const fn new(framing: Framing) -> Self {
+        Self {
+            framing,
+            buffer: heapless::Vec::new(),
+            escaped: false,
+            invalid: false,
+            complete: false,
+        }
+    }
+
+    /// Feeds a received byte, and returns the frame it completes
+    pub fn push(&mut self, byte: u8) -> Option<Result<&[u8], TypesEnumError>> {
+        if core::mem::take(&mut self.complete) {
+            self.buffer.clear();
+        }
+        match self.framing {
+            Framing::Cobs => self.push_cobs(byte),
+            Framing::Slip => self.push_slip(byte),
+            Framing::SyncLength => self.push_sync_length(byte),
+        }
+    }
+
+    fn store(&mut self, byte: u8) {
+        if self.buffer.push(byte).is_err() {
+            self.invalid = true;
+        }
+    }
+
+    /// Ends the frame held in `buffer[start..]`, unless it is empty
+    fn complete(&mut self, start: usize) -> Option<Result<&[u8], TypesEnumError>> {
+        if core::mem::take(&mut self.invalid) {
+            self.buffer.clear();
+            return Some(Err(TypesEnumError::InvalidFraming));
+        }
+        if self.buffer.len() == start {
+            return None;
+        }
+        self.complete = true;
+        Some(Ok(&self.buffer[start..]))
+    }
+
+    fn push_cobs(&mut self, byte: u8) -> Option<Result<&[u8], TypesEnumError>> {
+        if byte != 0 {
+            self.store(byte);
+            return None;
+        }
+        if !self.invalid {
+            match Framing::cobs_decode(&mut self.buffer) {
+                Ok(len) => self.buffer.truncate(len),
+                Err(_) => self.invalid = true,
+            }
+        }
+        self.complete(0)
+    }
+
+    fn push_slip(&mut self, byte: u8) -> Option<Result<&[u8], TypesEnumError>> {
+        match (self.escaped, byte) {
+            (false, SLIP_END) => return self.complete(0),
+            (false, SLIP_ESC) => self.escaped = true,
+            (false, byte) => self.store(byte),
+            (true, byte) => {
+                self.escaped = false;
+                match byte {
+                    SLIP_ESC_END => self.store(SLIP_END),
+                    SLIP_ESC_ESC => self.store(SLIP_ESC),
+                    _ => self.invalid = true,
+                }
+            }
+        }
+        None
+    }
+
+    fn push_sync_length(&mut self, byte: u8) -> Option<Result<&[u8], TypesEnumError>> {
+        // Drop the noise received before the sync byte
+        if self.buffer.is_empty() && byte != SYNC_BYTE {
+            return None;
+        }
+        self.store(byte);
+        if self.buffer.len() < SYNC_HEADER_SIZE {
+            return None;
+        }
+
+        let length = u16::decode(&mut Reader::new(&self.buffer[1..SYNC_HEADER_SIZE])).ok()?;
+        if length == 0 || SYNC_HEADER_SIZE + length as usize > N {
+            // Not a real header or a frame too long, look for the next sync byte
+            self.buffer.clear();
+            return Some(Err(TypesEnumError::InvalidFraming));
+        }
+        if self.buffer.len() < SYNC_HEADER_SIZE + length as usize {
+            return None;
+        }
+        self.complete(SYNC_HEADER_SIZE)
+    }
+}
+
+// ##TYPES_DECLARATIONS##
+
+// ##TYPES_IMPLEMENTATION##
+
+// ##FEEDBACKS_DECLARATIONS##
+
+// ##INSTRUCTIONS_DECLARATIONS##
+
+// ##FEEDBACKS_IMPLEMENTATION##
+
+// ##INSTRUCTIONS_IMPLEMENTATION##
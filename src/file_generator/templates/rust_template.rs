@@ -1,6 +1,8 @@
 // This file is autogenerated, please do not edit manually
 use serde_derive::Serialize;
-use std::{convert::TryInto, fmt};
+use std::{convert::TryInto, fmt, io};
+
+// ##SETTINGS##
 
 #[derive(PartialEq, Debug)]
 enum TypesEnum {
@@ -12,6 +14,8 @@ enum TypesEnum {
     I32(i32),
     U64(u64),
     I64(i64),
+    F32(f32),
+    F64(f64),
     Bool(bool),
     Str(String),
 }
@@ -22,15 +26,33 @@ pub enum TypesEnumError {
     NoStringTerminationFound,
     FailedToDecodeString,
     UnknownCode,
+    UnknownEnumValue,
+    ChecksumMismatch,
+    InvalidFraming,
+    BufferTooSmall,
+    /// The items count of an array doesn't fit its length type
+    ArrayTooLong,
+    /// The frame doesn't fit the length field of the stream framing
+    FrameTooLong,
 }
 
+// ##RUST_COMMON##
+
 impl TypesEnum {
+    /// Converts little endian bytes to the wire byte order, and back
+    fn wire_bytes<const N: usize>(mut bytes: [u8; N]) -> [u8; N] {
+        if BIG_ENDIAN {
+            bytes.reverse();
+        }
+        bytes
+    }
+
     pub fn size(&self) -> usize {
         match self {
             Self::Bool(_) | Self::U8(_) | Self::I8(_) => 1,
             Self::U16(_) | Self::I16(_) => 2,
-            Self::U32(_) | Self::I32(_) => 4,
-            Self::U64(_) | Self::I64(_) => 8,
+            Self::U32(_) | Self::I32(_) | Self::F32(_) => 4,
+            Self::U64(_) | Self::I64(_) | Self::F64(_) => 8,
             Self::Str(a) => a.len() + 1,
         }
     }
@@ -62,7 +84,7 @@ impl TypesEnum {
             Err(TypesEnumError::TooShort)
         } else {
             Ok((
-                u16::from_le_bytes(buffer[0..2].try_into().unwrap()),
+                u16::from_le_bytes(Self::wire_bytes(buffer[0..2].try_into().unwrap())),
                 &buffer[2..],
             ))
         }
@@ -73,7 +95,7 @@ impl TypesEnum {
             Err(TypesEnumError::TooShort)
         } else {
             Ok((
-                i16::from_le_bytes(buffer[0..2].try_into().unwrap()),
+                i16::from_le_bytes(Self::wire_bytes(buffer[0..2].try_into().unwrap())),
                 &buffer[2..],
             ))
         }
@@ -84,7 +106,7 @@ impl TypesEnum {
             Err(TypesEnumError::TooShort)
         } else {
             Ok((
-                u32::from_le_bytes(buffer[0..4].try_into().unwrap()),
+                u32::from_le_bytes(Self::wire_bytes(buffer[0..4].try_into().unwrap())),
                 &buffer[4..],
             ))
         }
@@ -95,7 +117,7 @@ impl TypesEnum {
             Err(TypesEnumError::TooShort)
         } else {
             Ok((
-                i32::from_le_bytes(buffer[0..4].try_into().unwrap()),
+                i32::from_le_bytes(Self::wire_bytes(buffer[0..4].try_into().unwrap())),
                 &buffer[4..],
             ))
         }
@@ -106,7 +128,7 @@ impl TypesEnum {
             Err(TypesEnumError::TooShort)
         } else {
             Ok((
-                u64::from_le_bytes(buffer[0..8].try_into().unwrap()),
+                u64::from_le_bytes(Self::wire_bytes(buffer[0..8].try_into().unwrap())),
                 &buffer[8..],
             ))
         }
@@ -117,7 +139,29 @@ impl TypesEnum {
             Err(TypesEnumError::TooShort)
         } else {
             Ok((
-                i64::from_le_bytes(buffer[0..8].try_into().unwrap()),
+                i64::from_le_bytes(Self::wire_bytes(buffer[0..8].try_into().unwrap())),
+                &buffer[8..],
+            ))
+        }
+    }
+
+    pub fn f32_from_buffer(buffer: &[u8]) -> Result<(f32, &[u8]), TypesEnumError> {
+        if buffer.len() < Self::F32(0.0).size() {
+            Err(TypesEnumError::TooShort)
+        } else {
+            Ok((
+                f32::from_le_bytes(Self::wire_bytes(buffer[0..4].try_into().unwrap())),
+                &buffer[4..],
+            ))
+        }
+    }
+
+    pub fn f64_from_buffer(buffer: &[u8]) -> Result<(f64, &[u8]), TypesEnumError> {
+        if buffer.len() < Self::F64(0.0).size() {
+            Err(TypesEnumError::TooShort)
+        } else {
+            Ok((
+                f64::from_le_bytes(Self::wire_bytes(buffer[0..8].try_into().unwrap())),
                 &buffer[8..],
             ))
         }
@@ -145,23 +189,314 @@ impl TypesEnum {
             }
         }
     }
+
+    fn length_from_buffer(
+        buffer: &[u8],
+        length_size: usize,
+    ) -> Result<(usize, &[u8]), TypesEnumError> {
+        if buffer.len() < length_size {
+            Err(TypesEnumError::TooShort)
+        } else {
+            let mut bytes = [0u8; 8];
+            bytes[..length_size].copy_from_slice(&buffer[..length_size]);
+            if BIG_ENDIAN {
+                bytes[..length_size].reverse();
+            }
+            let length = u64::from_le_bytes(bytes) as usize;
+            Ok((length, &buffer[length_size..]))
+        }
+    }
+
+    fn items_from_buffer<T>(
+        mut buffer: &[u8],
+        count: usize,
+        item_from_buffer: impl Fn(&[u8]) -> Result<(T, &[u8]), TypesEnumError>,
+    ) -> Result<(Vec<T>, &[u8]), TypesEnumError> {
+        // Don't trust the received count for the allocation, each item takes at least a byte
+        let mut items = Vec::with_capacity(count.min(buffer.len()));
+        for _ in 0..count {
+            let (item, rest) = item_from_buffer(buffer)?;
+            items.push(item);
+            buffer = rest;
+        }
+        Ok((items, buffer))
+    }
+
+    pub fn array_from_buffer<T>(
+        buffer: &[u8],
+        length_size: usize,
+        item_from_buffer: impl Fn(&[u8]) -> Result<(T, &[u8]), TypesEnumError>,
+    ) -> Result<(Vec<T>, &[u8]), TypesEnumError> {
+        let (length, buffer) = Self::length_from_buffer(buffer, length_size)?;
+        Self::items_from_buffer(buffer, length, item_from_buffer)
+    }
+
+    pub fn fixed_array_from_buffer<T, const N: usize>(
+        buffer: &[u8],
+        item_from_buffer: impl Fn(&[u8]) -> Result<(T, &[u8]), TypesEnumError>,
+    ) -> Result<([T; N], &[u8]), TypesEnumError> {
+        let (items, buffer) = Self::items_from_buffer(buffer, N, item_from_buffer)?;
+        match items.try_into() {
+            Ok(items) => Ok((items, buffer)),
+            Err(_) => Err(TypesEnumError::TooShort),
+        }
+    }
+
+    /// Writes the items count on `length_size` bytes followed by the already encoded items
+    pub fn array_to_buffer(
+        length_size: usize,
+        items: Vec<Vec<u8>>,
+    ) -> Result<Vec<u8>, TypesEnumError> {
+        let length = items.len() as u64;
+        if length_size < 8 && length >> (8 * length_size) != 0 {
+            return Err(TypesEnumError::ArrayTooLong);
+        }
+        let mut value = length.to_le_bytes()[..length_size].to_vec();
+        if BIG_ENDIAN {
+            value.reverse();
+        }
+        value.extend(Self::fixed_array_to_buffer(items));
+        Ok(value)
+    }
+
+    pub fn fixed_array_to_buffer(items: Vec<Vec<u8>>) -> Vec<u8> {
+        items.concat()
+    }
+
+    /// Parses the presence flag of an optional value, followed by the value when present
+    pub fn optional_from_buffer<T>(
+        buffer: &[u8],
+        value_from_buffer: impl Fn(&[u8]) -> Result<(T, &[u8]), TypesEnumError>,
+    ) -> Result<(Option<T>, &[u8]), TypesEnumError> {
+        match Self::bool_from_buffer(buffer)? {
+            (true, buffer) => {
+                let (value, buffer) = value_from_buffer(buffer)?;
+                Ok((Some(value), buffer))
+            }
+            (false, buffer) => Ok((None, buffer)),
+        }
+    }
+
+    /// Writes the presence flag of an optional value, followed by the already encoded value
+    pub fn optional_to_buffer(value: Option<Vec<u8>>) -> Vec<u8> {
+        match value {
+            Some(value) => [Self::Bool(true).write_to_buffer(), value].concat(),
+            None => Self::Bool(false).write_to_buffer(),
+        }
+    }
+
+    /// Appends the configured checksum to an encoded frame
+    pub fn append_checksum(mut frame: Vec<u8>) -> Vec<u8> {
+        if let Some(checksum) = CHECKSUM {
+            let crc = checksum.compute(&frame);
+            frame.extend(
+                match checksum {
+                    Checksum::Crc8 => Self::U8(crc as u8),
+                    Checksum::Crc16 => Self::U16(crc as u16),
+                    Checksum::Crc32 => Self::U32(crc),
+                }
+                .write_to_buffer(),
+            );
+        }
+        frame
+    }
+
+    /// Serializes fixed arrays as sequences, serde only implements arrays up to 32 items
+    pub fn serialize_fixed_array<S: serde::Serializer, T: serde::Serialize, const N: usize>(
+        value: &[T; N],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(value)
+    }
+
+    /// Serializes optional fixed arrays, serde only implements arrays up to 32 items
+    pub fn serialize_optional_fixed_array<
+        S: serde::Serializer,
+        T: serde::Serialize,
+        const N: usize,
+    >(
+        value: &Option<[T; N]>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => serializer.serialize_some(value.as_slice()),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl Framing {
+    /// Wraps an encoded frame to send it on a byte stream
+    pub fn encode(&self, frame: &[u8]) -> Result<Vec<u8>, TypesEnumError> {
+        let mut stream = Vec::with_capacity(frame.len() + frame.len() / 254 + SYNC_HEADER_SIZE);
+        self.write_stream(frame, |bytes| {
+            stream.extend_from_slice(bytes);
+            Ok(())
+        })?;
+        Ok(stream)
+    }
+}
+
+/// Incremental decoder extracting frames from the chunks received on a byte stream, up to
+/// `max_frame_length` bytes long. Longer frames are reported as invalid.
+pub struct StreamDecoder {
+    framing: Framing,
+    max_frame_length: usize,
+    buffer: Vec<u8>,
+    escaped: bool,
+    invalid: bool,
+}
+
+impl StreamDecoder {
+    pub fn new(framing: Framing, max_frame_length: usize) -> Self {
+        Self {
+            framing,
+            max_frame_length,
+            buffer: Vec::new(),
+            escaped: false,
+            invalid: false,
+        }
+    }
+
+    /// Feeds the received `chunk`, and returns the frames it completes
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<Result<Vec<u8>, TypesEnumError>> {
+        match self.framing {
+            Framing::Cobs => chunk
+                .iter()
+                .filter_map(|byte| self.push_cobs(*byte))
+                .collect(),
+            Framing::Slip => chunk
+                .iter()
+                .filter_map(|byte| self.push_slip(*byte))
+                .collect(),
+            Framing::SyncLength => {
+                self.buffer.extend_from_slice(chunk);
+                let mut frames = Vec::new();
+                while let Some(frame) = self.pop_sync_length() {
+                    frames.push(frame);
+                }
+                frames
+            }
+        }
+    }
+
+    /// Stores a received byte, unless the frame gets longer than `limit` bytes
+    fn store(&mut self, byte: u8, limit: usize) {
+        if self.buffer.len() < limit {
+            self.buffer.push(byte);
+        } else {
+            self.invalid = true;
+        }
+    }
+
+    fn push_cobs(&mut self, byte: u8) -> Option<Result<Vec<u8>, TypesEnumError>> {
+        if byte != 0 {
+            // Each block of up to 254 bytes adds a code byte
+            let limit = self.max_frame_length + self.max_frame_length / 254 + 1;
+            self.store(byte, limit);
+            return None;
+        }
+        let mut frame = std::mem::take(&mut self.buffer);
+        if std::mem::take(&mut self.invalid) {
+            return Some(Err(TypesEnumError::InvalidFraming));
+        }
+        match Framing::cobs_decode(&mut frame) {
+            Ok(0) => None,
+            Ok(len) if len > self.max_frame_length => Some(Err(TypesEnumError::InvalidFraming)),
+            Ok(len) => {
+                frame.truncate(len);
+                Some(Ok(frame))
+            }
+            Err(error) => Some(Err(error)),
+        }
+    }
+
+    fn push_slip(&mut self, byte: u8) -> Option<Result<Vec<u8>, TypesEnumError>> {
+        match (self.escaped, byte) {
+            (false, SLIP_END) => {
+                let frame = std::mem::take(&mut self.buffer);
+                let invalid = std::mem::take(&mut self.invalid);
+                if invalid {
+                    Some(Err(TypesEnumError::InvalidFraming))
+                } else if frame.is_empty() {
+                    None
+                } else {
+                    Some(Ok(frame))
+                }
+            }
+            (false, SLIP_ESC) => {
+                self.escaped = true;
+                None
+            }
+            (false, byte) => {
+                self.store(byte, self.max_frame_length);
+                None
+            }
+            (true, byte) => {
+                self.escaped = false;
+                match byte {
+                    SLIP_ESC_END => self.store(SLIP_END, self.max_frame_length),
+                    SLIP_ESC_ESC => self.store(SLIP_ESC, self.max_frame_length),
+                    _ => self.invalid = true,
+                }
+                None
+            }
+        }
+    }
+
+    fn pop_sync_length(&mut self) -> Option<Result<Vec<u8>, TypesEnumError>> {
+        // Drop the noise received before the sync byte
+        let start = self
+            .buffer
+            .iter()
+            .position(|byte| *byte == SYNC_BYTE)
+            .unwrap_or(self.buffer.len());
+        self.buffer.drain(..start);
+
+        let (length, _) = TypesEnum::u16_from_buffer(self.buffer.get(1..)?).ok()?;
+        let length = length as usize;
+        if length == 0 || length > self.max_frame_length {
+            // Not a real header or a frame too long, look for the next sync byte
+            self.buffer.remove(0);
+            return Some(Err(TypesEnumError::InvalidFraming));
+        }
+        if self.buffer.len() < SYNC_HEADER_SIZE + length {
+            return None;
+        }
+        let frame = self.buffer[SYNC_HEADER_SIZE..SYNC_HEADER_SIZE + length].to_vec();
+        self.buffer.drain(..SYNC_HEADER_SIZE + length);
+        Some(Ok(frame))
+    }
 }
 
 trait WriteToBuffer {
     fn write_to_buffer(&self) -> Vec<u8>;
 }
 
+/// Writing of the values whose encoding can fail, such as structures holding arrays
+trait TryWriteToBuffer {
+    fn try_write_to_buffer(&self) -> Result<Vec<u8>, TypesEnumError>;
+}
+
+impl<T: WriteToBuffer> TryWriteToBuffer for T {
+    fn try_write_to_buffer(&self) -> Result<Vec<u8>, TypesEnumError> {
+        Ok(self.write_to_buffer())
+    }
+}
+
 impl WriteToBuffer for TypesEnum {
     fn write_to_buffer(&self) -> Vec<u8> {
         match self {
             Self::U8(v) => v.to_le_bytes().to_vec(),
             Self::I8(v) => v.to_le_bytes().to_vec(),
-            Self::U16(v) => v.to_le_bytes().to_vec(),
-            Self::I16(v) => v.to_le_bytes().to_vec(),
-            Self::U32(v) => v.to_le_bytes().to_vec(),
-            Self::I32(v) => v.to_le_bytes().to_vec(),
-            Self::U64(v) => v.to_le_bytes().to_vec(),
-            Self::I64(v) => v.to_le_bytes().to_vec(),
+            Self::U16(v) => Self::wire_bytes(v.to_le_bytes()).to_vec(),
+            Self::I16(v) => Self::wire_bytes(v.to_le_bytes()).to_vec(),
+            Self::U32(v) => Self::wire_bytes(v.to_le_bytes()).to_vec(),
+            Self::I32(v) => Self::wire_bytes(v.to_le_bytes()).to_vec(),
+            Self::U64(v) => Self::wire_bytes(v.to_le_bytes()).to_vec(),
+            Self::I64(v) => Self::wire_bytes(v.to_le_bytes()).to_vec(),
+            Self::F32(v) => Self::wire_bytes(v.to_le_bytes()).to_vec(),
+            Self::F64(v) => Self::wire_bytes(v.to_le_bytes()).to_vec(),
             Self::Bool(true) => vec![1],
             Self::Bool(false) => vec![0],
             Self::Str(v) => {
@@ -173,6 +508,139 @@ impl WriteToBuffer for TypesEnum {
     }
 }
 
+/// Encoding of a frame into any output without intermediate buffers, computing the
+/// configured checksum on the fly
+pub struct Writer<W: io::Write> {
+    output: W,
+    written: usize,
+    crc: u32,
+}
+
+impl<W: io::Write> Writer<W> {
+    pub fn new(output: W) -> Self {
+        Self {
+            output,
+            written: 0,
+            crc: CHECKSUM.map_or(0, |checksum| checksum.initial()),
+        }
+    }
+
+    /// Writes encoded bytes
+    pub fn put(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.output.write_all(bytes)?;
+        self.written += bytes.len();
+        if let Some(checksum) = CHECKSUM {
+            self.crc = checksum.update(self.crc, bytes);
+        }
+        Ok(())
+    }
+
+    /// Writes an unsigned integer on `size` bytes
+    fn uint(&mut self, value: u64, size: usize) -> io::Result<()> {
+        let bytes = TypesEnum::wire_bytes(value.to_le_bytes());
+        if BIG_ENDIAN {
+            self.put(&bytes[bytes.len() - size..])
+        } else {
+            self.put(&bytes[..size])
+        }
+    }
+
+    /// Appends the configured checksum, and returns the length of the frame
+    pub fn finish(mut self) -> io::Result<usize> {
+        if let Some(checksum) = CHECKSUM {
+            let crc = checksum.finish(self.crc);
+            self.uint(crc as u64, checksum.size())?;
+        }
+        Ok(self.written)
+    }
+}
+
+/// Value written on the wire
+pub trait Encode {
+    fn encode<W: io::Write>(&self, writer: &mut Writer<W>) -> io::Result<()>;
+}
+
+macro_rules! impl_encode_for_numbers {
+    ($($type:ty),*) => {
+        $(
+            impl Encode for $type {
+                fn encode<W: io::Write>(&self, writer: &mut Writer<W>) -> io::Result<()> {
+                    writer.put(&TypesEnum::wire_bytes(self.to_le_bytes()))
+                }
+            }
+        )*
+    };
+}
+
+impl_encode_for_numbers!(u8, i8, u16, i16, u32, i32, u64, i64, f32, f64);
+
+impl Encode for bool {
+    fn encode<W: io::Write>(&self, writer: &mut Writer<W>) -> io::Result<()> {
+        writer.put(&[*self as u8])
+    }
+}
+
+impl Encode for str {
+    fn encode<W: io::Write>(&self, writer: &mut Writer<W>) -> io::Result<()> {
+        writer.put(self.as_bytes())?;
+        writer.put(&[0])
+    }
+}
+
+impl Encode for String {
+    fn encode<W: io::Write>(&self, writer: &mut Writer<W>) -> io::Result<()> {
+        self.as_str().encode(writer)
+    }
+}
+
+impl<T: Encode + ?Sized> Encode for &T {
+    fn encode<W: io::Write>(&self, writer: &mut Writer<W>) -> io::Result<()> {
+        (**self).encode(writer)
+    }
+}
+
+impl<T: Encode, const N: usize> Encode for [T; N] {
+    fn encode<W: io::Write>(&self, writer: &mut Writer<W>) -> io::Result<()> {
+        self.iter().try_for_each(|item| item.encode(writer))
+    }
+}
+
+impl<T: Encode> Encode for Option<T> {
+    fn encode<W: io::Write>(&self, writer: &mut Writer<W>) -> io::Result<()> {
+        match self {
+            Some(value) => {
+                true.encode(writer)?;
+                value.encode(writer)
+            }
+            None => false.encode(writer),
+        }
+    }
+}
+
+/// Fails with an `InvalidInput` error if the items count doesn't fit in `COUNT_SIZE` bytes
+impl<T: Encode, const COUNT_SIZE: usize> Encode for Array<'_, T, COUNT_SIZE> {
+    fn encode<W: io::Write>(&self, writer: &mut Writer<W>) -> io::Result<()> {
+        let count = self.count().map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "Array of {} items doesn't fit a {COUNT_SIZE} bytes length",
+                    self.len()
+                ),
+            )
+        })?;
+        writer.uint(count, COUNT_SIZE)?;
+        match self {
+            Self::Items(items) => items.iter().try_for_each(|item| item.encode(writer)),
+            Self::Encoded { bytes, .. } => writer.put(bytes),
+        }
+    }
+}
+
+// ##TYPES_DECLARATIONS##
+
+// ##TYPES_IMPLEMENTATION##
+
 // ##FEEDBACKS_DECLARATIONS##
 
 // ##INSTRUCTIONS_DECLARATIONS##
@@ -1,7 +1,11 @@
 #[cfg(test)]
 mod test {
 
-    use crate::file_generator::rust_template::{TypesEnum, WriteToBuffer};
+    use crate::file_generator::rust_template::{
+        Array, Checksum, Decode, Encode, Framing, Reader, StreamDecoder, TypesEnum,
+        TryWriteToBuffer, TypesEnumError, WriteToBuffer, Writer, verify_checksum,
+    };
+    use serde_derive::Serialize;
     #[test]
     fn write_u8_to_buffer() {
         let value = TypesEnum::U8(9u8);
@@ -73,6 +77,22 @@ mod test {
         );
     }
 
+    #[test]
+    fn write_f32_to_buffer() {
+        let value = TypesEnum::F32(1.5);
+        let converted = value.write_to_buffer();
+
+        assert_eq!(converted, &[0u8, 0, 0xc0, 0x3f]);
+    }
+
+    #[test]
+    fn write_f64_to_buffer() {
+        let value = TypesEnum::F64(-2.0);
+        let converted = value.write_to_buffer();
+
+        assert_eq!(converted, &[0u8, 0, 0, 0, 0, 0, 0, 0xc0]);
+    }
+
     #[test]
     fn write_bool_to_buffer() {
         let value = TypesEnum::Bool(true);
@@ -174,6 +194,33 @@ mod test {
         assert_eq!(-99i64, result);
     }
 
+    #[test]
+    fn parse_f32_from_buffer() {
+        let test_value = [0u8, 0, 0xc0, 0x3f, 0];
+        let (result, rest) =
+            TypesEnum::f32_from_buffer(&test_value).expect("Should success parsing");
+
+        assert_eq!(1, rest.len());
+        assert_eq!(1.5f32, result);
+    }
+
+    #[test]
+    fn parse_f64_from_buffer() {
+        let test_value = [0u8, 0, 0, 0, 0, 0, 0, 0xc0];
+        let (result, rest) =
+            TypesEnum::f64_from_buffer(&test_value).expect("Should success parsing");
+
+        assert_eq!(0, rest.len());
+        assert_eq!(-2.0f64, result);
+    }
+
+    #[test]
+    fn parse_f32_from_too_short_buffer() {
+        let test_value = [0u8, 0, 0xc0];
+
+        assert!(TypesEnum::f32_from_buffer(&test_value).is_err());
+    }
+
     #[test]
     fn parse_bool_from_buffer() {
         let test_value = [1, 0xff, 0xff, 0xff, 0];
@@ -195,4 +242,441 @@ mod test {
         assert_eq!(2, rest.len());
         assert_eq!(result, expected_string);
     }
+
+    #[test]
+    fn write_array_to_buffer() {
+        let items = vec![TypesEnum::U16(259).write_to_buffer(), vec![4, 0]];
+        let converted = TypesEnum::array_to_buffer(2, items).expect("Should fit its length");
+
+        assert_eq!(converted, &[2u8, 0, 3, 1, 4, 0]);
+    }
+
+    #[test]
+    fn try_write_infallible_value_to_buffer() {
+        let converted = TypesEnum::U16(259).try_write_to_buffer();
+
+        assert!(matches!(converted.as_deref(), Ok([3u8, 1])));
+    }
+
+    #[test]
+    fn write_array_too_long_for_length() {
+        let items = vec![vec![0u8]; 256];
+
+        assert!(matches!(
+            TypesEnum::array_to_buffer(1, items),
+            Err(TypesEnumError::ArrayTooLong)
+        ));
+    }
+
+    #[test]
+    fn write_fixed_array_to_buffer() {
+        let items = vec![vec![1u8], vec![2u8], vec![3u8]];
+        let converted = TypesEnum::fixed_array_to_buffer(items);
+
+        assert_eq!(converted, &[1u8, 2, 3]);
+    }
+
+    #[test]
+    fn parse_array_from_buffer() {
+        let test_value = [2u8, 0, 0, 0, 3, 1, 4, 0, 9];
+        let (result, rest) = TypesEnum::array_from_buffer(&test_value, 4, TypesEnum::u16_from_buffer)
+            .expect("Should success parsing");
+
+        assert_eq!(1, rest.len());
+        assert_eq!(vec![259u16, 4], result);
+    }
+
+    #[test]
+    fn parse_array_longer_than_buffer() {
+        let test_value = [3u8, 1, 2];
+        let result = TypesEnum::array_from_buffer(&test_value, 1, TypesEnum::u8_from_buffer);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_fixed_array_from_buffer() {
+        let test_value = [b'a', 0, b'b', 0, 7];
+        let (result, rest): ([String; 2], &[u8]) =
+            TypesEnum::fixed_array_from_buffer(&test_value, TypesEnum::string_from_buffer)
+                .expect("Should success parsing");
+
+        assert_eq!(1, rest.len());
+        assert_eq!([String::from("a"), String::from("b")], result);
+    }
+
+    #[test]
+    fn parse_fixed_array_longer_than_buffer() {
+        let test_value = [1u8, 2];
+        let result: Result<([u8; 3], &[u8]), _> =
+            TypesEnum::fixed_array_from_buffer(&test_value, TypesEnum::u8_from_buffer);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn serialize_large_fixed_array() {
+        #[derive(Serialize)]
+        struct LargeArray {
+            #[serde(serialize_with = "TypesEnum::serialize_fixed_array")]
+            value: [u8; 33],
+        }
+
+        let serialized = serde_yaml::to_string(&LargeArray { value: [7; 33] })
+            .expect("Should success serializing");
+
+        assert_eq!(33, serialized.matches("- 7").count());
+    }
+
+    #[test]
+    fn write_optional_to_buffer() {
+        let present = TypesEnum::optional_to_buffer(Some(TypesEnum::U16(259).write_to_buffer()));
+        let absent = TypesEnum::optional_to_buffer(None);
+
+        assert_eq!(present, &[1u8, 3, 1]);
+        assert_eq!(absent, &[0u8]);
+    }
+
+    #[test]
+    fn parse_optional_from_buffer() {
+        let test_value = [1u8, 3, 1, 0, 9];
+        let (present, rest) =
+            TypesEnum::optional_from_buffer(&test_value, TypesEnum::u16_from_buffer)
+                .expect("Should success parsing");
+        let (absent, rest) = TypesEnum::optional_from_buffer(rest, TypesEnum::u16_from_buffer)
+            .expect("Should success parsing");
+
+        assert_eq!(Some(259u16), present);
+        assert_eq!(None, absent);
+        assert_eq!(&[9u8], rest);
+    }
+
+    #[test]
+    fn parse_present_optional_without_value() {
+        let test_value = [1u8, 3];
+        let result = TypesEnum::optional_from_buffer(&test_value, TypesEnum::u16_from_buffer);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn serialize_large_optional_fixed_array() {
+        #[derive(Serialize)]
+        struct LargeArray {
+            #[serde(serialize_with = "TypesEnum::serialize_optional_fixed_array")]
+            value: Option<[u8; 33]>,
+        }
+
+        let serialized = serde_yaml::to_string(&LargeArray {
+            value: Some([7; 33]),
+        })
+        .expect("Should success serializing");
+
+        assert_eq!(33, serialized.matches("- 7").count());
+    }
+
+    #[test]
+    fn compute_crc8() {
+        assert_eq!(0xf4, Checksum::Crc8.compute(b"123456789"));
+    }
+
+    #[test]
+    fn compute_crc16_ccitt() {
+        assert_eq!(0x29b1, Checksum::Crc16.compute(b"123456789"));
+    }
+
+    #[test]
+    fn compute_crc32() {
+        assert_eq!(0xcbf43926, Checksum::Crc32.compute(b"123456789"));
+    }
+
+    #[test]
+    fn computed_checksum_fits_its_size() {
+        for checksum in [Checksum::Crc8, Checksum::Crc16, Checksum::Crc32] {
+            let crc = checksum.compute(&[0xff; 3]) as u64;
+            assert_eq!(0, crc >> (8 * checksum.size()));
+        }
+    }
+
+    #[test]
+    fn frame_without_checksum_is_unchanged() {
+        assert_eq!(vec![1u8, 2], TypesEnum::append_checksum(vec![1, 2]));
+        assert_eq!(
+            &[1u8, 2],
+            verify_checksum(&[1, 2]).expect("Should accept the frame")
+        );
+    }
+
+    #[test]
+    fn empty_frame_is_too_short() {
+        assert!(matches!(
+            verify_checksum(&[]),
+            Err(TypesEnumError::TooShort)
+        ));
+    }
+
+    #[test]
+    fn decode_borrowed_values() {
+        let test_value = [3u8, 1, b'h', b'i', 0, 1, 9];
+        let mut reader = Reader::new(&test_value);
+
+        assert_eq!(259u16, u16::decode(&mut reader).expect("Should success parsing"));
+        let string = <&str>::decode(&mut reader).expect("Should success parsing");
+        assert_eq!("hi", string);
+        assert_eq!(test_value[2..].as_ptr(), string.as_ptr());
+        assert!(bool::decode(&mut reader).expect("Should success parsing"));
+        assert_eq!(&[9u8], reader.remaining());
+    }
+
+    #[test]
+    fn decode_from_too_short_reader() {
+        let mut reader = Reader::new(&[1u8, 2, 3]);
+
+        assert!(matches!(
+            u32::decode(&mut reader),
+            Err(TypesEnumError::TooShort)
+        ));
+        assert!(matches!(
+            <&str>::decode(&mut reader),
+            Err(TypesEnumError::NoStringTerminationFound)
+        ));
+    }
+
+    #[test]
+    fn decode_fixed_array_and_optional() {
+        let test_value = [b'a', 0, b'b', 0, 1, 3, 1, 0];
+        let mut reader = Reader::new(&test_value);
+
+        let strings = <[&str; 2]>::decode(&mut reader).expect("Should success parsing");
+        let present = Option::<u16>::decode(&mut reader).expect("Should success parsing");
+        let absent = Option::<u16>::decode(&mut reader).expect("Should success parsing");
+
+        assert_eq!(["a", "b"], strings);
+        assert_eq!(Some(259), present);
+        assert_eq!(None, absent);
+    }
+
+    #[test]
+    fn decode_fixed_array_longer_than_reader() {
+        let mut reader = Reader::new(&[1u8, 2]);
+
+        assert!(<[u8; 3]>::decode(&mut reader).is_err());
+    }
+
+    #[test]
+    fn decode_array_and_iterate() {
+        let test_value = [2u8, 0, 3, 1, 4, 0, 9];
+        let mut reader = Reader::new(&test_value);
+
+        let array = Array::<u16, 2>::decode(&mut reader).expect("Should success parsing");
+
+        assert_eq!(2, array.len());
+        assert!(!array.is_empty());
+        assert_eq!(vec![259u16, 4], array.iter().collect::<Vec<u16>>());
+        assert_eq!(Array::from(&[259u16, 4][..]), array);
+        assert_eq!(&[9u8], reader.remaining());
+    }
+
+    #[test]
+    fn decode_array_longer_than_reader() {
+        let mut reader = Reader::new(&[3u8, 1, 2]);
+
+        assert!(Array::<u8, 1>::decode(&mut reader).is_err());
+    }
+
+    #[test]
+    fn encode_into_slice() {
+        let mut buffer = [0u8; 8];
+        let mut writer = Writer::new(&mut buffer[..]);
+        259u16.encode(&mut writer).expect("Should success encoding");
+        "hi".encode(&mut writer).expect("Should success encoding");
+        Some(true).encode(&mut writer).expect("Should success encoding");
+        let len = writer.finish().expect("Should success encoding");
+
+        assert_eq!(&[3u8, 1, b'h', b'i', 0, 1, 1], &buffer[..len]);
+    }
+
+    #[test]
+    fn encode_into_too_small_slice() {
+        let mut buffer = [0u8; 2];
+        let mut writer = Writer::new(&mut buffer[..]);
+
+        assert!("hi".encode(&mut writer).is_err());
+    }
+
+    #[test]
+    fn encode_array_views() {
+        let mut output = Vec::new();
+        let mut writer = Writer::new(&mut output);
+        Array::<_, 2>::from(&[259u16, 4][..])
+            .encode(&mut writer)
+            .expect("Should success encoding");
+        let decoded = Array::<u8, 1>::decode(&mut Reader::new(&[2u8, 7, 8]))
+            .expect("Should success parsing");
+        decoded.encode(&mut writer).expect("Should success encoding");
+        writer.finish().expect("Should success encoding");
+
+        assert_eq!(vec![2u8, 0, 3, 1, 4, 0, 2, 7, 8], output);
+    }
+
+    #[test]
+    fn encode_array_too_long_for_count() {
+        let items = [0u8; 256];
+        let mut writer = Writer::new(Vec::new());
+
+        let error = Array::<_, 1>::from(&items[..])
+            .encode(&mut writer)
+            .expect_err("The count shouldn't fit a byte");
+
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn checksum_updates_match_computed_checksum() {
+        for checksum in [Checksum::Crc8, Checksum::Crc16, Checksum::Crc32] {
+            let crc = checksum.update(checksum.initial(), b"1234");
+            let crc = checksum.finish(checksum.update(crc, b"56789"));
+
+            assert_eq!(checksum.compute(b"123456789"), crc);
+        }
+    }
+
+    #[test]
+    fn encode_cobs_frame() {
+        assert_eq!(vec![1u8, 1, 0], Framing::Cobs.encode(&[0]).unwrap());
+        assert_eq!(
+            vec![3u8, 0x11, 0x22, 2, 0x33, 0],
+            Framing::Cobs.encode(&[0x11, 0x22, 0, 0x33]).unwrap()
+        );
+    }
+
+    #[test]
+    fn encode_cobs_long_frame() {
+        let frame = (1..=254u8).collect::<Vec<u8>>();
+        let encoded = Framing::Cobs.encode(&frame).expect("Should success encoding");
+
+        assert_eq!(256, encoded.len());
+        assert_eq!(0xff, encoded[0]);
+        assert_eq!(0, encoded[255]);
+    }
+
+    #[test]
+    fn encode_slip_frame() {
+        assert_eq!(
+            vec![0xc0u8, 1, 0xdb, 0xdc, 0xdb, 0xdd, 0xc0],
+            Framing::Slip.encode(&[1, 0xc0, 0xdb]).unwrap()
+        );
+    }
+
+    #[test]
+    fn encode_sync_length_frame() {
+        assert_eq!(
+            vec![0xaau8, 2, 0, 7, 8],
+            Framing::SyncLength.encode(&[7, 8]).unwrap()
+        );
+    }
+
+    #[test]
+    fn encode_sync_length_too_long_frame() {
+        let frame = vec![0u8; 0x10000];
+
+        assert!(matches!(
+            Framing::SyncLength.encode(&frame),
+            Err(TypesEnumError::FrameTooLong)
+        ));
+    }
+
+    fn decode_in_chunks(framing: Framing, frames: &[Vec<u8>], chunk_size: usize) -> Vec<Vec<u8>> {
+        let stream = frames
+            .iter()
+            .flat_map(|frame| framing.encode(frame).expect("Should success encoding"))
+            .collect::<Vec<u8>>();
+        let mut decoder = StreamDecoder::new(framing, 1000);
+
+        stream
+            .chunks(chunk_size)
+            .flat_map(|chunk| decoder.push(chunk))
+            .map(|frame| frame.expect("Should decode the frame"))
+            .collect()
+    }
+
+    #[test]
+    fn stream_decoder_rebuilds_split_frames() {
+        let frames = vec![
+            vec![1u8, 0, 0xc0, 0xdb, 0xaa],
+            vec![0u8; 3],
+            (0..=255u8).cycle().take(600).collect(),
+        ];
+
+        for framing in [Framing::Cobs, Framing::Slip, Framing::SyncLength] {
+            for chunk_size in [1, 3, 1000] {
+                let decoded = decode_in_chunks(framing, &frames, chunk_size);
+                assert_eq!(frames, decoded, "{framing:?} by {chunk_size}");
+            }
+        }
+    }
+
+    #[test]
+    fn stream_decoder_skips_noise_before_sync_byte() {
+        let mut decoder = StreamDecoder::new(Framing::SyncLength, 16);
+        let frames = decoder.push(&[0x55, 0x12, 0xaa, 1, 0, 7]);
+
+        assert_eq!(1, frames.len());
+        assert_eq!(vec![7u8], *frames[0].as_ref().unwrap());
+    }
+
+    #[test]
+    fn stream_decoder_resyncs_after_too_long_sync_header() {
+        let mut decoder = StreamDecoder::new(Framing::SyncLength, 16);
+        let frames = decoder.push(&[0xaa, 0xff, 0xff, 0xaa, 2, 0, 7, 8]);
+
+        assert_eq!(2, frames.len());
+        assert!(matches!(frames[0], Err(TypesEnumError::InvalidFraming)));
+        assert_eq!(vec![7u8, 8], *frames[1].as_ref().unwrap());
+    }
+
+    #[test]
+    fn stream_decoder_reports_too_long_frames() {
+        let frame = vec![1u8; 17];
+
+        for framing in [Framing::Cobs, Framing::Slip, Framing::SyncLength] {
+            let mut decoder = StreamDecoder::new(framing, 16);
+            let stream = [framing.encode(&frame).unwrap(), framing.encode(&[3]).unwrap()].concat();
+            let frames = decoder.push(&stream);
+
+            assert!(
+                matches!(frames[0], Err(TypesEnumError::InvalidFraming)),
+                "{framing:?}"
+            );
+            assert_eq!(vec![3u8], *frames.last().unwrap().as_ref().unwrap(), "{framing:?}");
+        }
+    }
+
+    #[test]
+    fn stream_decoder_reports_invalid_slip_escape() {
+        let mut decoder = StreamDecoder::new(Framing::Slip, 16);
+        let frames = decoder.push(&[0xc0, 1, 0xdb, 2, 0xc0, 3, 0xc0]);
+
+        assert_eq!(2, frames.len());
+        assert!(matches!(frames[0], Err(TypesEnumError::InvalidFraming)));
+        assert_eq!(vec![3u8], *frames[1].as_ref().unwrap());
+    }
+
+    #[test]
+    fn cobs_decode_rejects_zero_code() {
+        assert!(matches!(
+            Framing::cobs_decode(&mut [0, 1]),
+            Err(TypesEnumError::InvalidFraming)
+        ));
+    }
+
+    #[test]
+    fn stream_decoder_reports_invalid_cobs_block() {
+        let mut decoder = StreamDecoder::new(Framing::Cobs, 16);
+        let frames = decoder.push(&[5, 1, 0, 2, 3, 0]);
+
+        assert_eq!(2, frames.len());
+        assert!(matches!(frames[0], Err(TypesEnumError::InvalidFraming)));
+        assert_eq!(vec![3u8], *frames[1].as_ref().unwrap());
+    }
 }
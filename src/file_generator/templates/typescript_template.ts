@@ -0,0 +1,332 @@
+// This file is autogenerated, please do not edit manually
+
+// ##CUSTOM_INCLUDES##
+
+export type Checksum = "crc8" | "crc16" | "crc32";
+
+// ##SETTINGS##
+
+export type FrameErrorReason =
+  | "TooShort"
+  | "NoStringTerminationFound"
+  | "FailedToDecodeString"
+  | "UnknownCode"
+  | "UnknownEnumValue"
+  | "ChecksumMismatch";
+
+/** Raised when a received frame can't be decoded */
+export class FrameError extends Error {
+  readonly reason: FrameErrorReason;
+
+  constructor(reason: FrameErrorReason) {
+    super(`Invalid frame: ${reason}`);
+    this.name = "FrameError";
+    this.reason = reason;
+  }
+}
+
+/** Encodes the values of a frame in order, in the wire byte order */
+class FrameWriter {
+  private readonly bytes: number[] = [];
+  private readonly scratch = new DataView(new ArrayBuffer(8));
+
+  private push(size: number): void {
+    for (let i = 0; i < size; i++) {
+      this.bytes.push(this.scratch.getUint8(i));
+    }
+  }
+
+  u8(value: number): void {
+    this.scratch.setUint8(0, value);
+    this.push(1);
+  }
+
+  i8(value: number): void {
+    this.scratch.setInt8(0, value);
+    this.push(1);
+  }
+
+  u16(value: number): void {
+    this.scratch.setUint16(0, value, LITTLE_ENDIAN);
+    this.push(2);
+  }
+
+  i16(value: number): void {
+    this.scratch.setInt16(0, value, LITTLE_ENDIAN);
+    this.push(2);
+  }
+
+  u32(value: number): void {
+    this.scratch.setUint32(0, value, LITTLE_ENDIAN);
+    this.push(4);
+  }
+
+  i32(value: number): void {
+    this.scratch.setInt32(0, value, LITTLE_ENDIAN);
+    this.push(4);
+  }
+
+  u64(value: bigint): void {
+    this.scratch.setBigUint64(0, value, LITTLE_ENDIAN);
+    this.push(8);
+  }
+
+  i64(value: bigint): void {
+    this.scratch.setBigInt64(0, value, LITTLE_ENDIAN);
+    this.push(8);
+  }
+
+  f32(value: number): void {
+    this.scratch.setFloat32(0, value, LITTLE_ENDIAN);
+    this.push(4);
+  }
+
+  f64(value: number): void {
+    this.scratch.setFloat64(0, value, LITTLE_ENDIAN);
+    this.push(8);
+  }
+
+  bool(value: boolean): void {
+    this.u8(value ? 1 : 0);
+  }
+
+  string(value: string): void {
+    this.raw(new TextEncoder().encode(value));
+    this.u8(0);
+  }
+
+  raw(bytes: Uint8Array): void {
+    bytes.forEach((byte) => this.bytes.push(byte));
+  }
+
+  /** Writes the items count on `lengthSize` bytes followed by the items */
+  array<T>(lengthSize: number, items: T[], write: (item: T) => void): void {
+    if (items.length >= 2 ** (8 * lengthSize)) {
+      throw new RangeError(`Array of ${items.length} items doesn't fit a ${lengthSize} bytes length`);
+    }
+    this.uint(lengthSize, items.length);
+    items.forEach(write);
+  }
+
+  fixedArray<T>(count: number, items: T[], write: (item: T) => void): void {
+    if (items.length !== count) {
+      throw new RangeError(`Expected ${count} items, got ${items.length}`);
+    }
+    items.forEach(write);
+  }
+
+  /** Writes the presence flag of an optional value, followed by the value when present */
+  optional<T>(value: T | undefined, write: (value: T) => void): void {
+    this.bool(value !== undefined);
+    if (value !== undefined) {
+      write(value);
+    }
+  }
+
+  uint(size: number, value: number): void {
+    switch (size) {
+      case 1:
+        return this.u8(value);
+      case 2:
+        return this.u16(value);
+      default:
+        return this.u32(value);
+    }
+  }
+
+  finish(): Uint8Array {
+    return Uint8Array.from(this.bytes);
+  }
+}
+
+/** Decodes the values of a received frame in order */
+class FrameReader {
+  private readonly view: DataView;
+  private offset: number;
+
+  constructor(bytes: Uint8Array, offset: number) {
+    this.view = new DataView(bytes.buffer, bytes.byteOffset, bytes.byteLength);
+    this.offset = offset;
+  }
+
+  /** Reserves `size` bytes, and returns their offset */
+  private take(size: number): number {
+    if (this.offset + size > this.view.byteLength) {
+      throw new FrameError("TooShort");
+    }
+    const offset = this.offset;
+    this.offset += size;
+    return offset;
+  }
+
+  u8(): number {
+    return this.view.getUint8(this.take(1));
+  }
+
+  i8(): number {
+    return this.view.getInt8(this.take(1));
+  }
+
+  u16(): number {
+    return this.view.getUint16(this.take(2), LITTLE_ENDIAN);
+  }
+
+  i16(): number {
+    return this.view.getInt16(this.take(2), LITTLE_ENDIAN);
+  }
+
+  u32(): number {
+    return this.view.getUint32(this.take(4), LITTLE_ENDIAN);
+  }
+
+  i32(): number {
+    return this.view.getInt32(this.take(4), LITTLE_ENDIAN);
+  }
+
+  u64(): bigint {
+    return this.view.getBigUint64(this.take(8), LITTLE_ENDIAN);
+  }
+
+  i64(): bigint {
+    return this.view.getBigInt64(this.take(8), LITTLE_ENDIAN);
+  }
+
+  f32(): number {
+    return this.view.getFloat32(this.take(4), LITTLE_ENDIAN);
+  }
+
+  f64(): number {
+    return this.view.getFloat64(this.take(8), LITTLE_ENDIAN);
+  }
+
+  bool(): boolean {
+    return this.u8() !== 0;
+  }
+
+  string(): string {
+    const bytes = new Uint8Array(this.view.buffer, this.view.byteOffset, this.view.byteLength);
+    const end = bytes.indexOf(0, this.offset);
+    if (end < 0) {
+      throw new FrameError("NoStringTerminationFound");
+    }
+    let value: string;
+    try {
+      value = new TextDecoder("utf-8", { fatal: true }).decode(bytes.subarray(this.offset, end));
+    } catch {
+      throw new FrameError("FailedToDecodeString");
+    }
+    this.offset = end + 1;
+    return value;
+  }
+
+  array<T>(lengthSize: number, read: () => T): T[] {
+    return this.fixedArray(this.uint(lengthSize), read);
+  }
+
+  fixedArray<T>(count: number, read: () => T): T[] {
+    const items: T[] = [];
+    for (let i = 0; i < count; i++) {
+      items.push(read());
+    }
+    return items;
+  }
+
+  /** Decodes the presence flag of an optional value, followed by the value when present */
+  optional<T>(read: () => T): T | undefined {
+    return this.bool() ? read() : undefined;
+  }
+
+  uint(size: number): number {
+    switch (size) {
+      case 1:
+        return this.u8();
+      case 2:
+        return this.u16();
+      default:
+        return this.u32();
+    }
+  }
+}
+
+function computeChecksum(checksum: Checksum, frame: Uint8Array): Uint8Array {
+  const writer = new FrameWriter();
+  switch (checksum) {
+    case "crc8": {
+      let crc = 0;
+      for (const byte of frame) {
+        crc ^= byte;
+        for (let bit = 0; bit < 8; bit++) {
+          crc = (crc & 0x80 ? (crc << 1) ^ 0x07 : crc << 1) & 0xff;
+        }
+      }
+      writer.u8(crc);
+      break;
+    }
+    case "crc16": {
+      let crc = 0xffff;
+      for (const byte of frame) {
+        crc ^= byte << 8;
+        for (let bit = 0; bit < 8; bit++) {
+          crc = (crc & 0x8000 ? (crc << 1) ^ 0x1021 : crc << 1) & 0xffff;
+        }
+      }
+      writer.u16(crc);
+      break;
+    }
+    case "crc32": {
+      let crc = 0xffffffff;
+      for (const byte of frame) {
+        crc ^= byte;
+        for (let bit = 0; bit < 8; bit++) {
+          crc = crc & 1 ? (crc >>> 1) ^ 0xedb88320 : crc >>> 1;
+        }
+      }
+      writer.u32((crc ^ 0xffffffff) >>> 0);
+      break;
+    }
+  }
+  return writer.finish();
+}
+
+/** Appends the configured checksum to an encoded frame */
+function appendChecksum(writer: FrameWriter): Uint8Array {
+  const frame = writer.finish();
+  if (CHECKSUM === null) {
+    return frame;
+  }
+  writer.raw(computeChecksum(CHECKSUM, frame));
+  return writer.finish();
+}
+
+/** Checks the configured checksum of a received frame, and returns the frame without it */
+function verifyChecksum(frame: Uint8Array): Uint8Array {
+  if (CHECKSUM === null) {
+    if (frame.length === 0) {
+      throw new FrameError("TooShort");
+    }
+    return frame;
+  }
+
+  const size = computeChecksum(CHECKSUM, new Uint8Array()).length;
+  if (frame.length <= size) {
+    throw new FrameError("TooShort");
+  }
+  const content = frame.subarray(0, frame.length - size);
+  const expected = computeChecksum(CHECKSUM, content);
+  if (expected.some((byte, i) => byte !== frame[content.length + i])) {
+    throw new FrameError("ChecksumMismatch");
+  }
+  return content;
+}
+
+// ##TYPES_DECLARATIONS##
+
+// ##TYPES_IMPLEMENTATION##
+
+// ##FEEDBACKS_DECLARATIONS##
+
+// ##INSTRUCTIONS_DECLARATIONS##
+
+// ##FEEDBACKS_IMPLEMENTATION##
+
+// ##INSTRUCTIONS_IMPLEMENTATION##
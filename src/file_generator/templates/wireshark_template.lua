@@ -0,0 +1,472 @@
+-- This file is autogenerated, please do not edit manually
+--
+-- Wireshark dissector of the protocol frames. Copy it to the Wireshark personal
+-- plugins folder, or load it with `wireshark -X lua_script:<file>`.
+
+-- ##CUSTOM_INCLUDES##
+
+-- ##SETTINGS##
+
+local proto = Proto(PROTOCOL_NAME, PROTOCOL_DESCRIPTION)
+
+-- How instructions are told apart from feedbacks
+local DIRECTION_HOST_SENDS_INSTRUCTIONS = 1
+local DIRECTION_HOST_SENDS_FEEDBACKS = 2
+local DIRECTION_HEURISTIC = 3
+local DIRECTION_INSTRUCTIONS = 4
+local DIRECTION_FEEDBACKS = 5
+
+proto.prefs.direction = Pref.enum(
+    "Frames direction",
+    DIRECTION_HOST_SENDS_INSTRUCTIONS,
+    "How instructions are told apart from feedbacks. The capture direction falls back on the heuristic when the packet direction is unknown.",
+    {
+        { 1, "Capture direction, the host sends instructions", DIRECTION_HOST_SENDS_INSTRUCTIONS },
+        { 2, "Capture direction, the host sends feedbacks", DIRECTION_HOST_SENDS_FEEDBACKS },
+        { 3, "Heuristic, the frame type whose parameters fill the frame", DIRECTION_HEURISTIC },
+        { 4, "Instructions only", DIRECTION_INSTRUCTIONS },
+        { 5, "Feedbacks only", DIRECTION_FEEDBACKS },
+    },
+    false
+)
+
+-- Fields by key, registered under `<protocol>.<key>`
+local F = {}
+-- Dissectors of the structures by name, and of the frames by code
+local TYPES, INSTRUCTIONS, FEEDBACKS = {}, {}, {}
+
+local function add_field(key, kind, name, ...)
+    F[key] = ProtoField[kind](PROTOCOL_NAME .. "." .. key, name, ...)
+end
+
+add_field("code", "uint" .. (8 * CODE_SIZE), "Code", base.HEX, CODE_NAMES)
+add_field("type", "string", "Frame type")
+if CHECKSUM_SIZE > 0 then
+    add_field("checksum", "uint" .. (8 * CHECKSUM_SIZE), "Checksum", base.HEX)
+end
+
+local expert_truncated = ProtoExpert.new(
+    PROTOCOL_NAME .. ".truncated", "Frame too short", expert.group.MALFORMED, expert.severity.ERROR)
+local expert_unknown_code = ProtoExpert.new(
+    PROTOCOL_NAME .. ".unknown_code", "Unknown code", expert.group.UNDECODED, expert.severity.WARN)
+local expert_trailing_bytes = ProtoExpert.new(
+    PROTOCOL_NAME .. ".trailing_bytes", "Unexpected bytes after the parameters", expert.group.MALFORMED, expert.severity.ERROR)
+local expert_bad_checksum = ProtoExpert.new(
+    PROTOCOL_NAME .. ".bad_checksum", "Checksum mismatch", expert.group.CHECKSUM, expert.severity.ERROR)
+local expert_bad_framing = ProtoExpert.new(
+    PROTOCOL_NAME .. ".bad_framing", "Invalid stream framing", expert.group.MALFORMED, expert.severity.ERROR)
+proto.experts = { expert_truncated, expert_unknown_code, expert_trailing_bytes, expert_bad_checksum, expert_bad_framing }
+
+local function read_uint(range)
+    if LITTLE_ENDIAN then
+        return range:le_uint()
+    end
+    return range:uint()
+end
+
+-- Dissectors combinators. Each dissector takes the frame `tvb`, the `tree` to fill
+-- and the `offset` of its value, and returns the offset following the value.
+-- A `nil` tree only measures the frame, as used by the heuristic.
+local D = {}
+
+local function add_value(tree, field, range)
+    if LITTLE_ENDIAN then
+        return tree:add_le(field, range)
+    end
+    return tree:add(field, range)
+end
+
+function D.value(field, size)
+    return function(tvb, tree, offset)
+        local range = tvb(offset, size)
+        if tree then
+            add_value(tree, field, range)
+        end
+        return offset + size
+    end
+end
+
+-- Enumeration value, unknown values are rejected by the heuristic
+function D.enum(field, size, values)
+    return function(tvb, tree, offset)
+        local range = tvb(offset, size)
+        if tree then
+            add_value(tree, field, range)
+        else
+            -- The names of the negative values are looked up by their two's complement
+            local value = read_uint(range)
+            if values[value] == nil and values[value - 2 ^ (8 * size)] == nil then
+                error("Unknown enumeration value")
+            end
+        end
+        return offset + size
+    end
+end
+
+-- Bitfield value, along with its fields masked from the same bytes
+function D.bitfield(field, size, subfields)
+    return function(tvb, tree, offset)
+        local range = tvb(offset, size)
+        if tree then
+            local item = add_value(tree, field, range)
+            for _, subfield in ipairs(subfields) do
+                add_value(item, subfield, range)
+            end
+        end
+        return offset + size
+    end
+end
+
+function D.stringz(field)
+    return function(tvb, tree, offset)
+        local length = 0
+        repeat
+            length = length + 1
+        until tvb(offset + length - 1, 1):uint() == 0
+        if tree then
+            tree:add(field, tvb(offset, length))
+        end
+        return offset + length
+    end
+end
+
+-- Items count on `count_size` bytes, holding the items in its subtree
+function D.array(count_field, count_size, item)
+    return function(tvb, tree, offset)
+        local count = read_uint(tvb(offset, count_size))
+        local start = offset
+        local subtree = tree and add_value(tree, count_field, tvb(offset, count_size))
+        offset = offset + count_size
+        for _ = 1, count do
+            offset = item(tvb, subtree, offset)
+        end
+        if subtree then
+            subtree:set_len(offset - start)
+        end
+        return offset
+    end
+end
+
+function D.fixed_array(field, count, item)
+    return D.group(field, function(tvb, tree, offset)
+        for _ = 1, count do
+            offset = item(tvb, tree, offset)
+        end
+        return offset
+    end)
+end
+
+-- Subtree of a structure, dissected by the `TYPES` entry of its name
+function D.struct(field, name)
+    return D.group(field, function(tvb, tree, offset)
+        return TYPES[name](tvb, tree, offset)
+    end)
+end
+
+function D.group(field, dissect)
+    return function(tvb, tree, offset)
+        local subtree = tree and tree:add(field, tvb(offset, 0))
+        local next_offset = dissect(tvb, subtree, offset)
+        if subtree then
+            subtree:set_len(next_offset - offset)
+        end
+        return next_offset
+    end
+end
+
+-- Presence flag of an optional value, followed by the value when present
+function D.optional(present_field, dissect)
+    return function(tvb, tree, offset)
+        local present = tvb(offset, 1):uint() ~= 0
+        if tree then
+            tree:add(present_field, tvb(offset, 1))
+        end
+        if present then
+            return dissect(tvb, tree, offset + 1)
+        end
+        return offset + 1
+    end
+end
+
+function D.sequence(dissectors)
+    return function(tvb, tree, offset)
+        for _, dissect in ipairs(dissectors) do
+            offset = dissect(tvb, tree, offset)
+        end
+        return offset
+    end
+end
+
+-- Bitwise xor of two unsigned integers, written without operators to run on any
+-- Lua version used by Wireshark
+local function bxor(a, b)
+    local result, bit = 0, 1
+    while a > 0 or b > 0 do
+        local a_bit, b_bit = a % 2, b % 2
+        if a_bit ~= b_bit then
+            result = result + bit
+        end
+        a, b, bit = (a - a_bit) / 2, (b - b_bit) / 2, bit * 2
+    end
+    return result
+end
+
+local function compute_checksum(tvb, length)
+    local crc
+    if CHECKSUM == "crc8" then
+        crc = 0
+        for i = 0, length - 1 do
+            crc = bxor(crc, tvb(i, 1):uint())
+            for _ = 1, 8 do
+                if crc >= 0x80 then
+                    crc = bxor((crc * 2) % 0x100, 0x07)
+                else
+                    crc = crc * 2
+                end
+            end
+        end
+    elseif CHECKSUM == "crc16" then
+        crc = 0xffff
+        for i = 0, length - 1 do
+            crc = bxor(crc, tvb(i, 1):uint() * 0x100)
+            for _ = 1, 8 do
+                if crc >= 0x8000 then
+                    crc = bxor((crc * 2) % 0x10000, 0x1021)
+                else
+                    crc = crc * 2
+                end
+            end
+        end
+    else
+        crc = 0xffffffff
+        for i = 0, length - 1 do
+            crc = bxor(crc, tvb(i, 1):uint())
+            for _ = 1, 8 do
+                if crc % 2 == 1 then
+                    crc = bxor((crc - 1) / 2, 0xedb88320)
+                else
+                    crc = crc / 2
+                end
+            end
+        end
+        crc = bxor(crc, 0xffffffff)
+    end
+    return crc
+end
+
+local function cobs_decode(content)
+    local frame = ByteArray.new()
+    local i = 0
+    while i < content:len() do
+        local code = content:get_index(i)
+        if code == 0 or i + code > content:len() then
+            return frame, false
+        end
+        if code > 1 then
+            frame:append(content:subset(i + 1, code - 1))
+        end
+        i = i + code
+        if code < 0xff and i < content:len() then
+            frame:append(ByteArray.new("00"))
+        end
+    end
+    return frame, true
+end
+
+local function slip_decode(content)
+    local frame = ByteArray.new()
+    local i = 0
+    while i < content:len() do
+        local byte = content:get_index(i)
+        if byte == 0xdb then
+            i = i + 1
+            if i >= content:len() then
+                return frame, false
+            end
+            local escaped = content:get_index(i)
+            if escaped == 0xdc then
+                frame:append(ByteArray.new("c0"))
+            elseif escaped == 0xdd then
+                frame:append(ByteArray.new("db"))
+            else
+                return frame, false
+            end
+        else
+            frame:append(content:subset(i, 1))
+        end
+        i = i + 1
+    end
+    return frame, true
+end
+
+-- Splits the packet into the frames it carries, decoding the stream framing
+local function unframe(tvb)
+    if FRAMING == nil then
+        return { tvb }, true
+    end
+
+    local frames = {}
+    if FRAMING == "sync" then
+        local offset = 0
+        while offset < tvb:len() do
+            if tvb(offset, 1):uint() ~= 0xaa or offset + 3 > tvb:len() then
+                return frames, false
+            end
+            local length = read_uint(tvb(offset + 1, 2))
+            if offset + 3 + length > tvb:len() then
+                return frames, false
+            end
+            if length > 0 then
+                table.insert(frames, tvb(offset + 3, length):tvb())
+            end
+            offset = offset + 3 + length
+        end
+        return frames, true
+    end
+
+    local valid = true
+    local delimiter = FRAMING == "cobs" and 0x00 or 0xc0
+    local start = 0
+    for offset = 0, tvb:len() - 1 do
+        if tvb(offset, 1):uint() == delimiter then
+            if offset > start then
+                local content = tvb(start, offset - start):bytes()
+                local frame, ok
+                if FRAMING == "cobs" then
+                    frame, ok = cobs_decode(content)
+                else
+                    frame, ok = slip_decode(content)
+                end
+                valid = valid and ok
+                if ok and frame:len() > 0 then
+                    table.insert(frames, frame:tvb("Decoded frame"))
+                end
+            end
+            start = offset + 1
+        end
+    end
+    return frames, valid and start == tvb:len()
+end
+
+-- ##TYPES_DECLARATIONS##
+
+-- ##TYPES_IMPLEMENTATION##
+
+-- ##FEEDBACKS_DECLARATIONS##
+
+-- ##INSTRUCTIONS_DECLARATIONS##
+
+-- ##FEEDBACKS_IMPLEMENTATION##
+
+-- ##INSTRUCTIONS_IMPLEMENTATION##
+
+local fields = {}
+for _, field in pairs(F) do
+    table.insert(fields, field)
+end
+proto.fields = fields
+
+-- Whether the parameters of the frame type fill exactly the frame payload
+local function fits(frames, tvb, code)
+    local dissect = frames[code]
+    if dissect == nil then
+        return false
+    end
+    local ok, offset = pcall(dissect, tvb, nil, CODE_SIZE)
+    return ok and offset == tvb:len()
+end
+
+local function is_instruction(tvb, pinfo, code)
+    local direction = proto.prefs.direction
+    if direction == DIRECTION_INSTRUCTIONS then
+        return true
+    elseif direction == DIRECTION_FEEDBACKS then
+        return false
+    elseif direction ~= DIRECTION_HEURISTIC then
+        local from_host
+        -- USB captures name the host address, serial captures set the point-to-point
+        -- direction to 0 when sent and 1 when received
+        if tostring(pinfo.src) == "host" or pinfo.p2p_dir == 0 then
+            from_host = true
+        elseif tostring(pinfo.dst) == "host" or pinfo.p2p_dir == 1 then
+            from_host = false
+        end
+        if from_host ~= nil then
+            return from_host == (direction == DIRECTION_HOST_SENDS_INSTRUCTIONS)
+        end
+    end
+
+    if fits(INSTRUCTIONS, tvb, code) then
+        return true
+    elseif fits(FEEDBACKS, tvb, code) then
+        return false
+    end
+    return INSTRUCTIONS[code] ~= nil or FEEDBACKS[code] == nil
+end
+
+local function dissect_frame(tvb, pinfo, tree)
+    local subtree = tree:add(proto, tvb())
+    local payload_length = tvb:len() - CHECKSUM_SIZE
+    if payload_length < CODE_SIZE then
+        subtree:add_proto_expert_info(expert_truncated)
+        return "Malformed"
+    end
+
+    local payload = tvb(0, payload_length):tvb()
+    local code = read_uint(payload(0, CODE_SIZE))
+    local instruction = is_instruction(payload, pinfo, code)
+    local frames = instruction and INSTRUCTIONS or FEEDBACKS
+    local frame_type = instruction and "Instruction" or "Feedback"
+    local name = CODE_NAMES[code] or string.format("0x%02x", code)
+    local summary = frame_type .. " " .. name
+
+    subtree:append_text(", " .. summary)
+    subtree:add(F.type, tvb(0, CODE_SIZE), frame_type):set_generated()
+    local code_item = add_value(subtree, F.code, tvb(0, CODE_SIZE))
+
+    local dissect = frames[code]
+    if dissect == nil then
+        code_item:add_proto_expert_info(expert_unknown_code, "No " .. frame_type:lower() .. " has this code")
+    else
+        local ok, offset = pcall(dissect, payload, subtree, CODE_SIZE)
+        if not ok then
+            subtree:add_proto_expert_info(expert_truncated)
+        elseif offset < payload_length then
+            subtree:add(tvb(offset, payload_length - offset), "Trailing bytes")
+                :add_proto_expert_info(expert_trailing_bytes)
+        end
+    end
+
+    if CHECKSUM_SIZE > 0 then
+        local range = tvb(payload_length, CHECKSUM_SIZE)
+        local item = add_value(subtree, F.checksum, range)
+        local expected = compute_checksum(tvb, payload_length)
+        if read_uint(range) ~= expected then
+            item:add_proto_expert_info(
+                expert_bad_checksum, string.format("Checksum mismatch, expected 0x%x", expected))
+        end
+    end
+    return summary
+end
+
+function proto.dissector(tvb, pinfo, tree)
+    pinfo.cols.protocol = proto.name
+    local frames, valid = unframe(tvb)
+    local summaries = {}
+    for _, frame in ipairs(frames) do
+        table.insert(summaries, dissect_frame(frame, pinfo, tree))
+    end
+    if not valid then
+        tree:add(proto, tvb()):add_proto_expert_info(expert_bad_framing)
+    end
+    pinfo.cols.info = table.concat(summaries, ", ")
+    return tvb:len()
+end
+
+-- CDC-ACM data interfaces are dissected by default, other transports can be
+-- selected with "Decode As..."
+DissectorTable.get("usb.bulk"):add(0x0a, proto)
+DissectorTable.get("udp.port"):add_for_decode_as(proto)
+DissectorTable.get("tcp.port"):add_for_decode_as(proto)
+
+-- ##CUSTOM_FOOTER##
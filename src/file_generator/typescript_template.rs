@@ -0,0 +1,30 @@
+use crate::file_generator::{FileGenerator, language_models};
+use crate::yaml_parser::{self};
+use std::fs::File;
+use std::io::{self, Write};
+
+const TYPESCRIPT_TEMPLATE: &str = include_str!("./templates/typescript_template.ts");
+
+pub struct TypeScriptFileGenerator {
+    writer: Box<dyn Write>,
+}
+
+impl FileGenerator for TypeScriptFileGenerator {
+    fn build_file(&mut self, codes: &yaml_parser::CodesFile) -> Result<(), io::Error> {
+        self.writer.write_all(
+            self.process_template(
+                TYPESCRIPT_TEMPLATE,
+                &language_models::TypeScriptLanguageModel {},
+                codes,
+            )
+            .as_bytes(),
+        )
+    }
+}
+
+impl TypeScriptFileGenerator {
+    pub fn new(file_name: String) -> Result<Self, io::Error> {
+        let file = Box::new(File::create(file_name)?);
+        Ok(Self { writer: file })
+    }
+}
@@ -0,0 +1,93 @@
+use crate::file_generator::{FileGenerator, language_models};
+use crate::yaml_parser::{self};
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+
+const WIRESHARK_TEMPLATE: &str = include_str!("./templates/wireshark_template.lua");
+
+pub struct WiresharkFileGenerator {
+    writer: Box<dyn Write>,
+    protocol_name: String,
+}
+
+impl FileGenerator for WiresharkFileGenerator {
+    fn build_file(&mut self, codes: &yaml_parser::CodesFile) -> Result<(), io::Error> {
+        self.writer.write_all(
+            self.process_template(
+                WIRESHARK_TEMPLATE,
+                &language_models::WiresharkLanguageModel {
+                    protocol_name: self.protocol_name.clone(),
+                },
+                codes,
+            )
+            .as_bytes(),
+        )
+    }
+}
+
+impl WiresharkFileGenerator {
+    /// The protocol is named after the dissector file, as a valid display filter prefix
+    pub fn new(file_name: String) -> Result<Self, io::Error> {
+        let protocol_name = Path::new(&file_name)
+            .file_stem()
+            .map(|stem| {
+                stem.to_string_lossy()
+                    .to_lowercase()
+                    .chars()
+                    .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                    .collect::<String>()
+            })
+            .filter(|name| name.starts_with(|c: char| c.is_ascii_lowercase()))
+            .unwrap_or_else(|| "codes".to_string());
+        let file = Box::new(File::create(file_name)?);
+        Ok(Self {
+            writer: file,
+            protocol_name,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::yaml_parser::test::integration_codes_file;
+
+    #[test]
+    fn direction_heuristic_follows_the_frames_dissectors() {
+        let generator = WiresharkFileGenerator {
+            writer: Box::new(io::sink()),
+            protocol_name: "codes".to_string(),
+        };
+        let dissector = generator.process_template(
+            WIRESHARK_TEMPLATE,
+            &language_models::WiresharkLanguageModel {
+                protocol_name: "codes".to_string(),
+            },
+            &integration_codes_file(),
+        );
+        let line = |text: &str| {
+            dissector
+                .lines()
+                .position(|line| line.trim_start() == text)
+                .unwrap()
+        };
+
+        assert!(!dissector.contains("##"));
+        assert!(
+            line("local TYPES, INSTRUCTIONS, FEEDBACKS = {}, {}, {}")
+                < line("TYPES.Event = D.sequence({")
+        );
+        assert!(
+            line("FEEDBACKS[0x0b] = D.sequence({") < line("local function fits(frames, tvb, code)")
+        );
+        assert!(
+            line("INSTRUCTIONS[0x0b] = D.sequence({")
+                < line("local function fits(frames, tvb, code)")
+        );
+        assert!(
+            line("if fits(INSTRUCTIONS, tvb, code) then")
+                < line("elseif fits(FEEDBACKS, tvb, code) then")
+        );
+    }
+}
@@ -1,24 +1,90 @@
+mod error;
 mod file_generator;
 mod yaml_parser;
 
+pub use error::Error;
 pub use file_generator::*;
-use std::error;
 use std::fs::File;
+use std::io;
 pub use yaml_parser::*;
 
 pub struct Arguments {
     pub input: String,
     pub c_header: Option<String>,
     pub c_source: Option<String>,
+    /// Platform providing the memory allocator of the generated C code
+    pub c_platform: CPlatform,
+    /// Generates C code storing the strings and arrays in place, parsing the frames
+    /// without allocating
+    pub c_no_alloc: bool,
     pub rust_source: Option<String>,
+    /// Generates allocation-free Rust code for `#![no_std]` crates
+    pub rust_no_std: bool,
+    pub python_source: Option<String>,
+    pub typescript_source: Option<String>,
+    pub documentation: Option<String>,
+    pub wireshark_dissector: Option<String>,
+    /// Only checks the input file, without writing any file
+    pub validate: bool,
+    /// Previous revision of the input file, to report the changes since instead of
+    /// generating any file
+    pub compat: Option<String>,
 }
 
 impl Arguments {
-    pub fn new(argv: Vec<String>) -> Result<Arguments, &'static str> {
+    /// Parses the command line arguments, failing with the usage when they can't be parsed
+    pub fn new(argv: Vec<String>) -> Result<Arguments, Error> {
         let mut options = getopts::Options::new();
         options.optopt("r", "rust", "Rust target file", "RUST_TARGET");
+        options.optflag(
+            "",
+            "no-std",
+            "Generate allocation-free Rust code for `#![no_std]` crates, depending on `heapless`",
+        );
+        options.optopt("p", "python", "Python target file", "PYTHON_TARGET");
+        options.optopt(
+            "t",
+            "typescript",
+            "TypeScript target file",
+            "TYPESCRIPT_TARGET",
+        );
+        options.optopt(
+            "d",
+            "doc",
+            "Markdown documentation target file",
+            "DOC_TARGET",
+        );
+        options.optopt(
+            "w",
+            "wireshark",
+            "Wireshark Lua dissector target file",
+            "WIRESHARK_TARGET",
+        );
         options.optopt("c", "csource", "C source target file", "C_SOURCE");
         options.optopt("h", "cheader", "C Header target file", "C_HEADER");
+        options.optopt(
+            "",
+            "c-platform",
+            "Platform of the generated C code: zephyr (default), libc, freertos, or custom to use the FRAME_MALLOC and FRAME_FREE macros defined by the user",
+            "PLATFORM",
+        );
+        options.optflag(
+            "",
+            "c-no-alloc",
+            "Generate C code parsing the frames without allocating, storing the strings and arrays in place up to their max_length and max_count",
+        );
+        options.optflag(
+            "",
+            "validate",
+            "Check the input file without generating any file, same as the `check` command",
+        );
+        options.optopt(
+            "",
+            "compat",
+            "Report the changes since a previous revision of the input file, without generating any file",
+            "PREVIOUS_INPUT",
+        );
+        options.optopt("", "diff", "Same as --compat", "PREVIOUS_INPUT");
         options.opt(
             "i",
             "input",
@@ -28,46 +94,143 @@ impl Arguments {
             getopts::Occur::Req,
         );
 
-        if let Ok(matches) = options.parse(&argv[1..]) {
-            Ok(Arguments {
-                input: matches.opt_str("i").unwrap(),
-                c_header: matches.opt_str("h"),
-                c_source: matches.opt_str("c"),
-                rust_source: matches.opt_str("r"),
-            })
-        } else {
-            println!(
-                "{}",
-                options.usage(
-                    format!(
-                        "Usage: {} -i INPUT [-r RUST_TARGET] [-c C_SOURCE] [-c C_HEADER]",
-                        argv[0]
-                    )
-                    .as_str()
-                )
-            );
-            Err("No arguments found")
+        match options.parse(&argv[1..]) {
+            Ok(matches) => {
+                let c_platform = matches
+                    .opt_get_default("c-platform", CPlatform::default())
+                    .map_err(Error::Usage)?;
+                Ok(Arguments {
+                    input: matches.opt_str("i").unwrap(),
+                    c_header: matches.opt_str("h"),
+                    c_source: matches.opt_str("c"),
+                    c_platform,
+                    c_no_alloc: matches.opt_present("c-no-alloc"),
+                    rust_source: matches.opt_str("r"),
+                    rust_no_std: matches.opt_present("no-std"),
+                    python_source: matches.opt_str("p"),
+                    typescript_source: matches.opt_str("t"),
+                    documentation: matches.opt_str("d"),
+                    wireshark_dissector: matches.opt_str("w"),
+                    validate: matches.opt_present("validate")
+                        || matches.free.iter().any(|command| command == "check"),
+                    compat: matches.opt_str("compat").or(matches.opt_str("diff")),
+                })
+            }
+            Err(error) => Err(Error::Usage(format!(
+                "{error}\n\n{}",
+                options.usage(&format!(
+                    "Usage: {} [check] -i INPUT [--validate] [--compat PREVIOUS_INPUT] [-r RUST_TARGET [--no-std]] [-p PYTHON_TARGET] [-t TYPESCRIPT_TARGET] [-d DOC_TARGET] [-w WIRESHARK_TARGET] [-c C_SOURCE] [-c C_HEADER] [--c-platform PLATFORM] [--c-no-alloc]",
+                    argv[0]
+                ))
+            ))),
         }
     }
 }
 
+/// Parses and validates an input file
+pub fn parse_input_file(input_file: File, path: &str) -> Result<CodesFile, Error> {
+    let codes: CodesFile =
+        serde_yaml::from_reader(input_file).map_err(|error| Error::parse(path, error))?;
+    codes.validate().map_err(|errors| Error::Validation {
+        path: path.to_string(),
+        errors,
+    })?;
+    Ok(codes)
+}
+
+/// Compares the input file with a previous revision of it
+pub fn check_compatibility(
+    input_file: File,
+    path: &str,
+    previous_path: &str,
+) -> Result<CompatibilityReport, Error> {
+    let previous_file = File::open(previous_path).map_err(|source| Error::Io {
+        path: previous_path.to_string(),
+        source,
+    })?;
+    let previous = parse_input_file(previous_file, previous_path)?;
+    Ok(parse_input_file(input_file, path)?.changes_since(&previous))
+}
+
 pub fn parse_input_file_and_generate_outputs(
     input_file: File,
     opts: Arguments,
-) -> Result<(), Box<dyn error::Error>> {
-    let input_file_content: yaml_parser::CodesFile = serde_yaml::from_reader(input_file)?;
+) -> Result<(), Error> {
+    let input_file_content = parse_input_file(input_file, &opts.input)?;
+    if opts.validate {
+        return Ok(());
+    }
 
     if let Some(rust) = opts.rust_source {
-        let mut builder = RustFileGenerator::new(rust)?;
-        builder.build_file(&input_file_content)?;
+        generate(
+            &rust,
+            RustFileGenerator::new(rust.clone(), opts.rust_no_std),
+            &input_file_content,
+        )?;
+    }
+    if let Some(python) = opts.python_source {
+        generate(
+            &python,
+            PythonFileGenerator::new(python.clone()),
+            &input_file_content,
+        )?;
+    }
+    if let Some(typescript) = opts.typescript_source {
+        generate(
+            &typescript,
+            TypeScriptFileGenerator::new(typescript.clone()),
+            &input_file_content,
+        )?;
+    }
+    if let Some(documentation) = opts.documentation {
+        generate(
+            &documentation,
+            MarkdownFileGenerator::new(documentation.clone()),
+            &input_file_content,
+        )?;
+    }
+    if let Some(wireshark) = opts.wireshark_dissector {
+        generate(
+            &wireshark,
+            WiresharkFileGenerator::new(wireshark.clone()),
+            &input_file_content,
+        )?;
+    }
+    if opts.c_no_alloc && (opts.c_header.is_some() || opts.c_source.is_some()) {
+        input_file_content
+            .validate_bounded()
+            .map_err(|errors| Error::Validation {
+                path: opts.input.clone(),
+                errors,
+            })?;
     }
     if let Some(c) = &opts.c_header {
-        let mut builder = CppHeaderGenerator::new(c)?;
-        builder.build_file(&input_file_content)?;
+        generate(
+            c,
+            CppHeaderGenerator::new(c, opts.c_no_alloc),
+            &input_file_content,
+        )?;
     }
     if let Some(c) = &opts.c_source {
-        let mut builder = CppFileGenerator::new(c, &opts.c_header)?;
-        builder.build_file(&input_file_content)?;
+        generate(
+            c,
+            CppFileGenerator::new(c, &opts.c_header, opts.c_platform, opts.c_no_alloc),
+            &input_file_content,
+        )?;
     }
     Ok(())
 }
+
+/// Builds an output file, reporting the failures along with its path
+fn generate(
+    path: &str,
+    builder: Result<impl FileGenerator, io::Error>,
+    codes: &yaml_parser::CodesFile,
+) -> Result<(), Error> {
+    builder
+        .and_then(|mut builder| builder.build_file(codes))
+        .map_err(|source| Error::Generation {
+            path: path.to_string(),
+            source,
+        })
+}
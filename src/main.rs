@@ -1,11 +1,43 @@
-use codes_parser::{Arguments, parse_input_file_and_generate_outputs};
+use codes_parser::{Arguments, Error, check_compatibility, parse_input_file_and_generate_outputs};
 use std::fs::File;
 
+/// Exit status of the `--compat` mode when breaking changes are found
+const BREAKING_CHANGES_EXIT_CODE: i32 = 6;
+
 fn main() {
     let commandline_arguments: Vec<String> = std::env::args().collect();
-    let opts = Arguments::new(commandline_arguments).unwrap_or_else(|_| std::process::exit(0));
+    let opts = Arguments::new(commandline_arguments).unwrap_or_else(|error| {
+        eprintln!("{error}");
+        std::process::exit(error.exit_code());
+    });
+
+    let input = opts.input.clone();
+    let validate = opts.validate;
+    let input_file = File::open(&input).map_err(|source| Error::Io {
+        path: input.clone(),
+        source,
+    });
 
-    let input_file = File::open(&opts.input).expect("Error openning input file");
+    let result = match opts.compat.clone() {
+        Some(previous) => input_file
+            .and_then(|input_file| check_compatibility(input_file, &input, &previous))
+            .map(|report| {
+                print!("{}", serde_yaml::to_string(&report).unwrap());
+                if !report.compatible {
+                    std::process::exit(BREAKING_CHANGES_EXIT_CODE);
+                }
+            }),
+        None => input_file
+            .and_then(|input_file| parse_input_file_and_generate_outputs(input_file, opts))
+            .map(|()| {
+                if validate {
+                    println!("{input} is valid");
+                }
+            }),
+    };
 
-    parse_input_file_and_generate_outputs(input_file, opts).expect("Failed processing file");
+    if let Err(error) = result {
+        eprintln!("{error}");
+        std::process::exit(error.exit_code());
+    }
 }
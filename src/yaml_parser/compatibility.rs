@@ -0,0 +1,466 @@
+use crate::yaml_parser::{BitfieldField, CodesFile, InstFeedback, InstFeedbackParameter};
+use serde_derive::Serialize;
+use std::fmt::Display;
+
+/// Whether the tools built from the previous revision still understand the new one
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Compatibility {
+    Compatible,
+    Breaking,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    EndiannessChanged,
+    CodeWidthChanged,
+    ChecksumChanged,
+    FramingChanged,
+    CodeAdded,
+    CodeRemoved,
+    CodeRenamed,
+    FrameAdded,
+    FrameRemoved,
+    ParameterAdded,
+    ParameterRemoved,
+    ParameterRenamed,
+    ParametersReordered,
+    ParameterTypeChanged,
+    ParameterOptionalityChanged,
+    ParameterBoundsChanged,
+    EnumTypeChanged,
+    EnumValueAdded,
+    EnumValueRemoved,
+    EnumValueRenamed,
+    BitfieldTypeChanged,
+    BitfieldFieldAdded,
+    BitfieldFieldRemoved,
+    BitfieldFieldRenamed,
+}
+
+/// Difference between two revisions of an input file
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Change {
+    /// Dotted path of the changed item, such as `codes.0x05.instruction.readings`
+    pub path: String,
+    pub kind: ChangeKind,
+    pub compatibility: Compatibility,
+    pub description: String,
+}
+
+/// Changes between two revisions of an input file, serialized as the `compat` mode output
+#[derive(Serialize, Debug, PartialEq)]
+pub struct CompatibilityReport {
+    pub compatible: bool,
+    pub changes: Vec<Change>,
+}
+
+impl CodesFile {
+    /// Lists the changes since the `previous` revision. Any change of the frames layout
+    /// is breaking, as the other side can't decode them anymore.
+    pub fn changes_since(&self, previous: &CodesFile) -> CompatibilityReport {
+        let mut changes = Changes(Vec::new());
+        changes.compare_settings(previous, self);
+        changes.compare_codes(previous, self);
+        changes.compare_types(previous, self);
+
+        CompatibilityReport {
+            compatible: changes
+                .0
+                .iter()
+                .all(|c| c.compatibility == Compatibility::Compatible),
+            changes: changes.0,
+        }
+    }
+
+    /// Whether a value of the `type_name` type can be sent in a feedback, directly or
+    /// within a structure
+    fn feedbacks_use_type(&self, type_name: &str) -> bool {
+        self.codes
+            .values()
+            .filter_map(|code| code.feedback.as_ref())
+            .any(|feedback| self.parameters_use_type(feedback, type_name, 0))
+    }
+
+    fn parameters_use_type(&self, structure: &InstFeedback, type_name: &str, depth: usize) -> bool {
+        // Structures can't contain themselves, the depth only guards against invalid files
+        depth <= self.types.len()
+            && structure.parameters.iter().any(|p| {
+                p.data_type.custom_type_name().is_some_and(|name| {
+                    name == type_name
+                        || self.types.get(name).is_some_and(|nested| {
+                            self.parameters_use_type(nested, type_name, depth + 1)
+                        })
+                })
+            })
+    }
+}
+
+struct Changes(Vec<Change>);
+
+impl Changes {
+    fn push(&mut self, path: String, kind: ChangeKind, description: String) {
+        let compatibility = match kind {
+            ChangeKind::CodeAdded
+            | ChangeKind::CodeRenamed
+            | ChangeKind::FrameAdded
+            | ChangeKind::ParameterRenamed
+            | ChangeKind::EnumValueRenamed
+            | ChangeKind::BitfieldFieldAdded
+            | ChangeKind::BitfieldFieldRenamed => Compatibility::Compatible,
+            _ => Compatibility::Breaking,
+        };
+        self.0.push(Change {
+            path,
+            kind,
+            compatibility,
+            description,
+        });
+    }
+
+    fn compare_settings(&mut self, previous: &CodesFile, current: &CodesFile) {
+        if previous.endianness != current.endianness {
+            self.push(
+                "endianness".into(),
+                ChangeKind::EndiannessChanged,
+                format!(
+                    "Endianness changed from {} to {}",
+                    setting_name(Some(previous.endianness)),
+                    setting_name(Some(current.endianness))
+                ),
+            );
+        }
+        if previous.code_width != current.code_width {
+            self.push(
+                "code_width".into(),
+                ChangeKind::CodeWidthChanged,
+                format!(
+                    "Code width changed from {} to {}",
+                    previous.code_width.size(),
+                    current.code_width.size()
+                ),
+            );
+        }
+        if previous.checksum != current.checksum {
+            self.push(
+                "checksum".into(),
+                ChangeKind::ChecksumChanged,
+                format!(
+                    "Checksum changed from {} to {}",
+                    setting_name(previous.checksum),
+                    setting_name(current.checksum)
+                ),
+            );
+        }
+        if previous.framing != current.framing {
+            self.push(
+                "framing".into(),
+                ChangeKind::FramingChanged,
+                format!(
+                    "Framing changed from {} to {}",
+                    setting_name(previous.framing),
+                    setting_name(current.framing)
+                ),
+            );
+        }
+    }
+
+    fn compare_codes(&mut self, previous: &CodesFile, current: &CodesFile) {
+        for (id, code) in &previous.codes {
+            let path = format!("codes.{id:#04x}");
+            let Some(new_code) = current.codes.get(id) else {
+                self.push(
+                    path,
+                    ChangeKind::CodeRemoved,
+                    format!("Code {} was removed", code.name),
+                );
+                continue;
+            };
+
+            if code.name != new_code.name {
+                self.push(
+                    format!("{path}.name"),
+                    ChangeKind::CodeRenamed,
+                    format!("Code {} was renamed {}", code.name, new_code.name),
+                );
+            }
+            for (frame_type, frame, new_frame) in [
+                ("instruction", &code.instruction, &new_code.instruction),
+                ("feedback", &code.feedback, &new_code.feedback),
+            ] {
+                let path = format!("{path}.{frame_type}");
+                match (frame, new_frame) {
+                    (Some(frame), Some(new_frame)) => {
+                        self.compare_parameters(&path, frame, new_frame)
+                    }
+                    (Some(_), None) => self.push(
+                        path,
+                        ChangeKind::FrameRemoved,
+                        format!("The {frame_type} of {} was removed", code.name),
+                    ),
+                    (None, Some(_)) => self.push(
+                        path,
+                        ChangeKind::FrameAdded,
+                        format!("The {frame_type} of {} was added", new_code.name),
+                    ),
+                    (None, None) => (),
+                }
+            }
+        }
+
+        for (id, code) in &current.codes {
+            if !previous.codes.contains_key(id) {
+                self.push(
+                    format!("codes.{id:#04x}"),
+                    ChangeKind::CodeAdded,
+                    format!("Code {} was added", code.name),
+                );
+            }
+        }
+    }
+
+    /// Compares the parameters of a frame or structure, a parameter at the same position
+    /// with the same type and an unknown name being renamed
+    fn compare_parameters(&mut self, path: &str, previous: &InstFeedback, current: &InstFeedback) {
+        let find = |parameters: &[InstFeedbackParameter], name: &str| {
+            parameters.iter().position(|p| p.name == name)
+        };
+        let renamed = |(old, new): &(&InstFeedbackParameter, &InstFeedbackParameter)| {
+            old.name != new.name
+                && old.data_type == new.data_type
+                && old.optional == new.optional
+                && find(&current.parameters, &old.name).is_none()
+                && find(&previous.parameters, &new.name).is_none()
+        };
+        let renames = previous
+            .parameters
+            .iter()
+            .zip(&current.parameters)
+            .filter(renamed)
+            .collect::<Vec<(&InstFeedbackParameter, &InstFeedbackParameter)>>();
+        let new_name = |name: &str| {
+            renames
+                .iter()
+                .find(|(old, _)| old.name == name)
+                .map_or(name.to_string(), |(_, new)| new.name.clone())
+        };
+
+        for (old, new) in &renames {
+            self.push(
+                format!("{path}.{}", new.name),
+                ChangeKind::ParameterRenamed,
+                format!("Parameter {} was renamed {}", old.name, new.name),
+            );
+        }
+
+        let mut kept = Vec::new();
+        for parameter in &previous.parameters {
+            let name = new_name(&parameter.name);
+            let Some(position) = find(&current.parameters, &name) else {
+                self.push(
+                    format!("{path}.{name}"),
+                    ChangeKind::ParameterRemoved,
+                    format!("Parameter {name} was removed"),
+                );
+                continue;
+            };
+            kept.push(position);
+
+            let new_parameter = &current.parameters[position];
+            if parameter.data_type != new_parameter.data_type {
+                self.push(
+                    format!("{path}.{name}"),
+                    ChangeKind::ParameterTypeChanged,
+                    format!(
+                        "Parameter {name} type changed from {} to {}",
+                        parameter.data_type.to_input_string(),
+                        new_parameter.data_type.to_input_string()
+                    ),
+                );
+            }
+            if parameter.optional != new_parameter.optional {
+                self.push(
+                    format!("{path}.{name}"),
+                    ChangeKind::ParameterOptionalityChanged,
+                    format!(
+                        "Parameter {name} is {} optional",
+                        if new_parameter.optional {
+                            "now"
+                        } else {
+                            "no longer"
+                        }
+                    ),
+                );
+            }
+            // The allocation-free C code rejects the strings and arrays over its bounds
+            for (bound, value, new_value) in [
+                ("max_length", parameter.max_length, new_parameter.max_length),
+                ("max_count", parameter.max_count, new_parameter.max_count),
+            ] {
+                if value != new_value {
+                    self.push(
+                        format!("{path}.{name}"),
+                        ChangeKind::ParameterBoundsChanged,
+                        format!(
+                            "Parameter {name} {bound} changed from {} to {}",
+                            setting_name(value),
+                            setting_name(new_value)
+                        ),
+                    );
+                }
+            }
+        }
+
+        if kept.windows(2).any(|w| w[0] > w[1]) {
+            self.push(
+                path.to_string(),
+                ChangeKind::ParametersReordered,
+                "Parameters were reordered".to_string(),
+            );
+        }
+
+        for (position, parameter) in current.parameters.iter().enumerate() {
+            if !kept.contains(&position) {
+                self.push(
+                    format!("{path}.{}", parameter.name),
+                    ChangeKind::ParameterAdded,
+                    format!("Parameter {} was added", parameter.name),
+                );
+            }
+        }
+    }
+
+    /// Compares the types declared in both revisions, the removed or added types being
+    /// reported by the parameters using them
+    fn compare_types(&mut self, previous: &CodesFile, current: &CodesFile) {
+        for (name, enumeration) in &previous.enums {
+            let Some(new_enumeration) = current.enums.get(name) else {
+                continue;
+            };
+            let path = format!("enums.{name}");
+            if enumeration.data_type != new_enumeration.data_type {
+                self.push(
+                    path.clone(),
+                    ChangeKind::EnumTypeChanged,
+                    format!(
+                        "Enumeration {name} type changed from {} to {}",
+                        enumeration.data_type.to_input_string(),
+                        new_enumeration.data_type.to_input_string()
+                    ),
+                );
+            }
+            for value in &enumeration.values {
+                match new_enumeration
+                    .values
+                    .iter()
+                    .find(|v| v.value == value.value)
+                {
+                    None => self.push(
+                        format!("{path}.{}", value.name),
+                        ChangeKind::EnumValueRemoved,
+                        format!("Value {} ({}) was removed", value.name, value.value),
+                    ),
+                    Some(new_value) if new_value.name != value.name => self.push(
+                        format!("{path}.{}", new_value.name),
+                        ChangeKind::EnumValueRenamed,
+                        format!("Value {} was renamed {}", value.name, new_value.name),
+                    ),
+                    Some(_) => (),
+                }
+            }
+            for value in &new_enumeration.values {
+                if enumeration.values.iter().all(|v| v.value != value.value) {
+                    let mut change = Change {
+                        path: format!("{path}.{}", value.name),
+                        kind: ChangeKind::EnumValueAdded,
+                        compatibility: Compatibility::Compatible,
+                        description: format!("Value {} ({}) was added", value.name, value.value),
+                    };
+                    // The previous revision rejects the unknown values it receives
+                    if current.feedbacks_use_type(name) {
+                        change.compatibility = Compatibility::Breaking;
+                        change.description += ", and can be sent in feedbacks";
+                    }
+                    self.0.push(change);
+                }
+            }
+        }
+
+        for (name, bitfield) in &previous.bitfields {
+            let Some(new_bitfield) = current.bitfields.get(name) else {
+                continue;
+            };
+            let path = format!("bitfields.{name}");
+            if bitfield.data_type != new_bitfield.data_type {
+                self.push(
+                    path.clone(),
+                    ChangeKind::BitfieldTypeChanged,
+                    format!(
+                        "Bitfield {name} type changed from {} to {}",
+                        bitfield.data_type.to_input_string(),
+                        new_bitfield.data_type.to_input_string()
+                    ),
+                );
+            }
+            let fields = bitfield.fields_with_offsets();
+            let new_fields = new_bitfield.fields_with_offsets();
+            let same_bits =
+                |(field, offset): &(&BitfieldField, u32),
+                 (other, other_offset): &(&BitfieldField, u32)| {
+                    field.bits == other.bits && offset == other_offset
+                };
+            for field in &fields {
+                match new_fields
+                    .iter()
+                    .find(|new_field| same_bits(field, new_field))
+                {
+                    None => self.push(
+                        format!("{path}.{}", field.0.name),
+                        ChangeKind::BitfieldFieldRemoved,
+                        format!(
+                            "Field {} on bits {}..{} was removed or moved",
+                            field.0.name,
+                            field.1,
+                            field.1 + field.0.bits
+                        ),
+                    ),
+                    Some(new_field) if new_field.0.name != field.0.name => self.push(
+                        format!("{path}.{}", new_field.0.name),
+                        ChangeKind::BitfieldFieldRenamed,
+                        format!("Field {} was renamed {}", field.0.name, new_field.0.name),
+                    ),
+                    Some(_) => (),
+                }
+            }
+            for new_field in &new_fields {
+                if !fields.iter().any(|field| same_bits(field, new_field)) {
+                    self.push(
+                        format!("{path}.{}", new_field.0.name),
+                        ChangeKind::BitfieldFieldAdded,
+                        format!(
+                            "Field {} was added on bits {}..{}",
+                            new_field.0.name,
+                            new_field.1,
+                            new_field.1 + new_field.0.bits
+                        ),
+                    );
+                }
+            }
+        }
+
+        for (name, structure) in &previous.types {
+            if let Some(new_structure) = current.types.get(name) {
+                self.compare_parameters(&format!("types.{name}"), structure, new_structure);
+            }
+        }
+    }
+}
+
+/// Optional value as written in the input file
+fn setting_name(value: Option<impl Display>) -> String {
+    match value {
+        Some(value) => value.to_string(),
+        None => "none".to_string(),
+    }
+}
@@ -4,9 +4,13 @@ use std::fmt;
 use serde::de::{self, Deserializer, Unexpected, Visitor};
 use serde_derive::Deserialize;
 
+mod compatibility;
 mod types;
+mod validation;
 
+pub use compatibility::{Change, ChangeKind, Compatibility, CompatibilityReport};
 pub use types::ParameterType;
+pub use validation::{ValidationError, ValidationErrors};
 
 struct ParameterTypeVisitor;
 impl<'de> Visitor<'de> for ParameterTypeVisitor {
@@ -41,6 +45,17 @@ pub struct InstFeedbackParameter {
     pub name: String,
     pub description: String,
     pub data_type: ParameterType,
+    /// Optional parameters are preceded by a presence byte, and only sent when present
+    #[serde(default)]
+    pub optional: bool,
+    /// Largest number of characters of a string, or of each string of an array, stored in
+    /// place by the allocation-free C code
+    #[serde(default)]
+    pub max_length: Option<usize>,
+    /// Largest number of items of a variable-length array, stored in place by the
+    /// allocation-free C code
+    #[serde(default)]
+    pub max_count: Option<usize>,
 }
 
 #[derive(Deserialize, Debug, Clone, PartialEq)]
@@ -56,24 +71,253 @@ pub struct Codes {
     pub feedback: Option<InstFeedback>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct EnumValue {
+    pub name: String,
+    pub description: String,
+    pub value: i64,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct EnumType {
+    pub description: String,
+    pub data_type: ParameterType,
+    pub values: Vec<EnumValue>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct BitfieldField {
+    pub name: String,
+    pub description: String,
+    pub bits: u32,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct BitfieldType {
+    pub description: String,
+    pub data_type: ParameterType,
+    /// Fields packed from the least significant bit
+    pub fields: Vec<BitfieldField>,
+}
+
+/// Byte order of the multi-bytes values on the wire
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Endianness {
+    #[default]
+    Little,
+    Big,
+}
+
+/// Name of the setting value in the input file
+impl fmt::Display for Endianness {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Endianness::Little => "little",
+            Endianness::Big => "big",
+        })
+    }
+}
+
+/// Number of bytes of the code starting every frame, in the wire byte order
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(try_from = "u8")]
+pub enum CodeWidth {
+    #[default]
+    One,
+    Two,
+    Four,
+}
+
+impl TryFrom<u8> for CodeWidth {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(CodeWidth::One),
+            2 => Ok(CodeWidth::Two),
+            4 => Ok(CodeWidth::Four),
+            _ => Err(format!("code_width must be 1, 2 or 4, not {value}")),
+        }
+    }
+}
+
+impl CodeWidth {
+    /// Number of bytes of the code
+    pub fn size(&self) -> usize {
+        self.data_type().size()
+    }
+
+    /// Unsigned type the code is encoded with
+    pub fn data_type(&self) -> ParameterType {
+        match self {
+            CodeWidth::One => ParameterType::Uint8,
+            CodeWidth::Two => ParameterType::Uint16,
+            CodeWidth::Four => ParameterType::Uint32,
+        }
+    }
+
+    /// Largest code fitting in the width
+    pub fn max_code(&self) -> u32 {
+        match self {
+            CodeWidth::One => u8::MAX as u32,
+            CodeWidth::Two => u16::MAX as u32,
+            CodeWidth::Four => u32::MAX,
+        }
+    }
+}
+
+/// Checksum appended to every frame
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Checksum {
+    /// CRC-8 with polynomial 0x07
+    Crc8,
+    /// CRC-16/CCITT-FALSE
+    #[serde(alias = "crc16-ccitt")]
+    Crc16,
+    /// CRC-32 as used by Ethernet and zlib
+    Crc32,
+}
+
+/// Name of the setting value in the input file
+impl fmt::Display for Checksum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Checksum::Crc8 => "crc8",
+            Checksum::Crc16 => "crc16",
+            Checksum::Crc32 => "crc32",
+        })
+    }
+}
+
+impl Checksum {
+    /// Number of bytes of the checksum
+    pub fn size(&self) -> usize {
+        match self {
+            Checksum::Crc8 => 1,
+            Checksum::Crc16 => 2,
+            Checksum::Crc32 => 4,
+        }
+    }
+}
+
+/// Delimitation of the frames sent on a byte stream
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Framing {
+    /// Consistent Overhead Byte Stuffing, frames end with a zero byte
+    Cobs,
+    /// Serial Line Internet Protocol, frames are enclosed by 0xC0 bytes
+    Slip,
+    /// Sync byte 0xAA followed by the frame length on two bytes
+    #[serde(rename = "sync")]
+    SyncLength,
+}
+
+/// Name of the setting value in the input file
+impl fmt::Display for Framing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Framing::Cobs => "cobs",
+            Framing::Slip => "slip",
+            Framing::SyncLength => "sync",
+        })
+    }
+}
+
+#[derive(Deserialize, Debug, Default)]
 pub struct CodesFile {
+    #[serde(default)]
+    pub endianness: Endianness,
+    #[serde(default)]
+    pub code_width: CodeWidth,
+    #[serde(default)]
+    pub checksum: Option<Checksum>,
+    #[serde(default)]
+    pub framing: Option<Framing>,
+    #[serde(default)]
+    pub enums: BTreeMap<String, EnumType>,
+    #[serde(default)]
+    pub bitfields: BTreeMap<String, BitfieldType>,
+    /// Structures shared between frames, described like the frames parameters
+    #[serde(default)]
+    pub types: BTreeMap<String, InstFeedback>,
     pub codes: BTreeMap<u32, Codes>,
 }
 
 impl InstFeedbackParameter {
     pub fn c_parameter_definition(&self) -> String {
-        format!("{} {}", self.data_type.to_cpp_type_string(), self.name)
-    }
-
-    pub fn c_parameter_definition_with_comment(&self) -> String {
         format!(
-            "{} {};\t// {}",
+            "{} {}{}",
             self.data_type.to_cpp_type_string(),
             self.name,
-            self.description
+            self.data_type.c_array_suffix()
         )
     }
+
+    /// Definition of the C field storing the strings and the variable-length arrays in
+    /// place, up to their `max_length` and `max_count`
+    pub fn c_bounded_parameter_definition(&self) -> String {
+        let string_suffix = format!("[{}]", self.max_length.unwrap_or_default() + 1);
+        let (item, suffix) = match &self.data_type {
+            ParameterType::String => return format!("char {}{string_suffix}", self.name),
+            ParameterType::Array { item, .. } => {
+                (item, format!("[{}]", self.max_count.unwrap_or_default()))
+            }
+            ParameterType::FixedArray { item, count } => (item, format!("[{count}]")),
+            _ => return self.c_parameter_definition(),
+        };
+        if **item == ParameterType::String {
+            format!("char {}{suffix}{string_suffix}", self.name)
+        } else {
+            format!("{} {}{suffix}", item.to_cpp_type_string(), self.name)
+        }
+    }
+
+    pub fn c_parameter_definition_with_comment(&self) -> String {
+        self.c_field_with_comment(self.c_parameter_definition())
+    }
+
+    pub fn c_bounded_parameter_definition_with_comment(&self) -> String {
+        self.c_field_with_comment(self.c_bounded_parameter_definition())
+    }
+
+    /// Field `definition` along with its description, and the count and presence fields
+    /// generated for the parameter
+    fn c_field_with_comment(&self, definition: String) -> String {
+        let definition = format!("{definition};\t// {}", self.description);
+
+        let definition = if let ParameterType::Array { .. } = self.data_type {
+            format!(
+                "{definition}\n\tsize_t {};\t// Number of items in {}",
+                self.c_array_count_name(),
+                self.name
+            )
+        } else {
+            definition
+        };
+
+        if self.optional {
+            format!(
+                "{definition}\n\tbool {};\t// Whether {} is present",
+                self.c_presence_name(),
+                self.name
+            )
+        } else {
+            definition
+        }
+    }
+
+    /// Name of the C field telling whether an optional parameter is present
+    pub fn c_presence_name(&self) -> String {
+        format!("has_{}", self.name)
+    }
+
+    /// Name of the C field holding the number of items of an array parameter
+    pub fn c_array_count_name(&self) -> String {
+        format!("{}_count", self.name)
+    }
 }
 
 impl Codes {
@@ -90,7 +334,91 @@ impl Codes {
     }
 }
 
+impl EnumType {
+    /// Range of the values held by the underlying type, which must be an integer up to
+    /// 32 bits
+    fn value_range(&self) -> Option<(i64, i64)> {
+        match self.data_type {
+            ParameterType::Uint8 => Some((0, u8::MAX as i64)),
+            ParameterType::Uint16 => Some((0, u16::MAX as i64)),
+            ParameterType::Uint32 => Some((0, u32::MAX as i64)),
+            ParameterType::Int8 => Some((i8::MIN as i64, i8::MAX as i64)),
+            ParameterType::Int16 => Some((i16::MIN as i64, i16::MAX as i64)),
+            ParameterType::Int32 => Some((i32::MIN as i64, i32::MAX as i64)),
+            _ => None,
+        }
+    }
+}
+
+impl BitfieldType {
+    /// Number of bits of the underlying type, which must be an unsigned integer
+    fn width(&self) -> Option<u32> {
+        match self.data_type {
+            ParameterType::Uint8
+            | ParameterType::Uint16
+            | ParameterType::Uint32
+            | ParameterType::Uint64 => Some(self.data_type.size() as u32 * 8),
+            _ => None,
+        }
+    }
+
+    /// Fields along with the position of their least significant bit
+    pub fn fields_with_offsets(&self) -> Vec<(&BitfieldField, u32)> {
+        self.fields
+            .iter()
+            .scan(0, |offset, field| {
+                let field_offset = *offset;
+                *offset += field.bits;
+                Some((field, field_offset))
+            })
+            .collect()
+    }
+}
+
+impl BitfieldField {
+    /// Mask of the field bits, once shifted to the least significant bit
+    pub fn mask(&self) -> u64 {
+        u64::MAX >> (64 - self.bits)
+    }
+}
+
 impl CodesFile {
+    /// Structure types sorted so that each one comes after the types of its fields
+    pub fn types_in_dependency_order(&self) -> Result<Vec<(&String, &InstFeedback)>, String> {
+        let mut sorted: Vec<(&String, &InstFeedback)> = Vec::new();
+        while sorted.len() < self.types.len() {
+            let next = self.types.iter().find(|(name, structure)| {
+                !sorted.iter().any(|(sorted_name, _)| sorted_name == name)
+                    && structure.parameters.iter().all(|p| {
+                        p.data_type.custom_type_name().is_none_or(|field_type| {
+                            !self.types.contains_key(field_type)
+                                || sorted
+                                    .iter()
+                                    .any(|(sorted_name, _)| *sorted_name == field_type)
+                        })
+                    })
+            });
+
+            match next {
+                Some(structure) => sorted.push(structure),
+                None => {
+                    return Err(format!(
+                        "types {} recursively contain each other",
+                        self.types
+                            .keys()
+                            .filter(|name| !sorted
+                                .iter()
+                                .any(|(sorted_name, _)| sorted_name == name))
+                            .cloned()
+                            .collect::<Vec<String>>()
+                            .join(", ")
+                    ));
+                }
+            }
+        }
+        Ok(sorted)
+    }
+
     pub fn get_instructions(&self) -> Vec<(u32, String, InstFeedback)> {
         self.codes
             .iter()
@@ -112,7 +440,14 @@ impl CodesFile {
             })
             .collect()
     }
+
+    /// Whether the `code` has a feedback, answering its instruction
+    pub fn has_feedback(&self, code: u32) -> bool {
+        self.codes
+            .get(&code)
+            .is_some_and(|codes| codes.feedback.is_some())
+    }
 }
 
 #[cfg(test)]
-mod test;
+pub(crate) mod test;
@@ -21,6 +21,11 @@ codes:
     serde_yaml::from_str(test_input).unwrap()
 }
 
+/// The codes file of the integration tests, using every type in both directions
+pub fn integration_codes_file() -> CodesFile {
+    serde_yaml::from_str(include_str!("../../integration_tests/test.yml")).unwrap()
+}
+
 mod parsing {
     use crate::yaml_parser::types::*;
     use std::convert::TryFrom;
@@ -72,7 +77,7 @@ mod parsing {
         expect: ParameterType,
     ) -> Result<(), String> {
         let test_results = input_iterator
-            .map(|type_string| construct_from_string_and_match_type(type_string, expect));
+            .map(|type_string| construct_from_string_and_match_type(type_string, expect.clone()));
         test_results
             .reduce(|previous, current| previous.and(current))
             .unwrap()
@@ -137,6 +142,104 @@ mod parsing {
         let input_types_iter = ["bool", "Bool", "Boolean", "boolean"].into_iter();
         construct_and_match_type_from_array(Box::new(input_types_iter), ParameterType::Bool)
     }
+
+    #[test]
+    fn type_float32_gives_float32() -> Result<(), String> {
+        let input_types_iter = ["f32", "F32", "float32", "float", "Float"].into_iter();
+        construct_and_match_type_from_array(Box::new(input_types_iter), ParameterType::Float32)
+    }
+
+    #[test]
+    fn type_float64_gives_float64() -> Result<(), String> {
+        let input_types_iter = ["f64", "F64", "float64", "double", "Double"].into_iter();
+        construct_and_match_type_from_array(Box::new(input_types_iter), ParameterType::Float64)
+    }
+
+    #[test]
+    fn type_camel_case_name_gives_custom() -> Result<(), String> {
+        construct_from_string_and_match_type("SleepMode", ParameterType::Custom("SleepMode".into()))
+    }
+
+    #[test]
+    fn type_lower_case_unknown_name_fails() {
+        construct_parameter_from_char_array("sleepmode").unwrap_err();
+    }
+
+    #[test]
+    fn type_custom_name_with_symbols_fails() {
+        construct_parameter_from_char_array("Sleep-Mode").unwrap_err();
+    }
+
+    #[test]
+    fn type_array_of_custom_gives_array() -> Result<(), String> {
+        construct_from_string_and_match_type(
+            "SleepMode[]",
+            ParameterType::Array {
+                item: Box::new(ParameterType::Custom("SleepMode".into())),
+                length: Box::new(ParameterType::Uint8),
+            },
+        )
+    }
+
+    #[test]
+    fn type_array_defaults_to_uint8_length() -> Result<(), String> {
+        let input_types_iter = ["u16[]", "Uint16[]", "u16[u8]", "uint16[byte]"].into_iter();
+        construct_and_match_type_from_array(
+            Box::new(input_types_iter),
+            ParameterType::Array {
+                item: Box::new(ParameterType::Uint16),
+                length: Box::new(ParameterType::Uint8),
+            },
+        )
+    }
+
+    #[test]
+    fn type_array_with_length_type() -> Result<(), String> {
+        construct_from_string_and_match_type(
+            "string[u32]",
+            ParameterType::Array {
+                item: Box::new(ParameterType::String),
+                length: Box::new(ParameterType::Uint32),
+            },
+        )
+    }
+
+    #[test]
+    fn type_array_with_signed_length_fails() {
+        construct_parameter_from_char_array("u8[i16]").unwrap_err();
+    }
+
+    #[test]
+    fn type_fixed_array_gives_fixed_array() -> Result<(), String> {
+        let input_types_iter = ["u8[16]", "byte[16]", "Uint8[16]"].into_iter();
+        construct_and_match_type_from_array(
+            Box::new(input_types_iter),
+            ParameterType::FixedArray {
+                item: Box::new(ParameterType::Uint8),
+                count: 16,
+            },
+        )
+    }
+
+    #[test]
+    fn type_empty_fixed_array_fails() {
+        construct_parameter_from_char_array("u8[0]").unwrap_err();
+    }
+
+    #[test]
+    fn type_nested_fixed_array_fails() {
+        construct_parameter_from_char_array("u8[4][2]").unwrap_err();
+    }
+
+    #[test]
+    fn type_nested_array_fails() {
+        construct_parameter_from_char_array("u8[][]").unwrap_err();
+    }
+
+    #[test]
+    fn type_array_with_unbalanced_brackets_fails() {
+        construct_parameter_from_char_array("u8]").unwrap_err();
+    }
 }
 
 mod display {
@@ -189,6 +292,18 @@ mod display {
         Ok(())
     }
 
+    #[test]
+    fn float32_displays_float32() -> Result<(), String> {
+        assert_eq!("Float32", ParameterType::Float32.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn float64_displays_float64() -> Result<(), String> {
+        assert_eq!("Float64", ParameterType::Float64.to_string());
+        Ok(())
+    }
+
     #[test]
     fn bool_displays_bool() -> Result<(), String> {
         assert_eq!("Bool", ParameterType::Bool.to_string());
@@ -200,6 +315,26 @@ mod display {
         assert_eq!("String", ParameterType::String.to_string());
         Ok(())
     }
+
+    #[test]
+    fn array_displays_item_and_length() -> Result<(), String> {
+        let array = ParameterType::Array {
+            item: Box::new(ParameterType::Int32),
+            length: Box::new(ParameterType::Uint16),
+        };
+        assert_eq!("Int32[Uint16]", array.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn fixed_array_displays_item_and_count() -> Result<(), String> {
+        let array = ParameterType::FixedArray {
+            item: Box::new(ParameterType::Uint8),
+            count: 6,
+        };
+        assert_eq!("Uint8[6]", array.to_string());
+        Ok(())
+    }
 }
 
 mod size {
@@ -245,6 +380,16 @@ mod size {
         assert_eq!(8, ParameterType::Int64.size());
     }
 
+    #[test]
+    fn float32_size_is_4_byte() {
+        assert_eq!(4, ParameterType::Float32.size());
+    }
+
+    #[test]
+    fn float64_size_is_8_byte() {
+        assert_eq!(8, ParameterType::Float64.size());
+    }
+
     #[test]
     fn bool_size_is_1_byte() {
         assert_eq!(1, ParameterType::Bool.size());
@@ -254,6 +399,24 @@ mod size {
     fn string_size_is_unknown_bytes_long() {
         assert_eq!(0, ParameterType::String.size());
     }
+
+    #[test]
+    fn array_size_is_unknown_bytes_long() {
+        let array = ParameterType::Array {
+            item: Box::new(ParameterType::Uint8),
+            length: Box::new(ParameterType::Uint8),
+        };
+        assert_eq!(0, array.size());
+    }
+
+    #[test]
+    fn fixed_array_size_is_items_size_times_count() {
+        let array = ParameterType::FixedArray {
+            item: Box::new(ParameterType::Uint16),
+            count: 3,
+        };
+        assert_eq!(6, array.size());
+    }
 }
 
 mod decoder {
@@ -340,6 +503,9 @@ mod parameter {
             name: "parameter_name".into(),
             description: "parameters description".into(),
             data_type: ParameterType::Uint32,
+            optional: false,
+            max_length: None,
+            max_count: None,
         };
 
         assert_eq!(
@@ -355,6 +521,9 @@ mod parameter {
             name: "parameter_name".into(),
             description: "parameter description".into(),
             data_type: ParameterType::Uint32,
+            optional: false,
+            max_length: None,
+            max_count: None,
         };
 
         assert_eq!(
@@ -363,6 +532,99 @@ mod parameter {
         );
         Ok(())
     }
+
+    #[test]
+    fn generate_fixed_array_parameter_c_string() -> Result<(), String> {
+        let parameter = InstFeedbackParameter {
+            name: "uuid".into(),
+            description: "parameter description".into(),
+            data_type: ParameterType::FixedArray {
+                item: Box::new(ParameterType::Uint8),
+                count: 16,
+            },
+            optional: false,
+            max_length: None,
+            max_count: None,
+        };
+
+        assert_eq!("uint8_t uuid[16]", parameter.c_parameter_definition());
+        Ok(())
+    }
+
+    #[test]
+    fn generate_array_parameter_c_string_with_count() -> Result<(), String> {
+        let parameter = InstFeedbackParameter {
+            name: "values".into(),
+            description: "parameter description".into(),
+            data_type: ParameterType::Array {
+                item: Box::new(ParameterType::Uint16),
+                length: Box::new(ParameterType::Uint8),
+            },
+            optional: false,
+            max_length: None,
+            max_count: None,
+        };
+
+        assert_eq!(
+            "uint16_t * values;\t// parameter description\n\tsize_t values_count;\t// Number of items in values",
+            parameter.c_parameter_definition_with_comment()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn generate_bounded_string_parameter_c_string() -> Result<(), String> {
+        let parameter = InstFeedbackParameter {
+            name: "label".into(),
+            description: "parameter description".into(),
+            data_type: ParameterType::String,
+            optional: false,
+            max_length: Some(16),
+            max_count: None,
+        };
+
+        assert_eq!("char label[17]", parameter.c_bounded_parameter_definition());
+        Ok(())
+    }
+
+    #[test]
+    fn generate_bounded_string_array_parameter_c_string_with_count() -> Result<(), String> {
+        let parameter = InstFeedbackParameter {
+            name: "labels".into(),
+            description: "parameter description".into(),
+            data_type: ParameterType::Array {
+                item: Box::new(ParameterType::String),
+                length: Box::new(ParameterType::Uint8),
+            },
+            optional: false,
+            max_length: Some(16),
+            max_count: Some(4),
+        };
+
+        assert_eq!(
+            "char labels[4][17];\t// parameter description\n\tsize_t labels_count;\t// Number of items in labels",
+            parameter.c_bounded_parameter_definition_with_comment()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn generate_optional_parameter_c_string_with_presence() -> Result<(), String> {
+        let parameter = InstFeedbackParameter {
+            name: "level".into(),
+            description: "parameter description".into(),
+            data_type: ParameterType::Uint8,
+            optional: true,
+            max_length: None,
+            max_count: None,
+        };
+
+        assert_eq!(
+            "uint8_t level;\t// parameter description\n\tbool has_level;\t// Whether level is present",
+            parameter.c_parameter_definition_with_comment()
+        );
+        Ok(())
+    }
 }
 
 mod codes {
@@ -405,11 +667,17 @@ mod codes {
                         name: "param1".into(),
                         description: "first parameter".into(),
                         data_type: ParameterType::Int16,
+                        optional: false,
+                        max_length: None,
+                        max_count: None,
                     },
                     InstFeedbackParameter {
                         name: "param2".into(),
                         description: "second parameter".into(),
                         data_type: ParameterType::Bool,
+                        optional: false,
+                        max_length: None,
+                        max_count: None,
                     },
                 ]
                 .into(),
@@ -443,11 +711,17 @@ mod codes {
                         name: "param1".into(),
                         description: "first parameter".into(),
                         data_type: ParameterType::Int16,
+                        optional: false,
+                        max_length: None,
+                        max_count: None,
                     },
                     InstFeedbackParameter {
                         name: "param2".into(),
                         description: "second parameter".into(),
                         data_type: ParameterType::Bool,
+                        optional: false,
+                        max_length: None,
+                        max_count: None,
                     },
                 ]
                 .into(),
@@ -482,9 +756,1067 @@ mod codes_file {
                     feedback: None,
                 },
             )]),
+            ..Default::default()
         };
 
         let codes = cf_under_test.get_instructions();
         assert!(codes.is_empty());
     }
+
+    #[test]
+    fn endianness_defaults_to_little() {
+        assert_eq!(Endianness::Little, test::parsed_code_file().endianness);
+    }
+
+    #[test]
+    fn parse_big_endianness() {
+        let parsed: CodesFile = serde_yaml::from_str("endianness: big\ncodes: {}").unwrap();
+
+        assert_eq!(Endianness::Big, parsed.endianness);
+    }
+
+    #[test]
+    fn checksum_defaults_to_none() {
+        assert_eq!(None, test::parsed_code_file().checksum);
+    }
+
+    #[test]
+    fn parse_checksums() {
+        for (name, expected, size) in [
+            ("crc8", Checksum::Crc8, 1),
+            ("crc16", Checksum::Crc16, 2),
+            ("crc16-ccitt", Checksum::Crc16, 2),
+            ("crc32", Checksum::Crc32, 4),
+        ] {
+            let parsed: CodesFile =
+                serde_yaml::from_str(&format!("checksum: {name}\ncodes: {{}}")).unwrap();
+
+            assert_eq!(Some(expected), parsed.checksum);
+            assert_eq!(size, expected.size());
+        }
+    }
+
+    #[test]
+    fn code_width_defaults_to_one_byte() {
+        assert_eq!(CodeWidth::One, test::parsed_code_file().code_width);
+    }
+
+    #[test]
+    fn parse_code_widths() {
+        for (width, expected, data_type) in [
+            (1, CodeWidth::One, ParameterType::Uint8),
+            (2, CodeWidth::Two, ParameterType::Uint16),
+            (4, CodeWidth::Four, ParameterType::Uint32),
+        ] {
+            let parsed: CodesFile =
+                serde_yaml::from_str(&format!("code_width: {width}\ncodes: {{}}")).unwrap();
+
+            assert_eq!(expected, parsed.code_width);
+            assert_eq!(width, expected.size());
+            assert_eq!(data_type, expected.data_type());
+        }
+    }
+
+    #[test]
+    fn unsupported_code_width_fails() {
+        let parsed = serde_yaml::from_str::<CodesFile>("code_width: 3\ncodes: {}");
+
+        assert!(parsed.is_err());
+    }
+
+    #[test]
+    fn unknown_endianness_fails() {
+        let parsed = serde_yaml::from_str::<CodesFile>("endianness: middle\ncodes: {}");
+
+        assert!(parsed.is_err());
+    }
+
+    #[test]
+    fn framing_defaults_to_none() {
+        assert_eq!(None, test::parsed_code_file().framing);
+    }
+
+    #[test]
+    fn parse_framings() {
+        for (name, expected) in [
+            ("cobs", Framing::Cobs),
+            ("slip", Framing::Slip),
+            ("sync", Framing::SyncLength),
+        ] {
+            let parsed: CodesFile =
+                serde_yaml::from_str(&format!("framing: {name}\ncodes: {{}}")).unwrap();
+
+            assert_eq!(Some(expected), parsed.framing);
+        }
+    }
+}
+
+mod enums {
+    use crate::yaml_parser::*;
+
+    fn parse_with_enum(enum_type: &str, value: i64, parameter_type: &str) -> CodesFile {
+        let input = format!(
+            "
+enums:
+  SleepMode:
+    description: Sleep depth
+    data_type: {enum_type}
+    values:
+      - name: Light
+        value: 0
+        description: Light sleep
+      - name: Deep
+        value: {value}
+        description: Deep sleep
+codes:
+  0x01:
+    name: Sleep
+    instruction:
+      description: Sleeps
+      parameters:
+        - name: mode
+          data_type: {parameter_type}
+          description: Sleep mode
+"
+        );
+        serde_yaml::from_str(&input).unwrap()
+    }
+
+    #[test]
+    fn parse_enums_section() {
+        let parsed = parse_with_enum("u8", 4, "SleepMode");
+
+        let sleep_mode = &parsed.enums["SleepMode"];
+        assert_eq!(ParameterType::Uint8, sleep_mode.data_type);
+        assert_eq!(2, sleep_mode.values.len());
+        assert_eq!("Deep", sleep_mode.values[1].name);
+        assert_eq!(4, sleep_mode.values[1].value);
+        assert_eq!(Ok(()), parsed.validate());
+    }
+
+    #[test]
+    fn enums_section_is_optional() {
+        let parsed = test::parsed_code_file();
+
+        assert!(parsed.enums.is_empty());
+        // The frame names of this file aren't identifiers, but its types are valid
+        let errors = parsed.validate().unwrap_err().0;
+        assert!(errors.iter().all(|e| e.path.ends_with(".name")));
+    }
+
+    #[test]
+    fn unknown_custom_type_is_rejected() {
+        let parsed = parse_with_enum("u8", 1, "SleepModes[]");
+
+        assert_eq!(
+            Err(ValidationErrors(vec![ValidationError {
+                path: "codes.0x01.instruction.mode".into(),
+                message: "unknown type SleepModes".into()
+            }])),
+            parsed.validate()
+        );
+    }
+
+    #[test]
+    fn enum_value_out_of_range_is_rejected() {
+        let parsed = parse_with_enum("i8", 128, "SleepMode");
+
+        assert_eq!(
+            Err(ValidationErrors(vec![ValidationError {
+                path: "enums.SleepMode.Deep".into(),
+                message: "value 128 doesn't fit in Int8".into()
+            }])),
+            parsed.validate()
+        );
+    }
+
+    #[test]
+    fn enum_based_on_non_integer_is_rejected() {
+        let parsed = parse_with_enum("string", 1, "SleepMode");
+
+        assert_eq!(
+            Err(ValidationErrors(vec![ValidationError {
+                path: "enums.SleepMode".into(),
+                message: "enumeration must be based on an integer type up to 32 bits, not String"
+                    .into()
+            }])),
+            parsed.validate()
+        );
+    }
+}
+
+mod structs {
+    use crate::yaml_parser::*;
+
+    fn parse_with_types(types: &str, parameter_type: &str) -> CodesFile {
+        let input = format!(
+            "
+enums:
+  SleepMode:
+    description: Sleep depth
+    data_type: u8
+    values:
+      - name: Light
+        value: 0
+        description: Light sleep
+types:
+{types}
+codes:
+  0x01:
+    name: SetDate
+    instruction:
+      description: Sets the date
+      parameters:
+        - name: date
+          data_type: {parameter_type}
+          description: Date
+"
+        );
+        serde_yaml::from_str(&input).unwrap()
+    }
+
+    const DATE_TYPES: &str = "
+  Event:
+    description: Dated event
+    parameters:
+      - name: date
+        data_type: RtcDate
+        description: Date of the event
+      - name: mode
+        data_type: SleepMode
+        description: Sleep mode
+  RtcDate:
+    description: Calendar date
+    parameters:
+      - name: year
+        data_type: u16
+        description: Year
+";
+
+    #[test]
+    fn parse_types_section() {
+        let parsed = parse_with_types(DATE_TYPES, "Event[]");
+
+        let event = &parsed.types["Event"];
+        assert_eq!(2, event.parameters.len());
+        assert_eq!(
+            ParameterType::Custom("RtcDate".into()),
+            event.parameters[0].data_type
+        );
+        assert_eq!(Ok(()), parsed.validate());
+    }
+
+    #[test]
+    fn types_are_sorted_by_dependency() {
+        let parsed = parse_with_types(DATE_TYPES, "Event");
+
+        let sorted = parsed
+            .types_in_dependency_order()
+            .unwrap()
+            .into_iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<&str>>();
+
+        assert_eq!(vec!["RtcDate", "Event"], sorted);
+    }
+
+    #[test]
+    fn unknown_field_type_is_rejected() {
+        let parsed = parse_with_types(
+            &DATE_TYPES.replace("data_type: RtcDate", "data_type: Date"),
+            "Event",
+        );
+
+        assert_eq!(
+            Err(ValidationErrors(vec![ValidationError {
+                path: "types.Event.date".into(),
+                message: "unknown type Date".into()
+            }])),
+            parsed.validate()
+        );
+    }
+
+    #[test]
+    fn recursive_types_are_rejected() {
+        let parsed = parse_with_types(
+            &DATE_TYPES.replace("data_type: u16", "data_type: Event[]"),
+            "Event",
+        );
+
+        assert_eq!(
+            Err(ValidationErrors(vec![ValidationError {
+                path: "types".into(),
+                message: "types Event, RtcDate recursively contain each other".into()
+            }])),
+            parsed.validate()
+        );
+    }
+
+    #[test]
+    fn type_declared_as_enum_and_struct_is_rejected() {
+        let parsed = parse_with_types(&DATE_TYPES.replace("RtcDate", "SleepMode"), "Event");
+
+        assert_eq!(
+            Err(ValidationErrors(vec![ValidationError {
+                path: "types.SleepMode".into(),
+                message: "name SleepMode is already used by an enumeration or a bitfield".into()
+            }])),
+            parsed.validate()
+        );
+    }
+
+    #[test]
+    fn type_named_as_a_feedback_structure_is_rejected() {
+        let mut parsed =
+            parse_with_types(&DATE_TYPES.replace("RtcDate", "SetDateFeedback"), "Event");
+        let set_date = parsed.codes.get_mut(&0x01).unwrap();
+        set_date.feedback = set_date.instruction.clone();
+
+        assert_eq!(
+            Err(ValidationErrors(vec![ValidationError {
+                path: "types.SetDateFeedback".into(),
+                message:
+                    "name SetDateFeedback is already used by the feedback structure of code 0x01"
+                        .into()
+            }])),
+            parsed.validate()
+        );
+    }
+
+    #[test]
+    fn enum_named_as_a_feedback_structure_is_rejected() {
+        let mut parsed = parse_with_types(DATE_TYPES, "Event");
+        let set_date = parsed.codes.get_mut(&0x01).unwrap();
+        set_date.feedback = set_date.instruction.clone();
+        let sleep_mode = parsed.enums.remove("SleepMode").unwrap();
+        parsed.enums.insert("SetDateFeedback".into(), sleep_mode);
+        parsed.types.get_mut("Event").unwrap().parameters.pop();
+
+        assert_eq!(
+            Err(ValidationErrors(vec![ValidationError {
+                path: "enums.SetDateFeedback".into(),
+                message:
+                    "name SetDateFeedback is already used by the feedback structure of code 0x01"
+                        .into()
+            }])),
+            parsed.validate()
+        );
+    }
+
+    #[test]
+    fn empty_type_is_rejected() {
+        let parsed = parse_with_types(
+            "
+  Empty:
+    description: Nothing
+    parameters: []
+",
+            "Empty",
+        );
+
+        assert_eq!(
+            Err(ValidationErrors(vec![ValidationError {
+                path: "types.Empty".into(),
+                message: "type must have at least one parameter".into()
+            }])),
+            parsed.validate()
+        );
+    }
+}
+
+mod bitfields {
+    use crate::yaml_parser::*;
+
+    fn parse_with_bitfield(data_type: &str, level_bits: u32) -> CodesFile {
+        let input = format!(
+            "
+bitfields:
+  Status:
+    description: Status flags
+    data_type: {data_type}
+    fields:
+      - name: charging
+        bits: 1
+        description: Charging
+      - name: level
+        bits: {level_bits}
+        description: Battery level
+codes:
+  0x01:
+    name: SetStatus
+    instruction:
+      description: Sets the status
+      parameters:
+        - name: status
+          data_type: Status
+          description: Status
+"
+        );
+        serde_yaml::from_str(&input).unwrap()
+    }
+
+    #[test]
+    fn parse_bitfields_section() {
+        let parsed = parse_with_bitfield("u8", 3);
+
+        let status = &parsed.bitfields["Status"];
+        assert_eq!(ParameterType::Uint8, status.data_type);
+        assert_eq!(
+            vec![("charging", 0, 0x1), ("level", 1, 0x7)],
+            status
+                .fields_with_offsets()
+                .into_iter()
+                .map(|(f, offset)| (f.name.as_str(), offset, f.mask()))
+                .collect::<Vec<(&str, u32, u64)>>()
+        );
+        assert_eq!(Ok(()), parsed.validate());
+    }
+
+    #[test]
+    fn full_width_field_mask() {
+        let parsed = parse_with_bitfield("u64", 63);
+
+        assert_eq!(u64::MAX >> 1, parsed.bitfields["Status"].fields[1].mask());
+        assert_eq!(Ok(()), parsed.validate());
+    }
+
+    #[test]
+    fn bitfield_overflowing_its_type_is_rejected() {
+        let parsed = parse_with_bitfield("u8", 8);
+
+        assert_eq!(
+            Err(ValidationErrors(vec![ValidationError {
+                path: "bitfields.Status".into(),
+                message: "fields use 9 bits, more than Uint8".into()
+            }])),
+            parsed.validate()
+        );
+    }
+
+    #[test]
+    fn bitfield_based_on_signed_type_is_rejected() {
+        let parsed = parse_with_bitfield("i16", 3);
+
+        assert_eq!(
+            Err(ValidationErrors(vec![ValidationError {
+                path: "bitfields.Status".into(),
+                message: "bitfield must be based on an unsigned integer type, not Int16".into()
+            }])),
+            parsed.validate()
+        );
+    }
+
+    #[test]
+    fn empty_field_is_rejected() {
+        let parsed = parse_with_bitfield("u8", 0);
+
+        assert_eq!(
+            Err(ValidationErrors(vec![ValidationError {
+                path: "bitfields.Status.level".into(),
+                message: "field has no bits".into()
+            }])),
+            parsed.validate()
+        );
+    }
+}
+
+mod validation {
+    use crate::yaml_parser::*;
+
+    fn parse_with_codes(codes: &str) -> CodesFile {
+        let input = format!(
+            "
+enums:
+  SleepMode:
+    description: Sleep depth
+    data_type: u8
+    values:
+      - name: Light
+        value: 0
+        description: Light sleep
+      - name: Deep
+        value: 1
+        description: Deep sleep
+codes:
+{codes}"
+        );
+        serde_yaml::from_str(&input).unwrap()
+    }
+
+    fn frame_with_parameters(code: &str, name: &str, parameters: &[(&str, &str)]) -> String {
+        if parameters.is_empty() {
+            return format!(
+                "
+  {code}:
+    name: {name}
+    instruction:
+      description: Instruction
+      parameters: []"
+            );
+        }
+        format!(
+            "
+  {code}:
+    name: {name}
+    instruction:
+      description: Instruction
+      parameters:{}",
+            parameters
+                .iter()
+                .map(|(name, data_type)| format!(
+                    "
+        - name: {name}
+          data_type: {data_type}
+          description: Parameter"
+                ))
+                .collect::<String>()
+        )
+    }
+
+    fn error_paths(parsed: &CodesFile) -> Vec<String> {
+        parsed
+            .validate()
+            .unwrap_err()
+            .0
+            .into_iter()
+            .map(|e| e.path)
+            .collect()
+    }
+
+    #[test]
+    fn valid_file_passes() {
+        let parsed = parse_with_codes(&frame_with_parameters(
+            "0xff",
+            "SetMode",
+            &[("mode", "SleepMode"), ("readings", "u16[]")],
+        ));
+
+        assert_eq!(Ok(()), parsed.validate());
+    }
+
+    #[test]
+    fn code_above_255_is_rejected() {
+        let parsed = parse_with_codes(&frame_with_parameters("0x100", "SetMode", &[]));
+
+        assert_eq!(
+            Err(ValidationErrors(vec![ValidationError {
+                path: "codes.0x100".into(),
+                message: "code doesn't fit on one byte".into()
+            }])),
+            parsed.validate()
+        );
+    }
+
+    #[test]
+    fn code_must_fit_the_code_width() {
+        let mut parsed = parse_with_codes(&format!(
+            "{}{}",
+            frame_with_parameters("0x100", "SetMode", &[]),
+            frame_with_parameters("0x10000", "Reset", &[])
+        ));
+        parsed.code_width = CodeWidth::Two;
+
+        assert_eq!(
+            Err(ValidationErrors(vec![ValidationError {
+                path: "codes.0x10000".into(),
+                message: "code doesn't fit on two bytes".into()
+            }])),
+            parsed.validate()
+        );
+    }
+
+    #[test]
+    fn duplicate_parameter_is_rejected() {
+        let parsed = parse_with_codes(&frame_with_parameters(
+            "0x01",
+            "SetMode",
+            &[("mode", "u8"), ("mode", "u16")],
+        ));
+
+        assert_eq!(vec!["codes.0x01.instruction.mode"], error_paths(&parsed));
+    }
+
+    #[test]
+    fn invalid_identifiers_are_rejected() {
+        let parsed = parse_with_codes(&frame_with_parameters(
+            "0x01",
+            "\"Set mode\"",
+            &[("2nd", "u8"), ("\"a-b\"", "u8"), ("_", "u8")],
+        ));
+
+        assert_eq!(
+            vec![
+                "codes.0x01.name",
+                "codes.0x01.instruction.2nd",
+                "codes.0x01.instruction.a-b",
+                "codes.0x01.instruction._",
+            ],
+            error_paths(&parsed)
+        );
+    }
+
+    #[test]
+    fn keywords_are_rejected() {
+        let parsed = parse_with_codes(&frame_with_parameters(
+            "0x01",
+            "SetMode",
+            &[
+                ("type", "u8"),
+                ("match", "u8"),
+                ("int", "u8"),
+                ("kind", "u8"),
+            ],
+        ));
+
+        assert_eq!(
+            vec![
+                "codes.0x01.instruction.type",
+                "codes.0x01.instruction.match",
+                "codes.0x01.instruction.int",
+            ],
+            error_paths(&parsed)
+        );
+    }
+
+    #[test]
+    fn names_colliding_with_c_fields_are_rejected() {
+        let parsed = parse_with_codes(&frame_with_parameters(
+            "0x01",
+            "SetMode",
+            &[("readings", "u16[]"), ("readings_count", "u8")],
+        ));
+
+        assert_eq!(
+            vec!["codes.0x01.instruction.readings_count"],
+            error_paths(&parsed)
+        );
+    }
+
+    /// Parameters of the instruction of the `code` frame
+    fn parameters_mut(parsed: &mut CodesFile, code: u32) -> &mut Vec<InstFeedbackParameter> {
+        &mut parsed
+            .codes
+            .get_mut(&code)
+            .unwrap()
+            .instruction
+            .as_mut()
+            .unwrap()
+            .parameters
+    }
+
+    #[test]
+    fn bounds_of_other_types_are_rejected() {
+        let mut parsed = parse_with_codes(&frame_with_parameters(
+            "0x01",
+            "SetMode",
+            &[("mode", "u8"), ("uuid", "u8[16]"), ("readings", "u16[]")],
+        ));
+        let parameters = parameters_mut(&mut parsed, 1);
+        parameters[0].max_length = Some(4);
+        parameters[1].max_count = Some(4);
+        parameters[2].max_count = Some(0);
+
+        assert_eq!(
+            vec![
+                "codes.0x01.instruction.mode",
+                "codes.0x01.instruction.uuid",
+                "codes.0x01.instruction.readings",
+            ],
+            error_paths(&parsed)
+        );
+    }
+
+    #[test]
+    fn allocation_free_code_needs_bounds() {
+        let mut parsed = parse_with_codes(&frame_with_parameters(
+            "0x01",
+            "SetLabels",
+            &[
+                ("label", "string"),
+                ("readings", "u16[]"),
+                ("names", "string[3]"),
+                ("mode", "u8"),
+            ],
+        ));
+
+        assert_eq!(
+            vec![
+                "codes.0x01.instruction.label",
+                "codes.0x01.instruction.readings",
+                "codes.0x01.instruction.names",
+            ],
+            parsed
+                .validate_bounded()
+                .unwrap_err()
+                .0
+                .into_iter()
+                .map(|e| e.path)
+                .collect::<Vec<String>>()
+        );
+
+        let parameters = parameters_mut(&mut parsed, 1);
+        parameters[0].max_length = Some(16);
+        parameters[1].max_count = Some(8);
+        parameters[2].max_length = Some(16);
+        assert_eq!(Ok(()), parsed.validate());
+        assert_eq!(Ok(()), parsed.validate_bounded());
+    }
+
+    #[test]
+    fn duplicate_code_names_are_rejected() {
+        let parsed = parse_with_codes(&format!(
+            "{}{}",
+            frame_with_parameters("0x01", "SetMode", &[]),
+            frame_with_parameters("0x02", "SetMode", &[])
+        ));
+
+        assert_eq!(vec!["codes.0x02.name"], error_paths(&parsed));
+    }
+
+    #[test]
+    fn every_problem_is_reported() {
+        let mut parsed = parse_with_codes(&frame_with_parameters(
+            "0x100",
+            "SetMode",
+            &[("mode", "Unknown"), ("loop", "u8")],
+        ));
+        parsed.enums.get_mut("SleepMode").unwrap().values[1].value = 0;
+
+        assert_eq!(
+            vec![
+                "codes.0x100",
+                "codes.0x100.instruction.loop",
+                "codes.0x100.instruction.mode",
+                "enums.SleepMode.Deep",
+            ],
+            error_paths(&parsed)
+        );
+    }
+
+    #[test]
+    fn every_type_problem_is_reported() {
+        let mut parsed = parse_with_codes(&format!(
+            "{}{}",
+            frame_with_parameters("0x01", "SetMode", &[("mode", "Unknown")]),
+            frame_with_parameters("0x02", "SetLevel", &[("level", "u8"), ("unit", "Unit[]")])
+        ));
+        for value in &mut parsed.enums.get_mut("SleepMode").unwrap().values {
+            value.value += 256;
+        }
+
+        assert_eq!(
+            vec![
+                "codes.0x01.instruction.mode",
+                "codes.0x02.instruction.unit",
+                "enums.SleepMode.Light",
+                "enums.SleepMode.Deep",
+            ],
+            error_paths(&parsed)
+        );
+    }
+}
+
+mod compatibility {
+    use crate::yaml_parser::*;
+
+    fn parse_with_frames(frames: &str) -> CodesFile {
+        let input = format!(
+            "
+enums:
+  SleepMode:
+    description: Sleep depth
+    data_type: u8
+    values:
+      - name: Light
+        value: 0
+        description: Light sleep
+      - name: Deep
+        value: 1
+        description: Deep sleep
+codes:
+  0x01:
+    name: Reset
+    instruction:
+      description: Resets the device
+      parameters: []
+{frames}"
+        );
+        serde_yaml::from_str(&input).unwrap()
+    }
+
+    fn frame(code: &str, name: &str, kind: &str, parameters: &[(&str, &str)]) -> String {
+        format!(
+            "
+  {code}:
+    name: {name}
+    {kind}:
+      description: Frame
+      parameters:{}",
+            parameters
+                .iter()
+                .map(|(name, data_type)| format!(
+                    "
+        - name: {name}
+          data_type: {data_type}
+          description: Parameter"
+                ))
+                .collect::<String>()
+        )
+    }
+
+    fn changes(previous: &CodesFile, current: &CodesFile) -> Vec<(String, ChangeKind)> {
+        current
+            .changes_since(previous)
+            .changes
+            .into_iter()
+            .map(|change| (change.path, change.kind))
+            .collect()
+    }
+
+    #[test]
+    fn same_file_is_compatible() {
+        let parsed = parse_with_frames(&frame(
+            "0x02",
+            "Sleep",
+            "instruction",
+            &[("mode", "SleepMode")],
+        ));
+
+        let report = parsed.changes_since(&parsed);
+
+        assert!(report.compatible);
+        assert!(report.changes.is_empty());
+    }
+
+    #[test]
+    fn added_code_is_compatible() {
+        let previous = parse_with_frames("");
+        let current = parse_with_frames(&frame(
+            "0x02",
+            "Sleep",
+            "instruction",
+            &[("mode", "SleepMode")],
+        ));
+
+        assert!(current.changes_since(&previous).compatible);
+        assert_eq!(
+            vec![("codes.0x02".to_string(), ChangeKind::CodeAdded)],
+            changes(&previous, &current)
+        );
+    }
+
+    #[test]
+    fn removed_code_is_breaking() {
+        let previous = parse_with_frames(&frame(
+            "0x02",
+            "Sleep",
+            "instruction",
+            &[("mode", "SleepMode")],
+        ));
+        let current = parse_with_frames("");
+
+        assert!(!current.changes_since(&previous).compatible);
+        assert_eq!(
+            vec![("codes.0x02".to_string(), ChangeKind::CodeRemoved)],
+            changes(&previous, &current)
+        );
+    }
+
+    #[test]
+    fn renames_are_compatible() {
+        let previous = parse_with_frames(&frame(
+            "0x02",
+            "Sleep",
+            "instruction",
+            &[("mode", "SleepMode")],
+        ));
+        let mut current = parse_with_frames(&frame(
+            "0x02",
+            "Suspend",
+            "instruction",
+            &[("depth", "SleepMode")],
+        ));
+        current.enums.get_mut("SleepMode").unwrap().values[1].name = "Hibernate".into();
+
+        assert!(current.changes_since(&previous).compatible);
+        assert_eq!(
+            vec![
+                ("codes.0x02.name".to_string(), ChangeKind::CodeRenamed),
+                (
+                    "codes.0x02.instruction.depth".to_string(),
+                    ChangeKind::ParameterRenamed
+                ),
+                (
+                    "enums.SleepMode.Hibernate".to_string(),
+                    ChangeKind::EnumValueRenamed
+                ),
+            ],
+            changes(&previous, &current)
+        );
+    }
+
+    #[test]
+    fn reordered_parameters_are_breaking() {
+        let previous = parse_with_frames(&frame(
+            "0x02",
+            "Move",
+            "instruction",
+            &[("x", "u8"), ("y", "u16")],
+        ));
+        let current = parse_with_frames(&frame(
+            "0x02",
+            "Move",
+            "instruction",
+            &[("y", "u16"), ("x", "u8")],
+        ));
+
+        assert!(!current.changes_since(&previous).compatible);
+        assert_eq!(
+            vec![(
+                "codes.0x02.instruction".to_string(),
+                ChangeKind::ParametersReordered
+            )],
+            changes(&previous, &current)
+        );
+    }
+
+    #[test]
+    fn parameter_type_change_is_breaking() {
+        let previous = parse_with_frames(&frame("0x02", "Move", "instruction", &[("x", "u8")]));
+        let current = parse_with_frames(&frame("0x02", "Move", "instruction", &[("x", "u16")]));
+
+        assert!(!current.changes_since(&previous).compatible);
+        assert_eq!(
+            vec![(
+                "codes.0x02.instruction.x".to_string(),
+                ChangeKind::ParameterTypeChanged
+            )],
+            changes(&previous, &current)
+        );
+    }
+
+    #[test]
+    fn enum_value_added_is_breaking_only_in_feedbacks() {
+        let add_value = |codes: &mut CodesFile| {
+            let mut value = codes.enums["SleepMode"].values[1].clone();
+            value.name = "Off".into();
+            value.value = 2;
+            codes.enums.get_mut("SleepMode").unwrap().values.push(value);
+        };
+
+        let sleep = frame("0x02", "Sleep", "instruction", &[("mode", "SleepMode")]);
+        let previous = parse_with_frames(&sleep);
+        let mut current = parse_with_frames(&sleep);
+        add_value(&mut current);
+        assert!(current.changes_since(&previous).compatible);
+
+        let sleep = frame("0x02", "Sleep", "feedback", &[("mode", "SleepMode")]);
+        let previous = parse_with_frames(&sleep);
+        let mut current = parse_with_frames(&sleep);
+        add_value(&mut current);
+        assert!(!current.changes_since(&previous).compatible);
+        assert_eq!(
+            vec![(
+                "enums.SleepMode.Off".to_string(),
+                ChangeKind::EnumValueAdded
+            )],
+            changes(&previous, &current)
+        );
+    }
+
+    #[test]
+    fn parameter_bounds_change_is_breaking() {
+        let previous = parse_with_frames(&frame(
+            "0x02",
+            "Label",
+            "instruction",
+            &[("text", "string"), ("values", "u8[]")],
+        ));
+        let mut current = parse_with_frames(&frame(
+            "0x02",
+            "Label",
+            "instruction",
+            &[("text", "string"), ("values", "u8[]")],
+        ));
+        let parameters = &mut current
+            .codes
+            .get_mut(&2)
+            .unwrap()
+            .instruction
+            .as_mut()
+            .unwrap()
+            .parameters;
+        parameters[0].max_length = Some(16);
+        parameters[1].max_count = Some(4);
+
+        let report = current.changes_since(&previous);
+
+        assert!(!report.compatible);
+        assert_eq!(
+            vec![
+                "Parameter text max_length changed from none to 16",
+                "Parameter values max_count changed from none to 4",
+            ],
+            report
+                .changes
+                .iter()
+                .map(|change| change.description.as_str())
+                .collect::<Vec<&str>>()
+        );
+        assert_eq!(
+            vec![
+                (
+                    "codes.0x02.instruction.text".to_string(),
+                    ChangeKind::ParameterBoundsChanged
+                ),
+                (
+                    "codes.0x02.instruction.values".to_string(),
+                    ChangeKind::ParameterBoundsChanged
+                ),
+            ],
+            changes(&previous, &current)
+        );
+    }
+
+    #[test]
+    fn settings_changes_use_the_input_file_names() {
+        let previous = parse_with_frames("");
+        let mut current = parse_with_frames("");
+        current.endianness = Endianness::Big;
+        current.checksum = Some(Checksum::Crc16);
+        current.framing = Some(Framing::SyncLength);
+
+        assert_eq!(
+            vec![
+                "Endianness changed from little to big",
+                "Checksum changed from none to crc16",
+                "Framing changed from none to sync",
+            ],
+            current
+                .changes_since(&previous)
+                .changes
+                .iter()
+                .map(|change| change.description.as_str())
+                .collect::<Vec<&str>>()
+        );
+    }
+
+    #[test]
+    fn settings_names_are_parsed_back() {
+        for framing in [Framing::Cobs, Framing::Slip, Framing::SyncLength] {
+            assert_eq!(framing, serde_yaml::from_str(&framing.to_string()).unwrap());
+        }
+        for checksum in [Checksum::Crc8, Checksum::Crc16, Checksum::Crc32] {
+            assert_eq!(
+                checksum,
+                serde_yaml::from_str(&checksum.to_string()).unwrap()
+            );
+        }
+        for endianness in [Endianness::Little, Endianness::Big] {
+            assert_eq!(
+                endianness,
+                serde_yaml::from_str(&endianness.to_string()).unwrap()
+            );
+        }
+    }
 }
@@ -2,7 +2,7 @@ use std::convert::TryFrom;
 use std::fmt::{self, Debug, Formatter};
 
 /// Parameter type internal reprensentation
-#[derive(PartialEq, Eq, Clone, Copy)]
+#[derive(PartialEq, Eq, Clone)]
 pub enum ParameterType {
     Uint64,
     Uint32,
@@ -12,13 +12,33 @@ pub enum ParameterType {
     Int32,
     Int16,
     Int8,
+    Float32,
+    Float64,
     Bool,
     String,
+    /// Variable-length array, prefixed on the wire by its item count
+    /// encoded with the `length` unsigned type.
+    Array {
+        item: Box<ParameterType>,
+        length: Box<ParameterType>,
+    },
+    /// Fixed-length array, sent without length on the wire
+    FixedArray {
+        item: Box<ParameterType>,
+        count: usize,
+    },
+    /// Reference to a type declared in the input file, by name
+    Custom(String),
 }
 
 impl ParameterType {
-    pub fn to_rust_type_string(self) -> String {
+    pub fn to_rust_type_string(&self) -> String {
         match self {
+            Self::Array { item, .. } => return format!("Vec<{}>", item.to_rust_type_string()),
+            Self::FixedArray { item, count } => {
+                return format!("[{}; {count}]", item.to_rust_type_string());
+            }
+            Self::Custom(name) => return name.clone(),
             Self::String => "String",
             Self::Uint64 => "u64",
             Self::Uint32 => "u32",
@@ -28,13 +48,45 @@ impl ParameterType {
             Self::Int32 => "i32",
             Self::Int16 => "i16",
             Self::Int8 => "i8",
+            Self::Float32 => "f32",
+            Self::Float64 => "f64",
             Self::Bool => "bool",
         }
         .into()
     }
 
-    pub fn to_cpp_type_string(self) -> String {
+    /// Type name as written in the input file
+    pub fn to_input_string(&self) -> String {
         match self {
+            ParameterType::Array { item, length } if **length == ParameterType::Uint8 => {
+                format!("{}[]", item.to_input_string())
+            }
+            ParameterType::Array { item, length } => {
+                format!("{}[{}]", item.to_input_string(), length.to_input_string())
+            }
+            ParameterType::FixedArray { item, count } => {
+                format!("{}[{count}]", item.to_input_string())
+            }
+            ParameterType::Custom(name) => name.clone(),
+            ParameterType::String => "string".to_string(),
+            // The Rust names are the input file names
+            scalar => scalar.to_rust_type_string(),
+        }
+    }
+
+    pub fn to_cpp_type_string(&self) -> String {
+        match self {
+            Self::Array { item, .. } => {
+                let item = item.to_cpp_type_string();
+                return if item.ends_with('*') {
+                    format!("{item}*")
+                } else {
+                    format!("{item} *")
+                };
+            }
+            // The items count is part of the declarator, see `c_array_suffix`
+            Self::FixedArray { item, .. } => return item.to_cpp_type_string(),
+            Self::Custom(name) => return name.clone(),
             Self::String => "char *",
             Self::Uint64 => "uint64_t",
             Self::Uint32 => "uint32_t",
@@ -44,14 +96,18 @@ impl ParameterType {
             Self::Int32 => "int32_t",
             Self::Int16 => "int16_t",
             Self::Int8 => "int8_t",
+            Self::Float32 => "float",
+            Self::Float64 => "double",
             Self::Bool => "bool",
         }
         .into()
     }
 
-    pub fn size(self) -> usize {
+    pub fn size(&self) -> usize {
         match self {
-            Self::String => 0,
+            // Custom types size depends on their declaration
+            Self::String | Self::Array { .. } | Self::Custom(_) => 0,
+            Self::FixedArray { item, count } => item.size() * count,
             Self::Uint64 => 8,
             Self::Uint32 => 4,
             Self::Uint16 => 2,
@@ -60,6 +116,8 @@ impl ParameterType {
             Self::Int32 => 4,
             Self::Int16 => 2,
             Self::Int8 => 1,
+            Self::Float32 => 4,
+            Self::Float64 => 8,
             Self::Bool => 1,
         }
     }
@@ -68,6 +126,9 @@ impl ParameterType {
 impl fmt::Display for ParameterType {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         f.write_str(match self {
+            Self::Array { item, length } => return write!(f, "{item}[{length}]"),
+            Self::FixedArray { item, count } => return write!(f, "{item}[{count}]"),
+            Self::Custom(name) => name.as_str(),
             Self::String => "String",
             Self::Uint64 => "Uint64",
             Self::Uint32 => "Uint32",
@@ -77,6 +138,8 @@ impl fmt::Display for ParameterType {
             Self::Int32 => "Int32",
             Self::Int16 => "Int16",
             Self::Int8 => "Int8",
+            Self::Float32 => "Float32",
+            Self::Float64 => "Float64",
             Self::Bool => "Bool",
         })
     }
@@ -92,18 +155,91 @@ impl TryFrom<String> for ParameterType {
     type Error = String;
 
     fn try_from(from: String) -> Result<Self, Self::Error> {
+        if let Some(array) = from.strip_suffix(']') {
+            return Self::array_try_from(array);
+        }
+
         match from.to_lowercase().as_str() {
             "u8" | "uint8" | "byte" => Ok(Self::Uint8),
             "u16" | "uint16" => Ok(Self::Uint16),
             "u32" | "uint32" => Ok(Self::Uint32),
             "u64" | "uint64" => Ok(Self::Uint64),
             "i8" | "int8" => Ok(Self::Int8),
+            "f32" | "float32" | "float" => Ok(Self::Float32),
+            "f64" | "float64" | "double" => Ok(Self::Float64),
             "i16" | "int16" => Ok(Self::Int16),
             "i32" | "int32" => Ok(Self::Int32),
             "i64" | "int64" => Ok(Self::Int64),
             "string" | "str" => Ok(Self::String),
             "bool" | "boolean" => Ok(Self::Bool),
+            _ if Self::is_custom_type_name(&from) => Ok(Self::Custom(from)),
             _ => Err(format!("Unknown type {from}")),
         }
     }
 }
+
+impl ParameterType {
+    /// Name of the custom type referenced by this type or its array items
+    pub fn custom_type_name(&self) -> Option<&str> {
+        match self {
+            Self::Custom(name) => Some(name),
+            Self::Array { item, .. } | Self::FixedArray { item, .. } => item.custom_type_name(),
+            _ => None,
+        }
+    }
+
+    /// Custom types are CamelCase identifiers, which keeps them apart from the
+    /// case-insensitive builtin types
+    fn is_custom_type_name(name: &str) -> bool {
+        name.starts_with(|c: char| c.is_ascii_uppercase())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    }
+
+    /// C declarator suffix giving the items count of fixed-length arrays
+    pub fn c_array_suffix(&self) -> String {
+        match self {
+            Self::FixedArray { count, .. } => format!("[{count}]"),
+            _ => String::new(),
+        }
+    }
+
+    /// Parses the `item[length]` and `item[count]` array syntaxes, the trailing `]`
+    /// being already removed.
+    fn array_try_from(array: &str) -> Result<Self, String> {
+        let (item, length) = array
+            .rsplit_once('[')
+            .ok_or_else(|| format!("Unbalanced brackets in type {array}]"))?;
+
+        let item = Self::try_from(String::from(item))?;
+        if let Self::Array { .. } | Self::FixedArray { .. } = item {
+            return Err(format!("Nested arrays are not supported: {array}]"));
+        }
+
+        if let Ok(count) = length.parse::<usize>() {
+            return if count > 0 {
+                Ok(Self::FixedArray {
+                    item: Box::new(item),
+                    count,
+                })
+            } else {
+                Err(format!("Fixed arrays can't be empty: {array}]"))
+            };
+        }
+
+        let length = if length.is_empty() {
+            Self::Uint8
+        } else {
+            Self::try_from(String::from(length))?
+        };
+
+        match length {
+            Self::Uint8 | Self::Uint16 | Self::Uint32 => Ok(Self::Array {
+                item: Box::new(item),
+                length: Box::new(length),
+            }),
+            _ => Err(format!(
+                "Array length must be u8, u16 or u32, not {length} in {array}]"
+            )),
+        }
+    }
+}
@@ -0,0 +1,472 @@
+use crate::yaml_parser::{CodeWidth, CodesFile, InstFeedback, ParameterType};
+use std::collections::BTreeMap;
+use std::error;
+use std::fmt;
+
+/// Keywords of the generated languages, which can't be used as names
+const RESERVED_WORDS: &[&str] = &[
+    // Rust strict and reserved keywords
+    "Self",
+    "abstract",
+    "as",
+    "async",
+    "await",
+    "become",
+    "box",
+    "break",
+    "const",
+    "continue",
+    "crate",
+    "do",
+    "dyn",
+    "else",
+    "enum",
+    "extern",
+    "false",
+    "final",
+    "fn",
+    "for",
+    "gen",
+    "if",
+    "impl",
+    "in",
+    "let",
+    "loop",
+    "macro",
+    "match",
+    "mod",
+    "move",
+    "mut",
+    "override",
+    "priv",
+    "pub",
+    "ref",
+    "return",
+    "self",
+    "static",
+    "struct",
+    "super",
+    "trait",
+    "true",
+    "try",
+    "type",
+    "typeof",
+    "unsafe",
+    "unsized",
+    "use",
+    "virtual",
+    "where",
+    "while",
+    "yield",
+    // C keywords not reserved by Rust
+    "_Bool",
+    "_Complex",
+    "_Imaginary",
+    "auto",
+    "bool",
+    "case",
+    "char",
+    "default",
+    "double",
+    "float",
+    "goto",
+    "inline",
+    "int",
+    "long",
+    "register",
+    "restrict",
+    "short",
+    "signed",
+    "sizeof",
+    "switch",
+    "typedef",
+    "union",
+    "unsigned",
+    "void",
+    "volatile",
+];
+
+/// Problem found in the input file, located by its path in the file
+#[derive(Debug, PartialEq, Clone)]
+pub struct ValidationError {
+    /// Dotted path of the faulty item, such as `codes.0x05.instruction.readings`
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+impl error::Error for ValidationError {}
+
+/// Every problem found in an input file
+#[derive(Debug, PartialEq)]
+pub struct ValidationErrors(pub Vec<ValidationError>);
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let lines = self
+            .0
+            .iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<String>>();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl error::Error for ValidationErrors {}
+
+impl CodesFile {
+    /// Checks the input file produces valid code in every output language, reporting
+    /// all the problems found
+    pub fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = Vec::new();
+        let mut code_names: BTreeMap<&str, u32> = BTreeMap::new();
+        for (&id, code) in &self.codes {
+            let path = format!("codes.{id:#04x}");
+            if id > self.code_width.max_code() {
+                push(
+                    &mut errors,
+                    &path,
+                    &format!(
+                        "code doesn't fit on {}",
+                        match self.code_width {
+                            CodeWidth::One => "one byte",
+                            CodeWidth::Two => "two bytes",
+                            CodeWidth::Four => "four bytes",
+                        }
+                    ),
+                );
+            }
+            check_name(&mut errors, &format!("{path}.name"), &code.name);
+            if let Some(first) = code_names.insert(&code.name, id) {
+                push(
+                    &mut errors,
+                    &format!("{path}.name"),
+                    &format!("name {} is already used by code {first:#04x}", code.name),
+                );
+            }
+            if let Some(instruction) = &code.instruction {
+                check_parameters(
+                    &mut errors,
+                    self,
+                    &format!("{path}.instruction"),
+                    instruction,
+                );
+            }
+            if let Some(feedback) = &code.feedback {
+                check_parameters(&mut errors, self, &format!("{path}.feedback"), feedback);
+            }
+        }
+        // The Rust code answers the instructions with a `<Name>Feedback` structure
+        let feedback_structures: BTreeMap<String, u32> = self
+            .codes
+            .iter()
+            .filter(|(_, code)| code.instruction.is_some() && code.feedback.is_some())
+            .map(|(&id, code)| (format!("{}Feedback", code.name), id))
+            .collect();
+
+        for (name, enumeration) in &self.enums {
+            let path = format!("enums.{name}");
+            check_name(&mut errors, &path, name);
+            check_feedback_structure(&mut errors, &feedback_structures, &path, name);
+            check_unique_names(
+                &mut errors,
+                &path,
+                enumeration.values.iter().map(|v| v.name.as_str()),
+            );
+            let range = enumeration.value_range();
+            if range.is_none() {
+                push(
+                    &mut errors,
+                    &path,
+                    &format!(
+                        "enumeration must be based on an integer type up to 32 bits, not {}",
+                        enumeration.data_type
+                    ),
+                );
+            }
+            let mut values: BTreeMap<i64, &str> = BTreeMap::new();
+            for value in &enumeration.values {
+                let value_path = format!("{path}.{}", value.name);
+                if let Some((min, max)) = range
+                    && (value.value < min || value.value > max)
+                {
+                    push(
+                        &mut errors,
+                        &value_path,
+                        &format!(
+                            "value {} doesn't fit in {}",
+                            value.value, enumeration.data_type
+                        ),
+                    );
+                }
+                if let Some(first) = values.insert(value.value, &value.name) {
+                    push(
+                        &mut errors,
+                        &value_path,
+                        &format!("value {} is already used by {first}", value.value),
+                    );
+                }
+            }
+        }
+
+        for (name, bitfield) in &self.bitfields {
+            let path = format!("bitfields.{name}");
+            check_name(&mut errors, &path, name);
+            check_feedback_structure(&mut errors, &feedback_structures, &path, name);
+            if self.enums.contains_key(name) {
+                push(
+                    &mut errors,
+                    &path,
+                    &format!("name {name} is already used by an enumeration"),
+                );
+            }
+            check_unique_names(
+                &mut errors,
+                &path,
+                bitfield.fields.iter().map(|f| f.name.as_str()),
+            );
+            match bitfield.width() {
+                Some(width) => {
+                    let used: u32 = bitfield.fields.iter().map(|f| f.bits).sum();
+                    if used > width {
+                        push(
+                            &mut errors,
+                            &path,
+                            &format!("fields use {used} bits, more than {}", bitfield.data_type),
+                        );
+                    }
+                }
+                None => push(
+                    &mut errors,
+                    &path,
+                    &format!(
+                        "bitfield must be based on an unsigned integer type, not {}",
+                        bitfield.data_type
+                    ),
+                ),
+            }
+            for field in bitfield.fields.iter().filter(|f| f.bits == 0) {
+                push(
+                    &mut errors,
+                    &format!("{path}.{}", field.name),
+                    "field has no bits",
+                );
+            }
+        }
+
+        for (name, structure) in &self.types {
+            let path = format!("types.{name}");
+            check_name(&mut errors, &path, name);
+            check_feedback_structure(&mut errors, &feedback_structures, &path, name);
+            if self.enums.contains_key(name) || self.bitfields.contains_key(name) {
+                push(
+                    &mut errors,
+                    &path,
+                    &format!("name {name} is already used by an enumeration or a bitfield"),
+                );
+            }
+            if structure.parameters.is_empty() {
+                push(&mut errors, &path, "type must have at least one parameter");
+            }
+            check_parameters(&mut errors, self, &path, structure);
+        }
+        if let Err(message) = self.types_in_dependency_order() {
+            push(&mut errors, "types", &message);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationErrors(errors))
+        }
+    }
+
+    /// Checks every string has a `max_length` and every variable-length array a
+    /// `max_count`, as needed to store them in place in the allocation-free C code
+    pub fn validate_bounded(&self) -> Result<(), ValidationErrors> {
+        let mut errors = Vec::new();
+        for (&id, code) in &self.codes {
+            let path = format!("codes.{id:#04x}");
+            if let Some(instruction) = &code.instruction {
+                check_bounds(&mut errors, &format!("{path}.instruction"), instruction);
+            }
+            if let Some(feedback) = &code.feedback {
+                check_bounds(&mut errors, &format!("{path}.feedback"), feedback);
+            }
+        }
+        for (name, structure) in &self.types {
+            check_bounds(&mut errors, &format!("types.{name}"), structure);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationErrors(errors))
+        }
+    }
+}
+
+fn push(errors: &mut Vec<ValidationError>, path: &str, message: &str) {
+    errors.push(ValidationError {
+        path: path.to_string(),
+        message: message.to_string(),
+    });
+}
+
+/// Checks the names, types and bounds of the parameters, along with the names the C code
+/// derives from them
+fn check_parameters(
+    errors: &mut Vec<ValidationError>,
+    codes: &CodesFile,
+    path: &str,
+    frame: &InstFeedback,
+) {
+    check_unique_names(
+        errors,
+        path,
+        frame.parameters.iter().map(|p| p.name.as_str()),
+    );
+
+    for parameter in &frame.parameters {
+        let mut derived = Vec::new();
+        if parameter.optional {
+            derived.push(parameter.c_presence_name());
+        }
+        if let ParameterType::Array { .. } = parameter.data_type {
+            derived.push(parameter.c_array_count_name());
+        }
+        let parameter_path = format!("{path}.{}", parameter.name);
+        if let Some(type_name) = parameter.data_type.custom_type_name()
+            && !codes.enums.contains_key(type_name)
+            && !codes.bitfields.contains_key(type_name)
+            && !codes.types.contains_key(type_name)
+        {
+            push(
+                errors,
+                &parameter_path,
+                &format!("unknown type {type_name}"),
+            );
+        }
+        let (holds_strings, is_array) = match &parameter.data_type {
+            ParameterType::String => (true, false),
+            ParameterType::Array { item, .. } => (**item == ParameterType::String, true),
+            ParameterType::FixedArray { item, .. } => (**item == ParameterType::String, false),
+            _ => (false, false),
+        };
+        if parameter.max_length.is_some() && !holds_strings {
+            push(
+                errors,
+                &parameter_path,
+                "max_length is only allowed on strings",
+            );
+        }
+        match parameter.max_count {
+            Some(_) if !is_array => push(
+                errors,
+                &parameter_path,
+                "max_count is only allowed on variable-length arrays",
+            ),
+            Some(0) => push(errors, &parameter_path, "max_count must be at least 1"),
+            _ => (),
+        }
+
+        for derived_name in derived {
+            if frame.parameters.iter().any(|p| p.name == derived_name) {
+                push(
+                    errors,
+                    &format!("{path}.{derived_name}"),
+                    &format!(
+                        "name collides with the C field generated for {}",
+                        parameter.name
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Checks the strings and the variable-length arrays of the parameters are bounded
+fn check_bounds(errors: &mut Vec<ValidationError>, path: &str, frame: &InstFeedback) {
+    for parameter in &frame.parameters {
+        let path = format!("{path}.{}", parameter.name);
+        let item = match &parameter.data_type {
+            ParameterType::Array { item, .. } => {
+                if parameter.max_count.is_none() {
+                    push(
+                        errors,
+                        &path,
+                        "variable-length array needs a max_count to be stored in place",
+                    );
+                }
+                item
+            }
+            ParameterType::FixedArray { item, .. } => item,
+            data_type => data_type,
+        };
+        if *item == ParameterType::String && parameter.max_length.is_none() {
+            push(
+                errors,
+                &path,
+                "string needs a max_length to be stored in place",
+            );
+        }
+    }
+}
+
+/// Checks every name is valid and used once
+fn check_unique_names<'a>(
+    errors: &mut Vec<ValidationError>,
+    path: &str,
+    names: impl Iterator<Item = &'a str>,
+) {
+    let mut seen: Vec<&str> = Vec::new();
+    for name in names {
+        let item_path = format!("{path}.{name}");
+        check_name(errors, &item_path, name);
+        if seen.contains(&name) {
+            push(errors, &item_path, "name is used more than once");
+        }
+        seen.push(name);
+    }
+}
+
+/// Checks a type name isn't the name of the `<Name>Feedback` structure generated for a code
+fn check_feedback_structure(
+    errors: &mut Vec<ValidationError>,
+    feedback_structures: &BTreeMap<String, u32>,
+    path: &str,
+    name: &str,
+) {
+    if let Some(id) = feedback_structures.get(name) {
+        push(
+            errors,
+            path,
+            &format!("name {name} is already used by the feedback structure of code {id:#04x}"),
+        );
+    }
+}
+
+/// Checks a name is an identifier of the generated languages, and not one of their keywords
+fn check_name(errors: &mut Vec<ValidationError>, path: &str, name: &str) {
+    let valid = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && name != "_";
+    if !valid {
+        push(
+            errors,
+            path,
+            &format!("{name:?} isn't a valid identifier, use letters, digits and underscores"),
+        );
+    } else if RESERVED_WORDS.contains(&name) {
+        push(errors, path, &format!("{name} is a reserved keyword"));
+    }
+}